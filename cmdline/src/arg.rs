@@ -0,0 +1,110 @@
+// What kind of argument an Arg is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Kind {
+    // --name on its own.
+    Flag,
+    // --name VALUE or --name=VALUE.
+    Option,
+    // A bare value, matched by position.
+    Positional,
+}
+
+// The declaration of a single argument.
+#[derive(Debug, Clone)]
+pub struct Arg {
+    pub(crate) name: String,
+    pub(crate) kind: Kind,
+    pub(crate) short: Option<char>,
+    pub(crate) value_name: Option<String>,
+    pub(crate) help: String,
+    pub(crate) default: Option<String>,
+    pub(crate) choices: Vec<String>,
+    pub(crate) required: bool,
+    pub(crate) repeatable: bool,
+}
+
+impl Arg {
+    fn new(name: &str, kind: Kind) -> Arg {
+        Arg {
+            name: name.to_string(),
+            kind,
+            short: None,
+            value_name: None,
+            help: String::new(),
+            default: None,
+            choices: Vec::new(),
+            required: false,
+            repeatable: false,
+        }
+    }
+
+    // A switch that is either present or not, given as --name.
+    pub fn flag(name: &str) -> Arg {
+        Arg::new(name, Kind::Flag)
+    }
+
+    // An option that takes a value, given as --name VALUE or --name=VALUE.
+    pub fn option(name: &str) -> Arg {
+        Arg::new(name, Kind::Option)
+    }
+
+    // A value given without a name. Positionals are matched in the order
+    // they are declared.
+    pub fn positional(name: &str) -> Arg {
+        Arg::new(name, Kind::Positional)
+    }
+
+    // A single letter alternative, given as -c.
+    pub fn short(mut self, short: char) -> Arg {
+        self.short = Some(short);
+        self
+    }
+
+    // What to call the value in the help, e.g. N in --seed N.
+    pub fn value_name(mut self, value_name: &str) -> Arg {
+        self.value_name = Some(value_name.to_string());
+        self
+    }
+
+    pub fn help(mut self, help: &str) -> Arg {
+        self.help = help.to_string();
+        self
+    }
+
+    // The value used when the argument isn't given.
+    pub fn default(mut self, default: &str) -> Arg {
+        self.default = Some(default.to_string());
+        self
+    }
+
+    // Restricts the value to one of the given strings.
+    pub fn choices(mut self, choices: &[&str]) -> Arg {
+        self.choices = choices.iter().map(|c| c.to_string()).collect();
+        self
+    }
+
+    pub fn required(mut self) -> Arg {
+        self.required = true;
+        self
+    }
+
+    // Allows an option to be given more than once, or a positional to take
+    // every remaining value.
+    pub fn repeatable(mut self) -> Arg {
+        self.repeatable = true;
+        self
+    }
+
+    // How the argument is written in messages, --name for flags and options
+    // and NAME for positionals.
+    pub(crate) fn display_name(&self) -> String {
+        match self.kind {
+            Kind::Positional => self.placeholder(),
+            _ => format!("--{}", self.name),
+        }
+    }
+
+    pub(crate) fn placeholder(&self) -> String {
+        self.value_name.clone().unwrap_or_else(|| self.name.to_uppercase())
+    }
+}
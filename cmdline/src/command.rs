@@ -0,0 +1,617 @@
+use std::collections::HashMap;
+
+use crate::arg::{Arg, Kind};
+use crate::error::Error;
+use crate::matches::Matches;
+
+// A program, or one of its subcommands, and the arguments it accepts.
+#[derive(Debug, Clone)]
+pub struct Command {
+    name: String,
+    about: String,
+    args: Vec<Arg>,
+    subcommands: Vec<Command>,
+    // The full name used in usage lines, e.g. "tutor quiz" for a subcommand.
+    path: String,
+}
+
+impl Command {
+    pub fn new(name: &str) -> Command {
+        Command {
+            name: name.to_string(),
+            about: String::new(),
+            args: Vec::new(),
+            subcommands: Vec::new(),
+            path: name.to_string(),
+        }
+    }
+
+    // A line describing the command, shown at the top of its help and in the
+    // list of subcommands.
+    pub fn about(mut self, about: &str) -> Command {
+        self.about = about.to_string();
+        self
+    }
+
+    pub fn arg(mut self, arg: Arg) -> Command {
+        assert!(
+            !self.args.iter().any(|a| a.name == arg.name),
+            "{} is declared twice",
+            arg.name
+        );
+        assert!(
+            arg.kind != Kind::Flag || arg.default.is_none(),
+            "flag --{} can't have a default",
+            arg.name
+        );
+
+        self.args.push(arg);
+        self
+    }
+
+    pub fn subcommand(mut self, mut subcommand: Command) -> Command {
+        subcommand.set_path(&self.path);
+        self.subcommands.push(subcommand);
+        self
+    }
+
+    fn set_path(&mut self, parent: &str) {
+        self.path = format!("{} {}", parent, self.name);
+
+        let path = self.path.clone();
+
+        for subcommand in &mut self.subcommands {
+            subcommand.set_path(&path);
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // Parses the arguments, exiting with the help or an error message if they
+    // can't be. See Error::exit.
+    pub fn parse_or_exit<I, S>(&self, args: I) -> Matches
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.parse(args).unwrap_or_else(|e| e.exit())
+    }
+
+    pub fn parse<I, S>(&self, args: I) -> Result<Matches, Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let args: Vec<String> = args.into_iter().map(Into::into).collect();
+
+        self.parse_from(&args)
+    }
+
+    fn parse_from(&self, args: &[String]) -> Result<Matches, Error> {
+        let mut values: HashMap<String, Vec<String>> = HashMap::new();
+        let mut flags: HashMap<String, usize> = HashMap::new();
+        let mut positionals = Vec::new();
+        let mut subcommand = None;
+        let mut options_ended = false;
+
+        let mut i = 0;
+
+        while i < args.len() {
+            let arg = &args[i];
+            i += 1;
+
+            if options_ended || arg == "-" || !arg.starts_with('-') {
+                if !self.subcommands.is_empty() && positionals.len() == self.positional_count() {
+                    let sub = self.find_subcommand(arg)?;
+                    let matches = sub.parse_from(&args[i..])?;
+
+                    subcommand = Some((sub.name.clone(), Box::new(matches)));
+                    break;
+                }
+
+                positionals.push(arg.clone());
+                continue;
+            }
+
+            if arg == "--" {
+                options_ended = true;
+                continue;
+            }
+
+            if arg == "--help" || arg == "-h" {
+                return Err(Error::Help(self.help()));
+            }
+
+            let (declared, inline) = self.find_option(arg)?;
+
+            match declared.kind {
+                Kind::Flag => {
+                    if inline.is_some() {
+                        return Err(self.error(format!("{} doesn't take a value", declared.display_name())));
+                    }
+
+                    *flags.entry(declared.name.clone()).or_insert(0) += 1;
+                }
+                Kind::Option => {
+                    let value = match inline {
+                        Some(value) => value,
+                        None => {
+                            let value = args.get(i).ok_or_else(|| {
+                                self.error(format!(
+                                    "{} needs a value, {}",
+                                    declared.display_name(),
+                                    declared.placeholder()
+                                ))
+                            })?;
+
+                            i += 1;
+                            value.clone()
+                        }
+                    };
+
+                    self.check_choice(declared, &value)?;
+
+                    let given = values.entry(declared.name.clone()).or_default();
+
+                    if !given.is_empty() && !declared.repeatable {
+                        return Err(self.error(format!("{} given more than once", declared.display_name())));
+                    }
+
+                    given.push(value);
+                }
+                Kind::Positional => unreachable!("positionals are never looked up as options"),
+            }
+        }
+
+        self.assign_positionals(positionals, &mut values)?;
+
+        if subcommand.is_none() && !self.subcommands.is_empty() {
+            return Err(self.error(format!("a command is needed, one of {}", self.subcommand_names())));
+        }
+
+        for arg in &self.args {
+            if arg.required && !values.contains_key(&arg.name) {
+                return Err(self.error(format!("{} is required", arg.display_name())));
+            }
+        }
+
+        Ok(Matches {
+            values,
+            flags,
+            args: self.args.clone(),
+            usage: self.usage(),
+            subcommand,
+        })
+    }
+
+    fn positional_count(&self) -> usize {
+        self.args.iter().filter(|a| a.kind == Kind::Positional).count()
+    }
+
+    fn assign_positionals(
+        &self,
+        given: Vec<String>,
+        values: &mut HashMap<String, Vec<String>>,
+    ) -> Result<(), Error> {
+        let mut given = given.into_iter();
+
+        for arg in self.args.iter().filter(|a| a.kind == Kind::Positional) {
+            let taken: Vec<String> = if arg.repeatable {
+                given.by_ref().collect()
+            } else {
+                given.next().into_iter().collect()
+            };
+
+            for value in &taken {
+                self.check_choice(arg, value)?;
+            }
+
+            if !taken.is_empty() {
+                values.insert(arg.name.clone(), taken);
+            }
+        }
+
+        match given.next() {
+            Some(extra) => Err(self.error(format!("unexpected argument {:?}", extra))),
+            None => Ok(()),
+        }
+    }
+
+    // Finds the option for --name, --name=value or -n, returning the value if
+    // it was given inline.
+    fn find_option(&self, arg: &str) -> Result<(&Arg, Option<String>), Error> {
+        let options = self.args.iter().filter(|a| a.kind != Kind::Positional);
+
+        if let Some(long) = arg.strip_prefix("--") {
+            let (name, inline) = match long.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (long, None),
+            };
+
+            return match options.clone().find(|a| a.name == name) {
+                Some(declared) => Ok((declared, inline)),
+                None => {
+                    let names = options.map(|a| a.name.as_str());
+                    let hint = suggest(name, names)
+                        .map(|s| format!(", did you mean --{}?", s))
+                        .unwrap_or_default();
+
+                    Err(self.error(format!("unknown option --{}{}", name, hint)))
+                }
+            };
+        }
+
+        let short = &arg[1..];
+        let mut chars = short.chars();
+
+        match (chars.next(), chars.as_str()) {
+            (Some(c), rest) => match options.clone().find(|a| a.short == Some(c)) {
+                // -n5 is the same as -n 5 for options.
+                Some(declared) if !rest.is_empty() && declared.kind == Kind::Option => {
+                    Ok((declared, Some(rest.to_string())))
+                }
+                Some(declared) if rest.is_empty() => Ok((declared, None)),
+                _ => Err(self.error(format!("unknown option {}", arg))),
+            },
+            (None, _) => Err(self.error(format!("unknown option {}", arg))),
+        }
+    }
+
+    fn find_subcommand(&self, name: &str) -> Result<&Command, Error> {
+        if let Some(sub) = self.subcommands.iter().find(|s| s.name == name) {
+            return Ok(sub);
+        }
+
+        let hint = suggest(name, self.subcommands.iter().map(|s| s.name.as_str()))
+            .map(|s| format!(", did you mean {}?", s))
+            .unwrap_or_else(|| format!(", expected one of {}", self.subcommand_names()));
+
+        Err(self.error(format!("unknown command {:?}{}", name, hint)))
+    }
+
+    fn subcommand_names(&self) -> String {
+        self.subcommands.iter().map(|s| s.name.as_str()).collect::<Vec<_>>().join(", ")
+    }
+
+    fn check_choice(&self, arg: &Arg, value: &str) -> Result<(), Error> {
+        if arg.choices.is_empty() || arg.choices.iter().any(|c| c == value) {
+            return Ok(());
+        }
+
+        Err(self.error(format!(
+            "invalid value {:?} for {}, expected one of {}",
+            value,
+            arg.display_name(),
+            arg.choices.join(", ")
+        )))
+    }
+
+    fn error(&self, message: String) -> Error {
+        Error::Invalid { message, usage: self.usage() }
+    }
+
+    pub fn usage(&self) -> String {
+        let mut usage = format!("usage: {}", self.path);
+
+        if self.args.iter().any(|a| a.kind != Kind::Positional) {
+            usage += " [options]";
+        }
+
+        for arg in self.args.iter().filter(|a| a.kind == Kind::Positional) {
+            let mut placeholder = arg.placeholder();
+
+            if arg.repeatable {
+                placeholder += "...";
+            }
+
+            if arg.required {
+                usage += &format!(" {}", placeholder);
+            } else {
+                usage += &format!(" [{}]", placeholder);
+            }
+        }
+
+        if !self.subcommands.is_empty() {
+            usage += " COMMAND";
+        }
+
+        usage
+    }
+
+    // The help for the command, built from its declarations.
+    pub fn help(&self) -> String {
+        let mut help = String::new();
+
+        if !self.about.is_empty() {
+            help += &format!("{}\n\n", self.about);
+        }
+
+        help += &self.usage();
+        help += "\n";
+
+        let mut options: Vec<(String, String)> = self.args
+            .iter()
+            .filter(|a| a.kind != Kind::Positional)
+            .map(|a| {
+                let short = a.short.map_or_else(|| "    ".to_string(), |c| format!("-{}, ", c));
+                let value = match a.kind {
+                    Kind::Option => format!(" {}", a.placeholder()),
+                    _ => String::new(),
+                };
+
+                (format!("{}--{}{}", short, a.name, value), describe(a))
+            })
+            .collect();
+
+        options.push(("-h, --help".to_string(), "show this help".to_string()));
+
+        let positionals: Vec<(String, String)> = self.args
+            .iter()
+            .filter(|a| a.kind == Kind::Positional)
+            .map(|a| (a.placeholder(), describe(a)))
+            .collect();
+
+        let commands: Vec<(String, String)> = self.subcommands
+            .iter()
+            .map(|s| (s.name.clone(), s.about.clone()))
+            .collect();
+
+        // Every section lines its descriptions up in the same column.
+        let width = options
+            .iter()
+            .chain(&positionals)
+            .chain(&commands)
+            .map(|(name, _)| name.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        for (title, rows) in [("arguments", &positionals), ("commands", &commands), ("options", &options)] {
+            if rows.is_empty() {
+                continue;
+            }
+
+            help += &format!("\n{}:\n", title);
+
+            for (name, description) in rows {
+                let line = format!("  {:width$}  {}", name, description, width = width);
+                help += line.trim_end();
+                help += "\n";
+            }
+        }
+
+        help
+    }
+}
+
+fn describe(arg: &Arg) -> String {
+    let mut description = arg.help.clone();
+
+    if !arg.choices.is_empty() {
+        description += &format!(" ({})", arg.choices.join(", "));
+    }
+
+    if let Some(ref default) = arg.default {
+        description += &format!(" [default: {}]", default);
+    }
+
+    description.trim().to_string()
+}
+
+// The closest of the candidates to a misspelt name, if any is close enough to
+// be what was meant.
+fn suggest<'a, I>(name: &str, candidates: I) -> Option<&'a str>
+where
+    I: Iterator<Item = &'a str>,
+{
+    candidates
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|&(distance, candidate)| distance <= 2.max(candidate.len() / 3))
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, candidate)| candidate)
+}
+
+// The Levenshtein distance, the number of single character insertions,
+// deletions or substitutions to turn a into b.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn greeter() -> Command {
+        Command::new("greet")
+            .about("Says hello")
+            .arg(Arg::option("name").short('n').value_name("NAME").help("who to greet").repeatable())
+            .arg(Arg::option("times").value_name("N").help("how often").default("1"))
+            .arg(Arg::option("format").choices(&["text", "json"]).default("text"))
+            .arg(Arg::flag("shout").short('s').help("use capitals"))
+            .arg(Arg::positional("files").help("where to write").repeatable())
+    }
+
+    fn invalid(result: Result<Matches, Error>) -> String {
+        match result {
+            Err(Error::Invalid { message, .. }) => message,
+            other => panic!("expected an error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn flags_options_and_positionals() {
+        let matches = greeter()
+            .parse(["--name", "Alice", "-s", "a.txt", "--times=3", "-nBob", "b.txt"])
+            .unwrap();
+
+        assert!(matches.flag("shout"));
+        assert_eq!(matches.values::<String>("name").unwrap(), vec!["Alice", "Bob"]);
+        assert_eq!(matches.value::<u32>("times").unwrap(), Some(3));
+        assert_eq!(matches.values::<String>("files").unwrap(), vec!["a.txt", "b.txt"]);
+    }
+
+    #[test]
+    fn defaults_fill_in_missing_values() {
+        let matches = greeter().parse(Vec::<String>::new()).unwrap();
+
+        assert!(!matches.flag("shout"));
+        assert_eq!(matches.value::<u32>("times").unwrap(), Some(1));
+        assert_eq!(matches.value::<String>("format").unwrap(), Some("text".to_string()));
+        assert_eq!(matches.value::<String>("name").unwrap(), None);
+        assert!(matches.values::<String>("files").unwrap().is_empty());
+    }
+
+    #[test]
+    fn flags_can_be_counted() {
+        let command = Command::new("v").arg(Arg::flag("verbose").short('v'));
+        let matches = command.parse(["-v", "--verbose", "-v"]).unwrap();
+
+        assert_eq!(matches.occurrences("verbose"), 3);
+    }
+
+    #[test]
+    fn double_dash_ends_options() {
+        let matches = greeter().parse(["--", "--shout"]).unwrap();
+
+        assert!(!matches.flag("shout"));
+        assert_eq!(matches.values::<String>("files").unwrap(), vec!["--shout"]);
+    }
+
+    #[test]
+    fn typed_values_report_bad_input() {
+        let matches = greeter().parse(["--times", "lots"]).unwrap();
+
+        assert_eq!(
+            matches.value::<u32>("times").unwrap_err().to_string(),
+            "invalid value \"lots\" for --times: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn errors_explain_what_went_wrong() {
+        assert_eq!(invalid(greeter().parse(["--name"])), "--name needs a value, NAME");
+        assert_eq!(invalid(greeter().parse(["--shout=yes"])), "--shout doesn't take a value");
+        assert_eq!(invalid(greeter().parse(["--times", "1", "--times", "2"])), "--times given more than once");
+        assert_eq!(
+            invalid(greeter().parse(["--format", "xml"])),
+            "invalid value \"xml\" for --format, expected one of text, json"
+        );
+        assert_eq!(invalid(greeter().parse(["-x"])), "unknown option -x");
+        assert_eq!(invalid(greeter().parse(["--verbose"])), "unknown option --verbose");
+    }
+
+    #[test]
+    fn misspelt_options_get_suggestions() {
+        assert_eq!(invalid(greeter().parse(["--shuot"])), "unknown option --shuot, did you mean --shout?");
+        assert_eq!(invalid(greeter().parse(["--time", "2"])), "unknown option --time, did you mean --times?");
+    }
+
+    #[test]
+    fn required_and_extra_arguments() {
+        let command = Command::new("copy")
+            .arg(Arg::positional("from").required())
+            .arg(Arg::positional("to"));
+
+        assert_eq!(invalid(command.parse(Vec::<String>::new())), "FROM is required");
+        assert_eq!(invalid(command.parse(["a", "b", "c"])), "unexpected argument \"c\"");
+
+        let matches = command.parse(["a"]).unwrap();
+        assert_eq!(matches.value::<String>("from").unwrap(), Some("a".to_string()));
+        assert_eq!(matches.value::<String>("to").unwrap(), None);
+    }
+
+    #[test]
+    fn subcommands_have_their_own_arguments() {
+        let command = Command::new("tool")
+            .arg(Arg::flag("quiet"))
+            .subcommand(Command::new("run").about("runs it").arg(Arg::positional("what").required()))
+            .subcommand(Command::new("list").about("lists them").arg(Arg::flag("all")));
+
+        let matches = command.parse(["--quiet", "list", "--all"]).unwrap();
+        let (name, sub) = matches.subcommand().unwrap();
+
+        assert!(matches.flag("quiet"));
+        assert_eq!(name, "list");
+        assert!(sub.flag("all"));
+
+        assert_eq!(invalid(command.parse(["lsit"])), "unknown command \"lsit\", did you mean list?");
+        assert_eq!(
+            invalid(command.parse(["zzzzzz"])),
+            "unknown command \"zzzzzz\", expected one of run, list"
+        );
+        assert_eq!(invalid(command.parse(["--quiet"])), "a command is needed, one of run, list");
+
+        match command.parse(["run", "--all"]) {
+            Err(Error::Invalid { message, usage }) => {
+                assert_eq!(message, "unknown option --all");
+                assert_eq!(usage, "usage: tool run WHAT");
+            }
+            other => panic!("expected an error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn help_is_generated_from_the_declarations() {
+        let help = match greeter().parse(["--shout", "--help"]) {
+            Err(Error::Help(help)) => help,
+            other => panic!("expected help, got {:?}", other),
+        };
+
+        assert_eq!(
+            help,
+            "\
+Says hello
+
+usage: greet [options] [FILES...]
+
+arguments:
+  FILES                where to write
+
+options:
+  -n, --name NAME      who to greet
+      --times N        how often [default: 1]
+      --format FORMAT  (text, json) [default: text]
+  -s, --shout          use capitals
+  -h, --help           show this help
+"
+        );
+    }
+
+    #[test]
+    fn subcommand_help_uses_the_full_path() {
+        let command = Command::new("tool")
+            .about("Does things")
+            .subcommand(Command::new("run").about("runs it"));
+
+        assert!(command.help().contains("usage: tool COMMAND\n\ncommands:\n  run         runs it\n"));
+
+        match command.parse(["run", "-h"]) {
+            Err(Error::Help(help)) => assert_eq!(help, "runs it\n\nusage: tool run\n\noptions:\n  -h, --help  show this help\n"),
+            other => panic!("expected help, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn edit_distances() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("same", "same"), 0);
+        assert_eq!(suggest("colour", ["color", "flavour"].into_iter()), Some("color"));
+        assert_eq!(suggest("x", ["verbose"].into_iter()), None);
+    }
+}
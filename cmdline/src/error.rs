@@ -0,0 +1,41 @@
+use std::error;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    // --help was asked for. Holds the help text, which isn't an error as such
+    // but does mean there is nothing else to do.
+    Help(String),
+    // The arguments were wrong. Holds what was wrong and the usage line of
+    // the command that was being parsed.
+    Invalid { message: String, usage: String },
+}
+
+impl Error {
+    // Prints the help to stdout and exits successfully, or prints the error
+    // and usage to stderr and exits with status 2, as most command line tools
+    // do for bad usage.
+    pub fn exit(&self) -> ! {
+        match *self {
+            Error::Help(ref help) => {
+                print!("{}", help);
+                std::process::exit(0);
+            }
+            Error::Invalid { ref message, ref usage } => {
+                eprintln!("error: {}\n\n{}\n\nFor more information, try --help.", message, usage);
+                std::process::exit(2);
+            }
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Help(ref help) => write!(f, "{}", help),
+            Error::Invalid { ref message, .. } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl error::Error for Error {}
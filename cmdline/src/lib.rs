@@ -0,0 +1,24 @@
+// Command line parsing shared by the binaries in this workspace.
+//
+// A Command is declared up front with the flags, options and positional
+// arguments it accepts. Parsing checks the arguments against the declaration
+// and returns Matches, from which values are read back as whatever type they
+// parse to. The declarations are also used to generate --help.
+//
+//  let command = Command::new("greet")
+//      .about("Says hello")
+//      .arg(Arg::option("name").value_name("NAME").help("who to greet"))
+//      .arg(Arg::flag("shout").help("use capitals"));
+//
+//  let matches = command.parse_or_exit(env::args().skip(1));
+//  let name: Option<String> = matches.value("name")?;
+
+mod arg;
+mod command;
+mod error;
+mod matches;
+
+pub use crate::arg::Arg;
+pub use crate::command::Command;
+pub use crate::error::Error;
+pub use crate::matches::Matches;
@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::arg::Arg;
+use crate::error::Error;
+
+// The arguments that were given, by name.
+#[derive(Debug, Clone, Default)]
+pub struct Matches {
+    pub(crate) values: HashMap<String, Vec<String>>,
+    pub(crate) flags: HashMap<String, usize>,
+    // Declarations, for defaults and error messages.
+    pub(crate) args: Vec<Arg>,
+    pub(crate) usage: String,
+    pub(crate) subcommand: Option<(String, Box<Matches>)>,
+}
+
+impl Matches {
+    // Whether a flag was given.
+    pub fn flag(&self, name: &str) -> bool {
+        self.occurrences(name) > 0
+    }
+
+    // How many times a flag was given, for things like -v -v.
+    pub fn occurrences(&self, name: &str) -> usize {
+        self.flags.get(name).copied().unwrap_or(0)
+    }
+
+    // The value of an option or positional, falling back to its default,
+    // parsed as T. None if it wasn't given and has no default.
+    pub fn value<T>(&self, name: &str) -> Result<Option<T>, Error>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        Ok(self.values::<T>(name)?.pop())
+    }
+
+    // Every value given for a repeatable option or positional, in order.
+    pub fn values<T>(&self, name: &str) -> Result<Vec<T>, Error>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let arg = self.args.iter().find(|a| a.name == name);
+
+        let raw: Vec<&str> = match self.values.get(name) {
+            Some(values) => values.iter().map(String::as_str).collect(),
+            None => arg.and_then(|a| a.default.as_deref()).into_iter().collect(),
+        };
+
+        let display = arg.map_or_else(|| name.to_string(), Arg::display_name);
+
+        raw.into_iter()
+            .map(|value| {
+                value.parse().map_err(|e| Error::Invalid {
+                    message: format!("invalid value {:?} for {}: {}", value, display, e),
+                    usage: self.usage.clone(),
+                })
+            })
+            .collect()
+    }
+
+    // The subcommand that was given, if any, with its own matches.
+    pub fn subcommand(&self) -> Option<(&str, &Matches)> {
+        self.subcommand.as_ref().map(|(name, matches)| (name.as_str(), &**matches))
+    }
+
+    // An error in the same style as the parsing errors, for checks that the
+    // declarations can't express, such as a number needing to be positive.
+    pub fn error(&self, message: &str) -> Error {
+        Error::Invalid { message: message.to_string(), usage: self.usage.clone() }
+    }
+}
@@ -0,0 +1,416 @@
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use crate::i18n::{Catalogue, Lang};
+use crate::secret::SecretSource;
+
+// The longest secret that can be played, since without duplicates there are
+// only ten digits to choose from.
+pub const MAX_LENGTH: usize = 10;
+
+// How the secret is made up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rules {
+    pub length: usize,
+    // Whether a digit may appear more than once, in the secret and in guesses.
+    pub duplicates: bool,
+}
+
+impl Default for Rules {
+    fn default() -> Rules {
+        Rules { length: 4, duplicates: false }
+    }
+}
+
+impl Rules {
+    pub fn new(length: usize, duplicates: bool) -> Result<Rules, String> {
+        if length == 0 || length > MAX_LENGTH {
+            return Err(format!("length must be between 1 and {}", MAX_LENGTH));
+        }
+
+        Ok(Rules { length, duplicates })
+    }
+
+    // Picks a secret that follows the rules.
+    //
+    // Without duplicates the digits are drawn without replacement, by
+    // shuffling the first length places of 0 to 9. Drawing again on a repeat
+    // would never finish with a source that keeps giving the same digit, as
+    // FixedSecret does.
+    pub fn secret<S: SecretSource + ?Sized>(&self, source: &mut S) -> Vec<u8> {
+        if self.duplicates {
+            return (0..self.length).map(|_| source.secret(0, 9) as u8).collect();
+        }
+
+        let mut digits: Vec<u8> = (0..=9).collect();
+
+        for i in 0..self.length {
+            let j = source.secret(i as u32, 9) as usize;
+            digits.swap(i, j);
+        }
+
+        digits.truncate(self.length);
+        digits
+    }
+
+    // Every secret that follows the rules, in ascending order.
+    pub fn candidates(&self) -> Vec<Vec<u8>> {
+        let mut candidates = vec![Vec::new()];
+
+        for _ in 0..self.length {
+            let mut longer = Vec::new();
+
+            for prefix in &candidates {
+                for digit in 0..10u8 {
+                    if self.duplicates || !prefix.contains(&digit) {
+                        let mut next = prefix.clone();
+                        next.push(digit);
+                        longer.push(next);
+                    }
+                }
+            }
+
+            candidates = longer;
+        }
+
+        candidates
+    }
+}
+
+// Bulls are the right digit in the right place, cows the right digit in the
+// wrong place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Score {
+    pub bulls: usize,
+    pub cows: usize,
+}
+
+impl Score {
+    pub fn message(&self, catalogue: &Catalogue) -> String {
+        catalogue.format("bulls.score", &[("bulls", &self.bulls), ("cows", &self.cows)])
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message(Lang::English.catalogue()))
+    }
+}
+
+pub fn score(secret: &[u8], guess: &[u8]) -> Score {
+    let bulls = secret.iter().zip(guess).filter(|(s, g)| s == g).count();
+
+    // Each digit matches as many times as it appears in both, which counts
+    // duplicates properly. The bulls are among those matches.
+    let mut in_secret = [0; 10];
+    let mut in_guess = [0; 10];
+
+    for &d in secret {
+        in_secret[d as usize] += 1;
+    }
+
+    for &d in guess {
+        in_guess[d as usize] += 1;
+    }
+
+    let matches: usize = in_secret.iter().zip(&in_guess).map(|(s, g)| *s.min(g)).sum();
+
+    Score { bulls, cows: matches - bulls }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GuessError {
+    WrongLength { expected: usize },
+    NotADigit(char),
+    Duplicate(u8),
+}
+
+impl GuessError {
+    pub fn message(&self, catalogue: &Catalogue) -> String {
+        match *self {
+            GuessError::WrongLength { expected } => {
+                catalogue.format("bulls.wrong_length", &[("length", &expected)])
+            }
+            GuessError::NotADigit(c) => {
+                catalogue.format("bulls.not_a_digit", &[("char", &format!("{:?}", c))])
+            }
+            GuessError::Duplicate(d) => catalogue.format("bulls.duplicate", &[("digit", &d)]),
+        }
+    }
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message(Lang::English.catalogue()))
+    }
+}
+
+pub fn parse_guess(line: &str, rules: &Rules) -> Result<Vec<u8>, GuessError> {
+    let mut guess = Vec::with_capacity(rules.length);
+
+    for c in line.trim().chars() {
+        let digit = c.to_digit(10).ok_or(GuessError::NotADigit(c))? as u8;
+
+        if !rules.duplicates && guess.contains(&digit) {
+            return Err(GuessError::Duplicate(digit));
+        }
+
+        guess.push(digit);
+    }
+
+    if guess.len() != rules.length {
+        return Err(GuessError::WrongLength { expected: rules.length });
+    }
+
+    Ok(guess)
+}
+
+pub fn digits(digits: &[u8]) -> String {
+    digits.iter().map(|d| char::from(b'0' + d)).collect()
+}
+
+// Plays by always guessing the smallest candidate that agrees with every
+// score so far. It isn't optimal but it is deterministic, so it can be used to
+// check a game from start to finish.
+//
+// All the candidates are generated up front, which is fine for the default of
+// four digits but gets slow for long secrets with duplicates.
+#[derive(Debug)]
+pub struct Solver {
+    candidates: Vec<Vec<u8>>,
+}
+
+impl Solver {
+    pub fn new(rules: &Rules) -> Solver {
+        Solver { candidates: rules.candidates() }
+    }
+
+    // None once the scores have ruled out every candidate.
+    pub fn next_guess(&self) -> Option<&[u8]> {
+        self.candidates.first().map(Vec::as_slice)
+    }
+
+    pub fn feedback(&mut self, guess: &[u8], result: Score) {
+        self.candidates.retain(|candidate| score(candidate, guess) == result);
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.candidates.len()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Won { guesses: u32 },
+    // Input ran out before the secret was guessed.
+    GaveUp { secret: String, guesses: u32 },
+}
+
+// Plays one game, following the same read, parse and compare loop as the
+// number guessing game.
+pub fn play<S, R, W>(
+    source: &mut S,
+    rules: &Rules,
+    lang: Lang,
+    input: R,
+    output: &mut W,
+) -> io::Result<Outcome>
+where
+    S: SecretSource + ?Sized,
+    R: BufRead,
+    W: Write,
+{
+    let catalogue = lang.catalogue();
+    let secret = rules.secret(source);
+    let mut guesses = 0;
+    let mut lines = input.lines();
+
+    let title = if rules.duplicates { "bulls.title" } else { "bulls.title_distinct" };
+
+    writeln!(output, "{}", catalogue.format(title, &[("length", &rules.length)]))?;
+
+    loop {
+        writeln!(output, "{}", catalogue.get("game.prompt"))?;
+
+        let guess = match lines.next() {
+            Some(line) => line?,
+            None => return Ok(Outcome::GaveUp { secret: digits(&secret), guesses }),
+        };
+
+        let guess = match parse_guess(&guess, rules) {
+            Ok(guess) => guess,
+            Err(e) => {
+                let reason = e.message(catalogue);
+
+                writeln!(output, "{}", catalogue.format("game.invalid", &[("reason", &reason)]))?;
+                continue;
+            }
+        };
+
+        guesses += 1;
+
+        let result = score(&secret, &guess);
+
+        if result.bulls == rules.length {
+            writeln!(output, "{}", catalogue.format("bulls.win", &[("guesses", &guesses)]))?;
+            return Ok(Outcome::Won { guesses });
+        }
+
+        writeln!(output, "{}", result.message(catalogue))?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secret::{FixedSecret, XorShift};
+    use std::io::Cursor;
+
+    fn solve(rules: &Rules, secret: &[u8]) -> Vec<Vec<u8>> {
+        let mut solver = Solver::new(rules);
+        let mut guesses = Vec::new();
+
+        loop {
+            let guess = solver.next_guess().expect("solver ran out of candidates").to_vec();
+            let result = score(secret, &guess);
+
+            guesses.push(guess.clone());
+
+            if result.bulls == rules.length {
+                return guesses;
+            }
+
+            solver.feedback(&guess, result);
+        }
+    }
+
+    #[test]
+    fn scores_count_bulls_and_cows() {
+        assert_eq!(score(&[1, 2, 3, 4], &[1, 2, 3, 4]), Score { bulls: 4, cows: 0 });
+        assert_eq!(score(&[1, 2, 3, 4], &[4, 3, 2, 1]), Score { bulls: 0, cows: 4 });
+        assert_eq!(score(&[1, 2, 3, 4], &[1, 3, 5, 6]), Score { bulls: 1, cows: 1 });
+        assert_eq!(score(&[1, 2, 3, 4], &[5, 6, 7, 8]), Score { bulls: 0, cows: 0 });
+    }
+
+    #[test]
+    fn duplicates_are_only_matched_once() {
+        assert_eq!(score(&[1, 1, 2, 2], &[1, 2, 1, 1]), Score { bulls: 1, cows: 2 });
+        assert_eq!(score(&[1, 2, 3, 4], &[1, 1, 1, 1]), Score { bulls: 1, cows: 0 });
+    }
+
+    #[test]
+    fn rules_limit_the_length() {
+        assert!(Rules::new(0, false).is_err());
+        assert!(Rules::new(11, true).is_err());
+        assert_eq!(Rules::new(10, false), Ok(Rules { length: 10, duplicates: false }));
+    }
+
+    #[test]
+    fn secrets_follow_the_rules() {
+        let mut rng = XorShift::new(3);
+
+        for _ in 0..100 {
+            let mut secret = Rules::default().secret(&mut rng);
+            assert_eq!(secret.len(), 4);
+
+            secret.sort();
+            secret.dedup();
+            assert_eq!(secret.len(), 4);
+        }
+
+        let rules = Rules::new(6, true).unwrap();
+        assert_eq!(rules.secret(&mut FixedSecret(7)), vec![7; 6]);
+    }
+
+    #[test]
+    fn a_fixed_source_still_gives_distinct_digits() {
+        // Each draw of 5 swaps a different place with place 5, so the digits
+        // that pass through it come out in order after the 5.
+        assert_eq!(Rules::default().secret(&mut FixedSecret(5)), vec![5, 0, 1, 2]);
+
+        // Every digit is used once, even when the source can only give 9.
+        let mut secret = Rules::new(10, false).unwrap().secret(&mut FixedSecret(9));
+        secret.sort();
+        assert_eq!(secret, (0..=9).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn candidates_follow_the_rules() {
+        assert_eq!(Rules::default().candidates().len(), 10 * 9 * 8 * 7);
+        assert_eq!(Rules::new(3, true).unwrap().candidates().len(), 1000);
+        assert_eq!(Rules::new(2, false).unwrap().candidates()[..2], [vec![0, 1], vec![0, 2]]);
+    }
+
+    #[test]
+    fn guesses_are_validated() {
+        let rules = Rules::default();
+
+        assert_eq!(parse_guess(" 0123\n", &rules), Ok(vec![0, 1, 2, 3]));
+        assert_eq!(parse_guess("123", &rules), Err(GuessError::WrongLength { expected: 4 }));
+        assert_eq!(parse_guess("12a4", &rules), Err(GuessError::NotADigit('a')));
+        assert_eq!(parse_guess("1214", &rules), Err(GuessError::Duplicate(1)));
+
+        let rules = Rules { duplicates: true, ..rules };
+        assert_eq!(parse_guess("1214", &rules), Ok(vec![1, 2, 1, 4]));
+    }
+
+    #[test]
+    fn solver_finds_every_three_digit_secret() {
+        let rules = Rules::new(3, false).unwrap();
+
+        for secret in rules.candidates() {
+            let guesses = solve(&rules, &secret);
+
+            assert_eq!(guesses.last(), Some(&secret));
+            assert!(guesses.len() <= 10, "{:?} took {} guesses", secret, guesses.len());
+        }
+    }
+
+    #[test]
+    fn solver_is_deterministic() {
+        let rules = Rules::default();
+
+        assert_eq!(solve(&rules, &[9, 3, 5, 1]), solve(&rules, &[9, 3, 5, 1]));
+        assert_eq!(solve(&rules, &[0, 1, 2, 3]), vec![vec![0, 1, 2, 3]]);
+    }
+
+    #[test]
+    fn solver_drives_a_whole_game() {
+        // FixedSecret(2) with duplicates gives 2222.
+        let rules = Rules::new(4, true).unwrap();
+        let guesses = solve(&rules, &[2, 2, 2, 2]);
+        let input: String = guesses.iter().map(|g| digits(g) + "\n").collect();
+        let mut output = Vec::new();
+
+        let outcome = play(&mut FixedSecret(2), &rules, Lang::English, Cursor::new(input), &mut output).unwrap();
+
+        assert_eq!(outcome, Outcome::Won { guesses: guesses.len() as u32 });
+        assert!(String::from_utf8(output).unwrap().contains("0 bulls, 0 cows\n"));
+    }
+
+    #[test]
+    fn invalid_guesses_are_explained() {
+        let mut output = Vec::new();
+
+        let outcome = play(&mut XorShift::new(1), &Rules::default(), Lang::English, Cursor::new("12\n1123\n"), &mut output)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(matches!(outcome, Outcome::GaveUp { guesses: 0, .. }));
+        assert!(output.contains("Invalid guess, the guess must be 4 digits\n"));
+        assert!(output.contains("Invalid guess, 1 appears more than once\n"));
+    }
+
+    #[test]
+    fn invalid_guesses_are_explained_in_french() {
+        let mut output = Vec::new();
+
+        let outcome = play(&mut XorShift::new(1), &Rules::default(), Lang::French, Cursor::new("12\n1123\n"), &mut output)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(matches!(outcome, Outcome::GaveUp { guesses: 0, .. }));
+        assert!(output.contains("Proposition invalide, la proposition doit faire 4 chiffres\n"));
+        assert!(output.contains("Proposition invalide, 1 apparaît plusieurs fois\n"));
+    }
+}
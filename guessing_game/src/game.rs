@@ -0,0 +1,285 @@
+use std::cmp::Ordering;
+use std::io::{self, BufRead, Write};
+
+use crate::hints::{Hints, Tracker};
+use crate::i18n::{Catalogue, Lang};
+use crate::input::parse_guess;
+use crate::secret::SecretSource;
+use crate::tui;
+
+// The secret is always between these two numbers, inclusive.
+pub const LOW: u32 = 1;
+pub const HIGH: u32 = 100;
+
+// Settings for a game that can be changed from the command line.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Options {
+    pub hints: Hints,
+    // Accept surrounding whitespace and hexadecimal guesses. See parse_guess.
+    pub lenient: bool,
+    // The game is lost once this many valid guesses have missed.
+    pub max_guesses: Option<u32>,
+    pub interface: Interface,
+    pub lang: Lang,
+}
+
+// How the game is shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Interface {
+    // A line of text at a time, which works anywhere.
+    #[default]
+    Plain,
+    // A full screen redrawn after every guess. See tui.
+    Terminal,
+}
+
+// Counts kept while a game is played.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Stats {
+    // Valid guesses, including the winning one.
+    pub guesses: u32,
+    // Lines that were rejected and didn't count as a guess.
+    pub invalid: u32,
+}
+
+// How a game ended.
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    // The secret was guessed.
+    Won { stats: Stats },
+    // Every guess allowed by max_guesses was used up.
+    Lost { secret: u32, stats: Stats },
+    // Input ran out before the secret was guessed.
+    GaveUp { secret: u32, stats: Stats },
+}
+
+// Plays one game, reading guesses from input and writing to output.
+//
+// Taking the reader, writer and secret source as parameters, rather than
+// using stdin, stdout and the thread RNG directly, means the whole game can be
+// driven from a test.
+pub fn play<S, R, W>(
+    source: &mut S,
+    options: &Options,
+    input: R,
+    output: &mut W,
+) -> io::Result<Outcome>
+where
+    S: SecretSource + ?Sized,
+    R: BufRead,
+    W: Write,
+{
+    if options.interface == Interface::Terminal {
+        return tui::play(source, options, input, output);
+    }
+
+    let catalogue = options.lang.catalogue();
+
+    writeln!(output, "{}", catalogue.get("game.title"))?;
+
+    let secret_number = source.secret(LOW, HIGH);
+    let mut tracker = Tracker::new(options.hints, LOW, HIGH);
+    let mut stats = Stats::default();
+    let mut lines = input.lines();
+
+    loop {
+        writeln!(output, "{}", catalogue.get("game.prompt"))?;
+
+        let guess = match lines.next() {
+            Some(line) => line?,
+            None => {
+                print_summary(&tracker, options, &stats, catalogue, output)?;
+                return Ok(Outcome::GaveUp { secret: secret_number, stats });
+            }
+        };
+
+        let guess = match parse_guess(&guess, options.lenient, LOW, HIGH) {
+            Ok(num) => num,
+            Err(e)  => {
+                stats.invalid += 1;
+                let reason = e.message(catalogue);
+
+                writeln!(output, "{}", catalogue.format("game.invalid", &[("reason", &reason)]))?;
+                continue;
+            }
+        };
+
+        stats.guesses += 1;
+
+        writeln!(output, "{}", catalogue.format("game.you_guessed", &[("guess", &guess)]))?;
+
+        for warning in tracker.warnings(guess) {
+            writeln!(output, "{}", warning.message(catalogue))?;
+        }
+
+        match guess.cmp(&secret_number) {
+            Ordering::Less      => writeln!(output, "{}", catalogue.get("game.too_small"))?,
+            Ordering::Greater   => writeln!(output, "{}", catalogue.get("game.too_big"))?,
+            Ordering::Equal     => {
+                writeln!(output, "{}", catalogue.get("game.win"))?;
+                tracker.record(guess, secret_number);
+                print_summary(&tracker, options, &stats, catalogue, output)?;
+                return Ok(Outcome::Won { stats });
+            }
+        }
+
+        for hint in tracker.record(guess, secret_number) {
+            writeln!(output, "{}", hint.message(catalogue))?;
+        }
+
+        if options.max_guesses.is_some_and(|max| stats.guesses >= max) {
+            writeln!(
+                output,
+                "{}",
+                catalogue.format("game.out_of_guesses", &[("secret", &secret_number)])
+            )?;
+            print_summary(&tracker, options, &stats, catalogue, output)?;
+            return Ok(Outcome::Lost { secret: secret_number, stats });
+        }
+    }
+}
+
+fn print_summary<W: Write>(
+    tracker: &Tracker,
+    options: &Options,
+    stats: &Stats,
+    catalogue: &Catalogue,
+    output: &mut W,
+) -> io::Result<()> {
+    writeln!(
+        output,
+        "{}",
+        catalogue.format("game.summary", &[("guesses", &stats.guesses), ("invalid", &stats.invalid)])
+    )?;
+
+    if !options.hints.history || tracker.guesses().is_empty() {
+        return Ok(());
+    }
+
+    let guesses: Vec<String> = tracker.guesses().iter().map(|g| g.to_string()).collect();
+
+    writeln!(output, "{}", catalogue.format("game.history", &[("guesses", &guesses.join(", "))]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secret::FixedSecret;
+    use std::io::Cursor;
+
+    fn run(secret: u32, input: &str) -> (Outcome, String) {
+        run_with(&Options::default(), secret, input)
+    }
+
+    fn run_with(options: &Options, secret: u32, input: &str) -> (Outcome, String) {
+        let mut output = Vec::new();
+        let outcome = play(&mut FixedSecret(secret), options, Cursor::new(input), &mut output)
+            .unwrap();
+
+        (outcome, String::from_utf8(output).unwrap())
+    }
+
+    fn hints(hints: Hints) -> Options {
+        Options { hints, ..Options::default() }
+    }
+
+    #[test]
+    fn guessing_the_secret_wins() {
+        let (outcome, output) = run(42, "50\n25\n42\n");
+
+        assert_eq!(outcome, Outcome::Won { stats: Stats { guesses: 3, invalid: 0 } });
+        assert!(output.contains("Too big!"));
+        assert!(output.contains("Too small!"));
+        assert!(output.ends_with("You win!\nGuesses: 3, invalid inputs: 0\n"));
+    }
+
+    #[test]
+    fn invalid_input_is_explained_and_counted() {
+        let (outcome, output) = run(7, "seven\n-3\n101\n7\n");
+
+        assert_eq!(outcome, Outcome::Won { stats: Stats { guesses: 1, invalid: 3 } });
+        assert!(output.contains("Invalid guess, \"seven\" is not a number\n"));
+        assert!(output.contains("Invalid guess, negative numbers are not allowed\n"));
+        assert!(output.contains("Invalid guess, the number must be between 1 and 100\n"));
+        assert!(output.ends_with("Guesses: 1, invalid inputs: 3\n"));
+    }
+
+    #[test]
+    fn lenient_input_is_opt_in() {
+        let (outcome, _) = run(42, "  42 \n0x2a\n42\n");
+
+        assert_eq!(outcome, Outcome::Won { stats: Stats { guesses: 1, invalid: 2 } });
+
+        let options = Options { lenient: true, ..Options::default() };
+        let (outcome, _) = run_with(&options, 42, "0x2a\n");
+
+        assert_eq!(outcome, Outcome::Won { stats: Stats { guesses: 1, invalid: 0 } });
+    }
+
+    #[test]
+    fn guesses_can_be_limited() {
+        let options = Options { max_guesses: Some(2), ..Options::default() };
+        let (outcome, output) = run_with(&options, 42, "1\n2\n42\n");
+
+        assert_eq!(outcome, Outcome::Lost { secret: 42, stats: Stats { guesses: 2, invalid: 0 } });
+        assert!(output.contains("Out of guesses, the number was 42\n"));
+
+        let (outcome, _) = run_with(&options, 42, "1\n42\n");
+        assert_eq!(outcome, Outcome::Won { stats: Stats { guesses: 2, invalid: 0 } });
+    }
+
+    #[test]
+    fn messages_follow_the_language() {
+        let options = Options { lang: Lang::French, hints: Hints::all(), ..Options::default() };
+        let (_, output) = run_with(&options, 42, "cinquante\n50\n42\n");
+
+        assert!(output.starts_with("Devinez le nombre !\nEntrez votre proposition\n"));
+        assert!(output.contains("Proposition invalide, \"cinquante\" n'est pas un nombre\n"));
+        assert!(output.contains("Trop grand !\nLe nombre est entre 1 et 49\n"));
+        assert!(output.contains("Gagné !\nPropositions : 2, saisies invalides : 1\n"));
+    }
+
+    #[test]
+    fn running_out_of_input_gives_up() {
+        let (outcome, output) = run(7, "1\n2\n");
+
+        assert_eq!(
+            outcome,
+            Outcome::GaveUp { secret: 7, stats: Stats { guesses: 2, invalid: 0 } }
+        );
+        assert!(!output.contains("You win!"));
+    }
+
+    #[test]
+    fn hints_are_off_by_default() {
+        let (_, output) = run(42, "50\n60\n50\n42\n");
+
+        assert!(!output.contains("Warmer"));
+        assert!(!output.contains("Colder"));
+        assert!(!output.contains("between"));
+        assert!(!output.contains("already"));
+        assert!(!output.contains("Your guesses"));
+    }
+
+    #[test]
+    fn hints_follow_each_guess() {
+        let (outcome, output) = run_with(&hints(Hints::all()), 42, "33\n62\n70\n62\n42\n");
+
+        assert_eq!(outcome, Outcome::Won { stats: Stats { guesses: 5, invalid: 0 } });
+        assert!(output.contains("Too small!\nThe number is between 34 and 100\n"));
+        assert!(output.contains("Too big!\nWarmer!\nThe number is between 34 and 61\n"));
+        assert!(output.contains(
+            "You guessed: 70\n70 can't be right, the number is between 34 and 61\nToo big!\nColder!\n"
+        ));
+        assert!(output.contains("You guessed: 62\nYou already guessed 62\n"));
+        assert!(output.ends_with("You win!\nGuesses: 5, invalid inputs: 0\nYour guesses: 33, 62, 70, 62, 42\n"));
+    }
+
+    #[test]
+    fn history_is_printed_when_giving_up() {
+        let options = hints(Hints { history: true, ..Hints::default() });
+        let (_, output) = run_with(&options, 42, "1\n2\n");
+
+        assert!(output.ends_with("Your guesses: 1, 2\n"));
+    }
+}
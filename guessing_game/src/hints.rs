@@ -0,0 +1,212 @@
+use std::fmt;
+
+use crate::i18n::{Catalogue, Lang};
+
+// Which hints to give. Each one can be turned on by itself from the command
+// line, or all of them together with --hints.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Hints {
+    // Say whether each guess is closer to the secret than the one before.
+    pub warmer: bool,
+    // Keep track of the range the secret must be in and warn about guesses
+    // outside it.
+    pub interval: bool,
+    // Warn when a number is guessed twice.
+    pub repeats: bool,
+    // List every guess once the game is over.
+    pub history: bool,
+}
+
+impl Hints {
+    pub fn all() -> Hints {
+        Hints { warmer: true, interval: true, repeats: true, history: true }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Hint {
+    Repeated(u32),
+    OutsideInterval { guess: u32, low: u32, high: u32 },
+    Warmer,
+    Colder,
+    NoCloser,
+    Interval { low: u32, high: u32 },
+}
+
+impl Hint {
+    pub fn message(&self, catalogue: &Catalogue) -> String {
+        match *self {
+            Hint::Repeated(guess) => catalogue.format("hint.repeated", &[("guess", &guess)]),
+            Hint::OutsideInterval { guess, low, high } => catalogue.format(
+                "hint.outside",
+                &[("guess", &guess), ("low", &low), ("high", &high)],
+            ),
+            Hint::Warmer => catalogue.get("hint.warmer").to_string(),
+            Hint::Colder => catalogue.get("hint.colder").to_string(),
+            Hint::NoCloser => catalogue.get("hint.no_closer").to_string(),
+            Hint::Interval { low, high } => {
+                catalogue.format("hint.interval", &[("low", &low), ("high", &high)])
+            }
+        }
+    }
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message(Lang::English.catalogue()))
+    }
+}
+
+// Remembers the guesses made so far and works out the hints for each new one.
+#[derive(Debug)]
+pub struct Tracker {
+    hints: Hints,
+    low: u32,
+    high: u32,
+    guesses: Vec<u32>,
+}
+
+impl Tracker {
+    pub fn new(hints: Hints, low: u32, high: u32) -> Tracker {
+        Tracker { hints, low, high, guesses: Vec::new() }
+    }
+
+    // Warnings about a guess, given before it is compared with the secret.
+    pub fn warnings(&self, guess: u32) -> Vec<Hint> {
+        let mut warnings = Vec::new();
+
+        if self.hints.repeats && self.guesses.contains(&guess) {
+            warnings.push(Hint::Repeated(guess));
+        }
+
+        if self.hints.interval && !(self.low..=self.high).contains(&guess) {
+            warnings.push(Hint::OutsideInterval { guess, low: self.low, high: self.high });
+        }
+
+        warnings
+    }
+
+    // Records a guess and returns the hints that follow from it. There are
+    // none once the secret has been guessed.
+    pub fn record(&mut self, guess: u32, secret: u32) -> Vec<Hint> {
+        let previous = self.guesses.last().copied();
+        self.guesses.push(guess);
+
+        if guess < secret {
+            self.low = self.low.max(guess + 1);
+        } else if guess > secret {
+            self.high = self.high.min(guess - 1);
+        } else {
+            self.low = guess;
+            self.high = guess;
+            return Vec::new();
+        }
+
+        let mut hints = Vec::new();
+
+        if self.hints.warmer {
+            if let Some(previous) = previous {
+                let before = previous.abs_diff(secret);
+                let now = guess.abs_diff(secret);
+
+                hints.push(if now < before {
+                    Hint::Warmer
+                } else if now > before {
+                    Hint::Colder
+                } else {
+                    Hint::NoCloser
+                });
+            }
+        }
+
+        if self.hints.interval {
+            hints.push(Hint::Interval { low: self.low, high: self.high });
+        }
+
+        hints
+    }
+
+    // Every guess so far, in the order they were made.
+    pub fn guesses(&self) -> &[u32] {
+        &self.guesses
+    }
+
+    pub fn interval(&self) -> (u32, u32) {
+        (self.low, self.high)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_hints_by_default() {
+        let mut tracker = Tracker::new(Hints::default(), 1, 100);
+
+        assert!(tracker.record(50, 42).is_empty());
+        assert!(tracker.record(30, 42).is_empty());
+        assert!(tracker.warnings(30).is_empty());
+        assert!(tracker.warnings(99).is_empty());
+    }
+
+    #[test]
+    fn warmer_and_colder_compare_with_the_previous_guess() {
+        let hints = Hints { warmer: true, ..Hints::default() };
+        let mut tracker = Tracker::new(hints, 1, 100);
+
+        assert!(tracker.record(90, 42).is_empty());
+        assert_eq!(tracker.record(60, 42), vec![Hint::Warmer]);
+        assert_eq!(tracker.record(80, 42), vec![Hint::Colder]);
+        assert_eq!(tracker.record(4, 42), vec![Hint::NoCloser]);
+        assert!(tracker.record(42, 42).is_empty());
+    }
+
+    #[test]
+    fn interval_narrows_with_each_guess() {
+        let hints = Hints { interval: true, ..Hints::default() };
+        let mut tracker = Tracker::new(hints, 1, 100);
+
+        assert_eq!(tracker.record(33, 42), vec![Hint::Interval { low: 34, high: 100 }]);
+        assert_eq!(tracker.record(62, 42), vec![Hint::Interval { low: 34, high: 61 }]);
+        assert_eq!(tracker.interval(), (34, 61));
+
+        assert_eq!(
+            tracker.warnings(70),
+            vec![Hint::OutsideInterval { guess: 70, low: 34, high: 61 }]
+        );
+        assert!(tracker.warnings(50).is_empty());
+
+        // A guess outside the interval mustn't widen it again.
+        tracker.record(20, 42);
+        assert_eq!(tracker.interval(), (34, 61));
+    }
+
+    #[test]
+    fn repeats_are_warned_about() {
+        let hints = Hints { repeats: true, ..Hints::default() };
+        let mut tracker = Tracker::new(hints, 1, 100);
+
+        tracker.record(10, 42);
+        assert_eq!(tracker.warnings(10), vec![Hint::Repeated(10)]);
+        assert!(tracker.warnings(11).is_empty());
+    }
+
+    #[test]
+    fn guesses_are_kept_in_order() {
+        let mut tracker = Tracker::new(Hints::all(), 1, 100);
+
+        for guess in [50, 25, 37, 42] {
+            tracker.record(guess, 42);
+        }
+
+        assert_eq!(tracker.guesses(), &[50, 25, 37, 42]);
+    }
+
+    #[test]
+    fn hints_read_naturally() {
+        assert_eq!(Hint::Interval { low: 34, high: 61 }.to_string(), "The number is between 34 and 61");
+        assert_eq!(Hint::Repeated(7).to_string(), "You already guessed 7");
+        assert_eq!(Hint::Warmer.message(Lang::French.catalogue()), "Plus chaud !");
+    }
+}
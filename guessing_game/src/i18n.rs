@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+// Every message the game shows comes from a catalogue, so that it can be
+// shown in more than one language. The catalogues are key = value files in
+// the lang directory that are built into the binary.
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Lang {
+    #[default]
+    English,
+    French,
+}
+
+impl Lang {
+    pub const ALL: [Lang; 2] = [Lang::English, Lang::French];
+
+    // Accepts a language code such as fr, or a locale such as fr_FR.UTF-8 as
+    // found in the LANG environment variable.
+    pub fn from_code(code: &str) -> Option<Lang> {
+        let language = code.split(['_', '.', '-', '@']).next().unwrap_or("");
+
+        match language.to_lowercase().as_str() {
+            "en" => Some(Lang::English),
+            "fr" => Some(Lang::French),
+            // The C and POSIX locales mean no particular language.
+            "c" | "posix" => Some(Lang::English),
+            _ => None,
+        }
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Lang::English => include_str!("../lang/en.txt"),
+            Lang::French => include_str!("../lang/fr.txt"),
+        }
+    }
+
+    // The catalogue is parsed the first time it is needed.
+    pub fn catalogue(self) -> &'static Catalogue {
+        static ENGLISH: OnceLock<Catalogue> = OnceLock::new();
+        static FRENCH: OnceLock<Catalogue> = OnceLock::new();
+
+        let cell = match self {
+            Lang::English => &ENGLISH,
+            Lang::French => &FRENCH,
+        };
+
+        cell.get_or_init(|| {
+            let fallback = match self {
+                Lang::English => None,
+                _ => Some(Lang::English.catalogue()),
+            };
+
+            Catalogue::parse(self.source(), fallback)
+                .unwrap_or_else(|e| panic!("bundled {:?} catalogue is invalid: {}", self, e))
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Catalogue {
+    messages: HashMap<String, String>,
+    // Where to look for messages this catalogue doesn't have.
+    fallback: Option<&'static Catalogue>,
+}
+
+impl Catalogue {
+    pub fn parse(source: &str, fallback: Option<&'static Catalogue>) -> Result<Catalogue, String> {
+        let mut messages = HashMap::new();
+
+        for (number, line) in source.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {}: expected key = value", number + 1))?;
+
+            let key = key.trim();
+
+            if messages.insert(key.to_string(), value.trim().to_string()).is_some() {
+                return Err(format!("line {}: {} is defined twice", number + 1, key));
+            }
+        }
+
+        Ok(Catalogue { messages, fallback })
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.messages.keys().map(String::as_str)
+    }
+
+    // The message for key, from the fallback if this catalogue doesn't have
+    // it. A key no catalogue has is returned as it is, so that it shows up
+    // rather than leaving a gap in the output.
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        match self.messages.get(key) {
+            Some(message) => message,
+            None => match self.fallback {
+                Some(fallback) => fallback.get(key),
+                None => key,
+            },
+        }
+    }
+
+    // The message for key with each {name} placeholder replaced by its value.
+    pub fn format(&self, key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+        let mut message = self.get(key).to_string();
+
+        for (name, value) in args {
+            message = message.replace(&format!("{{{}}}", name), &value.to_string());
+        }
+
+        message
+    }
+
+    // Whether answer is one of the comma separated answers listed under key,
+    // ignoring case and surrounding whitespace.
+    pub fn is_answer(&self, key: &str, answer: &str) -> bool {
+        let answer = answer.trim().to_lowercase();
+
+        self.get(key).split(',').any(|a| a.trim() == answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn placeholders(message: &str) -> BTreeSet<&str> {
+        message
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    fn every_catalogue_defines_every_key() {
+        let english: BTreeSet<&str> = Lang::English.catalogue().keys().collect();
+
+        for lang in Lang::ALL {
+            let keys: BTreeSet<&str> = lang.catalogue().keys().collect();
+
+            let missing: Vec<_> = english.difference(&keys).collect();
+            let unknown: Vec<_> = keys.difference(&english).collect();
+
+            assert!(missing.is_empty(), "{:?} is missing {:?}", lang, missing);
+            assert!(unknown.is_empty(), "{:?} has unknown keys {:?}", lang, unknown);
+        }
+    }
+
+    #[test]
+    fn translations_use_the_same_placeholders() {
+        let english = Lang::English.catalogue();
+
+        for lang in Lang::ALL {
+            for key in english.keys() {
+                assert_eq!(
+                    placeholders(lang.catalogue().get(key)),
+                    placeholders(english.get(key)),
+                    "{:?} {}",
+                    lang,
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn languages_come_from_codes_and_locales() {
+        assert_eq!(Lang::from_code("fr"), Some(Lang::French));
+        assert_eq!(Lang::from_code("fr_CA.UTF-8"), Some(Lang::French));
+        assert_eq!(Lang::from_code("en_GB"), Some(Lang::English));
+        assert_eq!(Lang::from_code("C"), Some(Lang::English));
+        assert_eq!(Lang::from_code("de_DE"), None);
+        assert_eq!(Lang::from_code(""), None);
+    }
+
+    #[test]
+    fn missing_keys_fall_back_to_english() {
+        let partial = Catalogue::parse("game.win = Gewonnen!", Some(Lang::English.catalogue())).unwrap();
+
+        assert_eq!(partial.get("game.win"), "Gewonnen!");
+        assert_eq!(partial.get("game.too_big"), "Too big!");
+        assert_eq!(partial.get("no.such.key"), "no.such.key");
+    }
+
+    #[test]
+    fn placeholders_are_filled_in() {
+        let english = Lang::English.catalogue();
+
+        assert_eq!(
+            english.format("hint.interval", &[("low", &34), ("high", &61)]),
+            "The number is between 34 and 61"
+        );
+        assert_eq!(
+            Lang::French.catalogue().format("game.you_guessed", &[("guess", &7)]),
+            "Vous avez proposé : 7"
+        );
+    }
+
+    #[test]
+    fn answers_are_matched_from_lists() {
+        let french = Lang::French.catalogue();
+
+        assert!(french.is_answer("session.yes", " Oui\n"));
+        assert!(french.is_answer("session.yes", "o"));
+        assert!(!french.is_answer("session.yes", "yes"));
+    }
+
+    #[test]
+    fn malformed_catalogues_are_rejected() {
+        assert_eq!(
+            Catalogue::parse("# comment\nno separator", None).unwrap_err(),
+            "line 2: expected key = value"
+        );
+        assert_eq!(
+            Catalogue::parse("a = 1\na = 2", None).unwrap_err(),
+            "line 2: a is defined twice"
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use std::fmt;
+use std::num::IntErrorKind;
+
+use crate::i18n::{Catalogue, Lang};
+
+// Why a line of input wasn't accepted as a guess.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputError {
+    NotANumber(String),
+    Negative,
+    OutOfRange { low: u32, high: u32 },
+}
+
+impl InputError {
+    pub fn message(&self, catalogue: &Catalogue) -> String {
+        match *self {
+            InputError::NotANumber(ref input) => {
+                catalogue.format("input.not_a_number", &[("input", &format!("{:?}", input))])
+            }
+            InputError::Negative => catalogue.get("input.negative").to_string(),
+            InputError::OutOfRange { low, high } => {
+                catalogue.format("input.out_of_range", &[("low", &low), ("high", &high)])
+            }
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message(Lang::English.catalogue()))
+    }
+}
+
+// Turns a line of input into a guess between low and high inclusive.
+//
+// Only plain decimal numbers are accepted unless lenient is set, in which case
+// surrounding whitespace is ignored and hexadecimal such as 0x2a is allowed.
+// The line ending, if there is one, is always ignored.
+pub fn parse_guess(line: &str, lenient: bool, low: u32, high: u32) -> Result<u32, InputError> {
+    let line = line.trim_end_matches(['\n', '\r']);
+    let text = if lenient { line.trim() } else { line };
+
+    // Only a leading - is a sign, the digits themselves must be plain.
+    let (negative, unsigned) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+
+    let (digits, radix) = match unsigned.strip_prefix("0x").or_else(|| unsigned.strip_prefix("0X")) {
+        Some(hex) if lenient => (hex, 16),
+        _ => (unsigned, 10),
+    };
+
+    if digits.starts_with(['+', '-']) {
+        return Err(InputError::NotANumber(line.to_string()));
+    }
+
+    let number = match u64::from_str_radix(digits, radix) {
+        Ok(number) => number,
+        Err(e) if *e.kind() == IntErrorKind::PosOverflow => u64::MAX,
+        Err(_) => return Err(InputError::NotANumber(line.to_string())),
+    };
+
+    if negative {
+        return Err(InputError::Negative);
+    }
+
+    if number < low as u64 || number > high as u64 {
+        return Err(InputError::OutOfRange { low, high });
+    }
+
+    Ok(number as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strict(line: &str) -> Result<u32, InputError> {
+        parse_guess(line, false, 1, 100)
+    }
+
+    fn lenient(line: &str) -> Result<u32, InputError> {
+        parse_guess(line, true, 1, 100)
+    }
+
+    #[test]
+    fn plain_numbers_are_accepted() {
+        assert_eq!(strict("42"), Ok(42));
+        assert_eq!(strict("42\n"), Ok(42));
+        assert_eq!(strict("1\r\n"), Ok(1));
+        assert_eq!(strict("100"), Ok(100));
+    }
+
+    #[test]
+    fn words_are_not_numbers() {
+        assert_eq!(strict("forty two"), Err(InputError::NotANumber("forty two".to_string())));
+        assert_eq!(strict("\n"), Err(InputError::NotANumber(String::new())));
+        assert_eq!(strict("+42"), Err(InputError::NotANumber("+42".to_string())));
+        assert_eq!(strict("-x"), Err(InputError::NotANumber("-x".to_string())));
+    }
+
+    #[test]
+    fn negative_numbers_are_rejected() {
+        assert_eq!(strict("-5"), Err(InputError::Negative));
+        assert_eq!(strict("-99999999999999999999999"), Err(InputError::Negative));
+        assert_eq!(lenient(" -0x10 "), Err(InputError::Negative));
+    }
+
+    #[test]
+    fn numbers_outside_the_range_are_rejected() {
+        let out_of_range = Err(InputError::OutOfRange { low: 1, high: 100 });
+
+        assert_eq!(strict("0"), out_of_range);
+        assert_eq!(strict("101"), out_of_range);
+        assert_eq!(strict("99999999999999999999999"), out_of_range);
+        assert_eq!(lenient("0xff"), out_of_range);
+    }
+
+    #[test]
+    fn whitespace_and_hex_need_lenient_parsing() {
+        assert_eq!(strict("  42 "), Err(InputError::NotANumber("  42 ".to_string())));
+        assert_eq!(strict("0x2a"), Err(InputError::NotANumber("0x2a".to_string())));
+
+        assert_eq!(lenient("  42 "), Ok(42));
+        assert_eq!(lenient("0x2a"), Ok(42));
+        assert_eq!(lenient("\t0X2A\n"), Ok(42));
+    }
+
+    #[test]
+    fn errors_explain_themselves() {
+        assert_eq!(strict("abc").unwrap_err().to_string(), "\"abc\" is not a number");
+        assert_eq!(strict("-1").unwrap_err().to_string(), "negative numbers are not allowed");
+        assert_eq!(strict("0").unwrap_err().to_string(), "the number must be between 1 and 100");
+    }
+}
@@ -0,0 +1,10 @@
+pub mod bulls;
+pub mod game;
+pub mod hints;
+pub mod i18n;
+pub mod input;
+pub mod reverse;
+pub mod secret;
+pub mod session;
+pub mod tui;
+pub mod words;
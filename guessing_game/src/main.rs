@@ -1,36 +1,306 @@
-extern crate rand;
+// Dependencies no longer need an extern crate rand; declaration, as was
+// required before the 2018 edition.
+use std::env;
+use std::fs::File;
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+use std::process;
 
-use std::io;
-use std::cmp::Ordering;
-use rand::Rng;
+use cmdline::{Arg, Command, Error};
+use guessing_game::bulls::{self, Rules};
+use guessing_game::game::Options;
+use guessing_game::hints::Hints;
+use guessing_game::i18n::Lang;
+use guessing_game::reverse;
+use guessing_game::secret::{FixedSecret, SecretSource, XorShift};
+use guessing_game::session;
+use guessing_game::tui;
+use guessing_game::words;
+
+#[derive(Debug, Default, PartialEq)]
+enum Mode {
+    #[default]
+    Classic,
+    Reverse,
+    Bulls,
+    Words,
+}
+
+#[derive(Debug)]
+struct Args {
+    mode: Mode,
+    seed: Option<u64>,
+    secret: Option<u32>,
+    xorshift: bool,
+    options: Options,
+    csv: Option<PathBuf>,
+    rules: Rules,
+    // A word list to read instead of the built in one.
+    words: Option<PathBuf>,
+    attempts: u32,
+    tui: bool,
+}
+
+fn command() -> Command {
+    Command::new("guessing_game")
+        .about("Guess the number!")
+        .arg(
+            Arg::option("mode")
+                .value_name("MODE")
+                .help("what to play, in reverse the computer guesses your number")
+                .choices(&["classic", "reverse", "bulls", "words"])
+                .default("classic"),
+        )
+        .arg(Arg::option("length").value_name("N").help("how many digits to guess in bulls mode").default("4"))
+        .arg(Arg::flag("duplicates").help("allow digits to repeat in bulls mode"))
+        .arg(Arg::option("words").value_name("FILE").help("a word list for words mode, one word per line"))
+        .arg(Arg::option("attempts").value_name("N").help("how many guesses words mode allows").default("6"))
+        .arg(Arg::option("seed").value_name("N").help("a seeded generator, so the same seed gives the same game"))
+        .arg(Arg::option("secret").value_name("N").help("always use N, handy when testing by hand"))
+        .arg(Arg::flag("xorshift").help("the built in generator rather than the one from rand"))
+        .arg(Arg::flag("warmer").help("say whether each guess is closer than the last"))
+        .arg(Arg::flag("interval").help("show the range the number must be in"))
+        .arg(Arg::flag("repeats").help("warn about numbers that have already been guessed"))
+        .arg(Arg::flag("history").help("list the guesses at the end of the game"))
+        .arg(Arg::flag("hints").help("all of the above hints"))
+        .arg(Arg::flag("lenient").help("allow spaces around guesses and hex such as 0x2a"))
+        .arg(Arg::option("csv").value_name("FILE").help("save the rounds played to FILE when the session ends"))
+        .arg(Arg::option("max-guesses").value_name("N").help("lose the round after N wrong guesses"))
+        .arg(Arg::flag("tui").help("draw the game full screen, when run in a terminal"))
+        .arg(
+            Arg::option("lang")
+                .value_name("LANG")
+                .help("the language to play in, en or fr, taken from LANG by default"),
+        )
+}
+
+// lang_env is the value of the LANG environment variable. --lang takes
+// precedence over it, and a LANG that isn't one of the bundled languages means
+// English.
+fn parse_args<I, S>(args: I, lang_env: Option<&str>) -> Result<Args, Error>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let matches = command().parse(args)?;
+
+    let mode = match matches.value::<String>("mode")?.as_deref() {
+        Some("reverse") => Mode::Reverse,
+        Some("bulls") => Mode::Bulls,
+        Some("words") => Mode::Words,
+        _ => Mode::Classic,
+    };
+
+    let lang = match matches.value::<String>("lang")? {
+        Some(code) => Lang::from_code(&code)
+            .ok_or_else(|| matches.error(&format!("unknown language {:?}, expected en or fr", code)))?,
+        None => lang_env.and_then(Lang::from_code).unwrap_or_default(),
+    };
+
+    let hints = if matches.flag("hints") {
+        Hints::all()
+    } else {
+        Hints {
+            warmer: matches.flag("warmer"),
+            interval: matches.flag("interval"),
+            repeats: matches.flag("repeats"),
+            history: matches.flag("history"),
+        }
+    };
+
+    let max_guesses = matches.value::<u32>("max-guesses")?;
+    let attempts = matches.value::<u32>("attempts")?.unwrap_or(words::DEFAULT_ATTEMPTS);
+
+    if max_guesses == Some(0) {
+        return Err(matches.error("--max-guesses must be at least 1"));
+    }
+
+    if attempts == 0 {
+        return Err(matches.error("--attempts must be at least 1"));
+    }
+
+    let length = matches.value::<usize>("length")?.unwrap_or_default();
+    let rules = Rules::new(length, matches.flag("duplicates"))
+        .map_err(|e| matches.error(&format!("--length: {}", e)))?;
+
+    Ok(Args {
+        mode,
+        seed: matches.value("seed")?,
+        secret: matches.value("secret")?,
+        xorshift: matches.flag("xorshift"),
+        options: Options {
+            hints,
+            lenient: matches.flag("lenient"),
+            max_guesses,
+            lang,
+            ..Options::default()
+        },
+        csv: matches.value("csv")?,
+        rules,
+        words: matches.value("words")?,
+        attempts,
+        tui: matches.flag("tui"),
+    })
+}
+
+// Without the rand feature the built in generator is always used.
+fn secret_source(args: &Args) -> Box<dyn SecretSource> {
+    if let Some(secret) = args.secret {
+        return Box::new(FixedSecret(secret));
+    }
+
+    if args.xorshift || !cfg!(feature = "rand") {
+        return Box::new(args.seed.map_or_else(XorShift::from_time, XorShift::new));
+    }
+
+    rand_source(args.seed)
+}
+
+#[cfg(feature = "rand")]
+fn rand_source(seed: Option<u64>) -> Box<dyn SecretSource> {
+    use guessing_game::secret::{SeededSource, ThreadRngSource};
+
+    match seed {
+        Some(seed) => Box::new(SeededSource::new(seed)),
+        None => Box::new(ThreadRngSource),
+    }
+}
+
+#[cfg(not(feature = "rand"))]
+fn rand_source(_: Option<u64>) -> Box<dyn SecretSource> {
+    unreachable!("the built in generator is used without the rand feature")
+}
 
 fn main() {
-    println!("Guess the number!");
+    let lang = env::var("LANG").ok();
+    let mut args = parse_args(env::args().skip(1), lang.as_deref()).unwrap_or_else(|e| e.exit());
 
-    let secret_number = rand::thread_rng().gen_range(1, 101);
+    args.options.interface = tui::choose(args.tui, io::stdout().is_terminal());
 
-    loop {
-        println!("Enter your guess");
+    let catalogue = args.options.lang.catalogue();
+    let stdin = io::stdin();
 
-        let mut guess = String::new();
+    if args.mode == Mode::Reverse {
+        if let Err(e) = reverse::play(args.options.lang, stdin.lock(), &mut io::stdout()) {
+            eprintln!("Failed to play: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
 
-        io::stdin().read_line(&mut guess)
-            .expect("Failed to read line");
+    let mut source = secret_source(&args);
 
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_)  => continue,
+    if args.mode == Mode::Words {
+        let list = match &args.words {
+            Some(path) => words::load_words(path).unwrap_or_else(|e| {
+                eprintln!("Failed to read {}: {}", path.display(), e);
+                process::exit(1);
+            }),
+            None => words::parse_words(words::WORDS),
         };
 
-        println!("You guessed: {}", guess);
+        match words::play(
+            source.as_mut(),
+            &list,
+            args.attempts,
+            args.options.lang,
+            stdin.lock(),
+            &mut io::stdout(),
+        ) {
+            Ok(words::Outcome::GaveUp { secret }) => {
+                println!("\n{}", catalogue.format("words.word_was", &[("secret", &secret)]));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("Failed to play: {}", e);
+                process::exit(1);
+            }
+        }
+        return;
+    }
 
-        match guess.cmp(&secret_number) {
-            Ordering::Less      => println!("Too small!"),
-            Ordering::Greater   => println!("Too big!"),
-            Ordering::Equal     => {
-                println!("You win!");
-                break;
+    if args.mode == Mode::Bulls {
+        match bulls::play(source.as_mut(), &args.rules, args.options.lang, stdin.lock(), &mut io::stdout()) {
+            Ok(bulls::Outcome::Won { .. }) => {}
+            Ok(bulls::Outcome::GaveUp { secret, .. }) => {
+                println!("\n{}", catalogue.format("bulls.number_was", &[("secret", &secret)]));
+            }
+            Err(e) => {
+                eprintln!("Failed to play: {}", e);
+                process::exit(1);
             }
         }
+        return;
+    }
+
+    let session = session::play(source.as_mut(), &args.options, stdin.lock(), &mut io::stdout())
+        .unwrap_or_else(|e| {
+            eprintln!("Failed to play: {}", e);
+            process::exit(1);
+        });
+
+    if let Some(path) = args.csv {
+        let written = File::create(&path).and_then(|mut file| session.write_csv(&mut file));
+
+        if let Err(e) = written {
+            eprintln!("Failed to write {}: {}", path.display(), e);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<Args, String> {
+        parse_args(args.iter().copied(), None).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn defaults_play_the_classic_game() {
+        let parsed = args(&[]).unwrap();
+
+        assert_eq!(parsed.mode, Mode::Classic);
+        assert_eq!(parsed.options, Options::default());
+        assert_eq!(parsed.rules, Rules::default());
+        assert_eq!(parsed.words, None);
+        assert_eq!(parsed.attempts, words::DEFAULT_ATTEMPTS);
+    }
+
+    #[test]
+    fn options_are_typed() {
+        let parsed = args(&["--mode", "bulls", "--length", "5", "--duplicates", "--seed", "9"]).unwrap();
+
+        assert_eq!(parsed.mode, Mode::Bulls);
+        assert_eq!(parsed.rules, Rules { length: 5, duplicates: true });
+        assert_eq!(parsed.seed, Some(9));
+        assert_eq!(args(&["--words", "fruit.txt"]).unwrap().words, Some(PathBuf::from("fruit.txt")));
+
+        assert_eq!(args(&["--seed", "x"]).unwrap_err(), "invalid value \"x\" for --seed: invalid digit found in string");
+        assert_eq!(args(&["--length", "11"]).unwrap_err(), "--length: length must be between 1 and 10");
+        assert_eq!(args(&["--max-guesses", "0"]).unwrap_err(), "--max-guesses must be at least 1");
+    }
+
+    #[test]
+    fn hints_can_be_chosen_one_at_a_time() {
+        assert_eq!(args(&["--warmer", "--history"]).unwrap().options.hints, Hints {
+            warmer: true,
+            history: true,
+            ..Hints::default()
+        });
+        assert_eq!(args(&["--hints"]).unwrap().options.hints, Hints::all());
+    }
+
+    #[test]
+    fn lang_option_beats_the_environment() {
+        let from_env = parse_args(Vec::<String>::new(), Some("fr_FR.UTF-8")).unwrap();
+        let from_option = parse_args(["--lang", "en"], Some("fr_FR.UTF-8")).unwrap();
+        let unknown = parse_args(Vec::<String>::new(), Some("de_DE.UTF-8")).unwrap();
+
+        assert_eq!(from_env.options.lang, Lang::French);
+        assert_eq!(from_option.options.lang, Lang::English);
+        assert_eq!(unknown.options.lang, Lang::English);
+        assert_eq!(args(&["--lang", "de"]).unwrap_err(), "unknown language \"de\", expected en or fr");
     }
 }
@@ -0,0 +1,288 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use crate::game::{HIGH, LOW};
+use crate::i18n::{Catalogue, Lang};
+
+// Answers that contradict each other, so the player must have cheated.
+//
+// above is the largest guess the player said their number was higher than
+// and below the smallest guess they said it was lower than. low and high are
+// the range the game was played over.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cheat {
+    pub above: Option<u32>,
+    pub below: Option<u32>,
+    pub low: u32,
+    pub high: u32,
+}
+
+impl Cheat {
+    pub fn message(&self, catalogue: &Catalogue) -> String {
+        match (self.above, self.below) {
+            (Some(above), Some(below)) => {
+                catalogue.format("reverse.cheat_both", &[("above", &above), ("below", &below)])
+            }
+            (Some(above), None) => {
+                catalogue.format("reverse.cheat_above", &[("above", &above), ("high", &self.high)])
+            }
+            (None, Some(below)) => {
+                catalogue.format("reverse.cheat_below", &[("below", &below), ("low", &self.low)])
+            }
+            (None, None) => catalogue.get("reverse.cheat_other").to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Cheat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message(Lang::English.catalogue()))
+    }
+}
+
+// Guesses a number between low and high by always picking the middle of the
+// range that is still possible, which needs at most seven guesses for 1 to
+// 100.
+//
+// low and high narrow as answers come in, while the range the player chose
+// from is kept in min and max.
+#[derive(Debug)]
+pub struct Guesser {
+    low: u32,
+    high: u32,
+    min: u32,
+    max: u32,
+    above: Option<u32>,
+    below: Option<u32>,
+    guesses: u32,
+}
+
+impl Guesser {
+    pub fn new(low: u32, high: u32) -> Guesser {
+        Guesser { low, high, min: low, max: high, above: None, below: None, guesses: 0 }
+    }
+
+    pub fn next_guess(&mut self) -> u32 {
+        self.guesses += 1;
+        self.low + (self.high - self.low) / 2
+    }
+
+    // Narrows the range using how the player's number compares with a guess,
+    // in the same sense as secret.cmp(&guess) in the normal game.
+    pub fn answer(&mut self, guess: u32, ordering: Ordering) -> Result<(), Cheat> {
+        match ordering {
+            Ordering::Greater => {
+                self.above = Some(self.above.map_or(guess, |a| a.max(guess)));
+                self.low = self.low.max(guess.saturating_add(1));
+            }
+            Ordering::Less => {
+                self.below = Some(self.below.map_or(guess, |b| b.min(guess)));
+                self.high = self.high.min(guess.saturating_sub(1));
+            }
+            Ordering::Equal => return Ok(()),
+        }
+
+        // Saying higher than the largest number, or lower than the smallest,
+        // leaves nothing in range even though the bounds haven't crossed.
+        let impossible = self.above.is_some_and(|a| a >= self.max)
+            || self.below.is_some_and(|b| b <= self.min);
+
+        if self.low > self.high || impossible {
+            return Err(Cheat { above: self.above, below: self.below, low: self.min, high: self.max });
+        }
+
+        Ok(())
+    }
+
+    pub fn guesses(&self) -> u32 {
+        self.guesses
+    }
+}
+
+// Turns the player's reply into how their number compares with the guess.
+// The replies that are understood depend on the language.
+pub fn parse_answer(answer: &str, catalogue: &Catalogue) -> Option<Ordering> {
+    if catalogue.is_answer("reverse.higher", answer) {
+        Some(Ordering::Greater)
+    } else if catalogue.is_answer("reverse.lower", answer) {
+        Some(Ordering::Less)
+    } else if catalogue.is_answer("reverse.correct", answer) {
+        Some(Ordering::Equal)
+    } else {
+        None
+    }
+}
+
+// How a reverse game ended.
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Guessed { guesses: u32 },
+    Cheated { guesses: u32, cheat: Cheat },
+    // Input ran out before the number was guessed.
+    GaveUp { guesses: u32 },
+}
+
+// Plays a game where the player thinks of a number and the program guesses.
+pub fn play<R, W>(lang: Lang, input: R, output: &mut W) -> io::Result<Outcome>
+where
+    R: BufRead,
+    W: Write,
+{
+    let catalogue = lang.catalogue();
+
+    writeln!(output, "{}", catalogue.format("reverse.title", &[("low", &LOW), ("high", &HIGH)]))?;
+
+    let mut guesser = Guesser::new(LOW, HIGH);
+    let mut lines = input.lines();
+
+    loop {
+        let guess = guesser.next_guess();
+
+        writeln!(output, "{}", catalogue.format("reverse.question", &[("guess", &guess)]))?;
+
+        let ordering = loop {
+            let answer = match lines.next() {
+                Some(line) => line?,
+                None => return Ok(Outcome::GaveUp { guesses: guesser.guesses() }),
+            };
+
+            match parse_answer(&answer, catalogue) {
+                Some(ordering) => break ordering,
+                None => writeln!(output, "{}", catalogue.get("reverse.help"))?,
+            }
+        };
+
+        if ordering == Ordering::Equal {
+            writeln!(
+                output,
+                "{}",
+                catalogue.format("reverse.got_it", &[("guesses", &guesser.guesses())])
+            )?;
+            return Ok(Outcome::Guessed { guesses: guesser.guesses() });
+        }
+
+        if let Err(cheat) = guesser.answer(guess, ordering) {
+            let reason = cheat.message(catalogue);
+
+            writeln!(output, "{}", catalogue.format("reverse.cheat", &[("reason", &reason)]))?;
+            return Ok(Outcome::Cheated { guesses: guesser.guesses(), cheat });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    // Plays the guesser against an honest player thinking of secret.
+    fn guesses_needed(secret: u32) -> u32 {
+        let mut guesser = Guesser::new(LOW, HIGH);
+
+        loop {
+            let guess = guesser.next_guess();
+            let ordering = secret.cmp(&guess);
+
+            if ordering == Ordering::Equal {
+                return guesser.guesses();
+            }
+
+            guesser.answer(guess, ordering).unwrap();
+        }
+    }
+
+    fn run(input: &str) -> (Outcome, String) {
+        let mut output = Vec::new();
+        let outcome = play(Lang::English, Cursor::new(input), &mut output).unwrap();
+
+        (outcome, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn every_number_is_found_within_seven_guesses() {
+        for secret in LOW..=HIGH {
+            assert!(guesses_needed(secret) <= 7, "{} took too many guesses", secret);
+        }
+
+        assert_eq!(guesses_needed(50), 1);
+    }
+
+    #[test]
+    fn answers_are_parsed() {
+        let english = Lang::English.catalogue();
+        let french = Lang::French.catalogue();
+
+        assert_eq!(parse_answer("higher", english), Some(Ordering::Greater));
+        assert_eq!(parse_answer(" Lower\n", english), Some(Ordering::Less));
+        assert_eq!(parse_answer("c", english), Some(Ordering::Equal));
+        assert_eq!(parse_answer("maybe", english), None);
+
+        assert_eq!(parse_answer("plus", french), Some(Ordering::Greater));
+        assert_eq!(parse_answer("Trouvé", french), Some(Ordering::Equal));
+        assert_eq!(parse_answer("higher", french), None);
+    }
+
+    #[test]
+    fn contradictions_are_caught() {
+        let mut guesser = Guesser::new(LOW, HIGH);
+
+        assert!(guesser.answer(50, Ordering::Greater).is_ok());
+        assert_eq!(
+            guesser.answer(40, Ordering::Less),
+            Err(Cheat { above: Some(50), below: Some(40), low: LOW, high: HIGH })
+        );
+    }
+
+    #[test]
+    fn answers_past_the_ends_of_the_range_are_caught() {
+        let cheat = Guesser::new(LOW, HIGH).answer(HIGH, Ordering::Greater).unwrap_err();
+        assert_eq!(cheat.to_string(), "you said higher than 100, but the number is at most 100");
+
+        let cheat = Guesser::new(LOW, HIGH).answer(LOW, Ordering::Less).unwrap_err();
+        assert_eq!(cheat.to_string(), "you said lower than 1, but the number is at least 1");
+    }
+
+    #[test]
+    fn the_guessers_own_range_is_checked() {
+        let mut guesser = Guesser::new(10, 20);
+
+        assert_eq!(guesser.next_guess(), 15);
+        assert!(guesser.answer(15, Ordering::Greater).is_ok());
+
+        let cheat = guesser.answer(20, Ordering::Greater).unwrap_err();
+        assert_eq!(cheat.to_string(), "you said higher than 20, but the number is at most 20");
+
+        let cheat = Guesser::new(10, 20).answer(10, Ordering::Less).unwrap_err();
+        assert_eq!(cheat.to_string(), "you said lower than 10, but the number is at least 10");
+    }
+
+    #[test]
+    fn playing_honestly() {
+        // Thinking of 30: 50, 25, 37, 31, 28, 29, 30.
+        let (outcome, output) = run("lower\nhigher\nlower\nlower\nhigher\nh\nwhat?\ncorrect\n");
+
+        assert_eq!(outcome, Outcome::Guessed { guesses: 7 });
+        assert!(output.contains("Is it 29? (higher, lower or correct)\n"));
+        assert!(output.ends_with(
+            "Is it 30? (higher, lower or correct)\nPlease answer higher, lower or correct\nGot it in 7 guesses!\n"
+        ));
+    }
+
+    #[test]
+    fn cheating_is_reported() {
+        // 50, 75, 62, 56, 53, 51 then nothing is left.
+        let (outcome, output) = run("higher\nlower\nlower\nlower\nlower\nlower\n");
+
+        assert_eq!(
+            outcome,
+            Outcome::Cheated { guesses: 6, cheat: Cheat { above: Some(50), below: Some(51), low: LOW, high: HIGH } }
+        );
+        assert!(output.ends_with("Cheat! you said higher than 50 and lower than 51\n"));
+    }
+
+    #[test]
+    fn running_out_of_input_gives_up() {
+        assert_eq!(run("higher\n").0, Outcome::GaveUp { guesses: 2 });
+    }
+}
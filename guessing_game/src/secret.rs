@@ -0,0 +1,178 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Somewhere to get the secret number from.
+//
+// The game only asks for a number in an inclusive range, so it doesn't care
+// whether that comes from a random number generator or is fixed in advance,
+// which is what makes the game testable.
+pub trait SecretSource {
+    // Returns a number between low and high inclusive.
+    fn secret(&mut self, low: u32, high: u32) -> u32;
+}
+
+// Always returns the same secret, clamped to the requested range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedSecret(pub u32);
+
+impl SecretSource for FixedSecret {
+    fn secret(&mut self, low: u32, high: u32) -> u32 {
+        self.0.clamp(low, high)
+    }
+}
+
+// The thread local generator from the rand crate, seeded by the OS.
+#[cfg(feature = "rand")]
+#[derive(Debug, Default)]
+pub struct ThreadRngSource;
+
+#[cfg(feature = "rand")]
+impl SecretSource for ThreadRngSource {
+    fn secret(&mut self, low: u32, high: u32) -> u32 {
+        use rand::Rng;
+
+        rand::thread_rng().gen_range(low..=high)
+    }
+}
+
+// A generator from the rand crate with a fixed seed, so the same seed always
+// produces the same sequence of secrets.
+#[cfg(feature = "rand")]
+#[derive(Debug)]
+pub struct SeededSource(rand::rngs::StdRng);
+
+#[cfg(feature = "rand")]
+impl SeededSource {
+    pub fn new(seed: u64) -> SeededSource {
+        use rand::SeedableRng;
+
+        SeededSource(rand::rngs::StdRng::seed_from_u64(seed))
+    }
+}
+
+#[cfg(feature = "rand")]
+impl SecretSource for SeededSource {
+    fn secret(&mut self, low: u32, high: u32) -> u32 {
+        use rand::Rng;
+
+        self.0.gen_range(low..=high)
+    }
+}
+
+// A small xorshift64* generator that needs no external crates.
+//
+// It's nowhere near good enough for cryptography but is plenty for picking a
+// number between 1 and 100.
+#[derive(Debug, Clone)]
+pub struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    pub fn new(seed: u64) -> XorShift {
+        // An all zero state would only ever produce zeros, so the seed is
+        // mixed with a constant first. The splitmix64 increment is used since
+        // it's odd and has plenty of bits set.
+        let state = seed ^ 0x9e37_79b9_7f4a_7c15;
+
+        XorShift { state: if state == 0 { 1 } else { state } }
+    }
+
+    // Seeds the generator from the current time.
+    pub fn from_time() -> XorShift {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+
+        XorShift::new(now)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+}
+
+impl SecretSource for XorShift {
+    fn secret(&mut self, low: u32, high: u32) -> u32 {
+        assert!(low <= high, "empty range {}..={}", low, high);
+
+        let span = (high - low) as u64 + 1;
+
+        // Scale the 64 bit value down to the span by taking the high bits of
+        // the product, which avoids the bias towards small numbers that taking
+        // the remainder of a division would give for large spans.
+        let scaled = ((self.next_u64() as u128 * span as u128) >> 64) as u64;
+
+        low + scaled as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draws<S: SecretSource>(source: &mut S, count: usize) -> Vec<u32> {
+        (0..count).map(|_| source.secret(1, 100)).collect()
+    }
+
+    #[test]
+    fn fixed_secret_is_clamped_to_range() {
+        assert_eq!(FixedSecret(42).secret(1, 100), 42);
+        assert_eq!(FixedSecret(0).secret(1, 100), 1);
+        assert_eq!(FixedSecret(500).secret(1, 100), 100);
+    }
+
+    #[test]
+    fn xorshift_is_deterministic_for_a_seed() {
+        let first = draws(&mut XorShift::new(7), 20);
+        let second = draws(&mut XorShift::new(7), 20);
+        let other = draws(&mut XorShift::new(8), 20);
+
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn xorshift_accepts_zero_seed() {
+        let values = draws(&mut XorShift::new(0), 10);
+
+        assert!(values.iter().any(|&v| v != values[0]));
+    }
+
+    #[test]
+    fn xorshift_stays_in_range_and_covers_it() {
+        let mut rng = XorShift::new(12345);
+        let mut seen = [false; 10];
+
+        for _ in 0..1000 {
+            let n = rng.secret(3, 12);
+            assert!((3..=12).contains(&n));
+            seen[(n - 3) as usize] = true;
+        }
+
+        assert!(seen.iter().all(|&s| s));
+        assert_eq!(rng.secret(5, 5), 5);
+
+        // The full range must not overflow when working out the span.
+        rng.secret(0, u32::MAX);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn seeded_source_is_deterministic() {
+        let first = draws(&mut SeededSource::new(42), 20);
+        let second = draws(&mut SeededSource::new(42), 20);
+
+        assert_eq!(first, second);
+        assert!(first.iter().all(|n| (1..=100).contains(n)));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn thread_rng_stays_in_range() {
+        assert!(draws(&mut ThreadRngSource, 100).iter().all(|n| (1..=100).contains(n)));
+    }
+}
@@ -0,0 +1,301 @@
+use std::io::{self, BufRead, Write};
+use std::time::{Duration, Instant};
+
+use crate::game::{self, Options, Outcome};
+use crate::i18n::Catalogue;
+use crate::secret::SecretSource;
+
+// One game within a session.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Round {
+    pub guesses: u32,
+    pub invalid: u32,
+    pub time: Duration,
+    // False if the number wasn't guessed.
+    pub won: bool,
+}
+
+// The rounds played in one sitting.
+//
+// Averages, best and worst only take rounds that were won into account, since
+// a round that was given up on says little about how many guesses it needed.
+#[derive(Debug, Default)]
+pub struct Session {
+    rounds: Vec<Round>,
+}
+
+impl Session {
+    pub fn new() -> Session {
+        Session::default()
+    }
+
+    pub fn push(&mut self, round: Round) {
+        self.rounds.push(round);
+    }
+
+    pub fn rounds(&self) -> &[Round] {
+        &self.rounds
+    }
+
+    fn won(&self) -> impl Iterator<Item = &Round> {
+        self.rounds.iter().filter(|r| r.won)
+    }
+
+    pub fn average(&self) -> Option<f64> {
+        let (count, total) = self.won().fold((0, 0), |(n, t), r| (n + 1, t + r.guesses));
+
+        if count == 0 {
+            None
+        } else {
+            Some(total as f64 / count as f64)
+        }
+    }
+
+    pub fn best(&self) -> Option<u32> {
+        self.won().map(|r| r.guesses).min()
+    }
+
+    pub fn worst(&self) -> Option<u32> {
+        self.won().map(|r| r.guesses).max()
+    }
+
+    // Writes every round as CSV, with a header line.
+    pub fn write_csv<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        writeln!(output, "round,guesses,invalid,seconds,won")?;
+
+        for (i, round) in self.rounds.iter().enumerate() {
+            writeln!(
+                output,
+                "{},{},{},{:.3},{}",
+                i + 1,
+                round.guesses,
+                round.invalid,
+                round.time.as_secs_f64(),
+                round.won
+            )?;
+        }
+
+        Ok(())
+    }
+
+    // A bar for each guess count from best to worst, showing how many rounds
+    // were won with that many guesses.
+    pub fn histogram(&self) -> String {
+        let (best, worst) = match (self.best(), self.worst()) {
+            (Some(best), Some(worst)) => (best, worst),
+            _ => return String::new(),
+        };
+
+        let width = worst.to_string().len();
+
+        (best..=worst)
+            .map(|guesses| {
+                let rounds = self.won().filter(|r| r.guesses == guesses).count();
+
+                format!("{:>width$} | {} {}\n", guesses, "#".repeat(rounds), rounds, width = width)
+            })
+            .collect()
+    }
+
+    // Totals for the session followed by the histogram.
+    pub fn summary(&self, catalogue: &Catalogue) -> String {
+        let mut summary = catalogue.format("session.rounds", &[("rounds", &self.rounds.len())]);
+        summary.push('\n');
+
+        if let (Some(average), Some(best), Some(worst)) = (self.average(), self.best(), self.worst()) {
+            let time: Duration = self.won().map(|r| r.time).sum();
+            let won = self.won().count() as u32;
+
+            let average = format!("{:.1}", average);
+            let seconds = format!("{:.1}", (time / won).as_secs_f64());
+
+            summary += &catalogue.format(
+                "session.guesses",
+                &[("average", &average), ("best", &best), ("worst", &worst)],
+            );
+            summary += "\n";
+            summary += &catalogue.format("session.time", &[("seconds", &seconds)]);
+            summary += "\n\n";
+            summary += &self.histogram();
+        }
+
+        summary
+    }
+}
+
+// Plays rounds until the player doesn't want to play again, or input runs out.
+pub fn play<S, R, W>(
+    source: &mut S,
+    options: &Options,
+    mut input: R,
+    output: &mut W,
+) -> io::Result<Session>
+where
+    S: SecretSource + ?Sized,
+    R: BufRead,
+    W: Write,
+{
+    let catalogue = options.lang.catalogue();
+    let mut session = Session::new();
+
+    loop {
+        let start = Instant::now();
+        let outcome = game::play(source, options, &mut input, output)?;
+        let time = start.elapsed();
+
+        let round = match outcome {
+            Outcome::Won { stats } => {
+                Round { guesses: stats.guesses, invalid: stats.invalid, time, won: true }
+            }
+            Outcome::Lost { stats, .. } => {
+                Round { guesses: stats.guesses, invalid: stats.invalid, time, won: false }
+            }
+            Outcome::GaveUp { secret, stats } => {
+                writeln!(output, "\n{}", catalogue.format("session.number_was", &[("secret", &secret)]))?;
+                session.push(Round { guesses: stats.guesses, invalid: stats.invalid, time, won: false });
+                break;
+            }
+        };
+
+        session.push(round);
+
+        if !play_again(&mut input, catalogue, output)? {
+            break;
+        }
+    }
+
+    write!(output, "\n{}", session.summary(catalogue))?;
+
+    Ok(session)
+}
+
+fn play_again<R, W>(input: &mut R, catalogue: &Catalogue, output: &mut W) -> io::Result<bool>
+where
+    R: BufRead,
+    W: Write,
+{
+    loop {
+        writeln!(output, "{}", catalogue.get("session.play_again"))?;
+
+        let mut answer = String::new();
+
+        if input.read_line(&mut answer)? == 0 {
+            return Ok(false);
+        }
+
+        if catalogue.is_answer("session.yes", &answer) {
+            return Ok(true);
+        } else if catalogue.is_answer("session.no", &answer) {
+            return Ok(false);
+        }
+
+        writeln!(output, "{}", catalogue.get("session.yes_or_no"))?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::Lang;
+    use crate::secret::FixedSecret;
+    use std::io::Cursor;
+
+    fn round(guesses: u32, seconds: u64) -> Round {
+        Round { guesses, invalid: 0, time: Duration::from_secs(seconds), won: true }
+    }
+
+    fn session(rounds: &[Round]) -> Session {
+        let mut session = Session::new();
+
+        for &round in rounds {
+            session.push(round);
+        }
+
+        session
+    }
+
+    #[test]
+    fn empty_sessions_have_no_stats() {
+        let session = Session::new();
+
+        assert_eq!(session.average(), None);
+        assert_eq!(session.best(), None);
+        assert_eq!(session.worst(), None);
+        assert_eq!(session.histogram(), "");
+        assert_eq!(session.summary(Lang::English.catalogue()), "Rounds played: 0\n");
+    }
+
+    #[test]
+    fn stats_only_count_rounds_that_were_won() {
+        let lost = Round { guesses: 20, invalid: 1, time: Duration::from_secs(9), won: false };
+        let session = session(&[round(4, 2), round(7, 4), lost, round(4, 6)]);
+
+        assert_eq!(session.average(), Some(5.0));
+        assert_eq!(session.best(), Some(4));
+        assert_eq!(session.worst(), Some(7));
+    }
+
+    #[test]
+    fn histogram_has_a_bar_for_every_count_between_best_and_worst() {
+        let session = session(&[round(9, 1), round(12, 1), round(9, 1)]);
+
+        assert_eq!(session.histogram(), " 9 | ## 2\n10 |  0\n11 |  0\n12 | # 1\n");
+    }
+
+    #[test]
+    fn sessions_export_to_csv() {
+        let lost = Round { guesses: 2, invalid: 3, time: Duration::from_millis(1500), won: false };
+        let mut csv = Vec::new();
+
+        session(&[round(5, 10), lost]).write_csv(&mut csv).unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "round,guesses,invalid,seconds,won\n1,5,0,10.000,true\n2,2,3,1.500,false\n"
+        );
+    }
+
+    #[test]
+    fn rounds_are_played_until_the_player_stops() {
+        let input = "50\n42\ny\n42\nmaybe\nn\n";
+        let mut output = Vec::new();
+
+        let session = play(&mut FixedSecret(42), &Options::default(), Cursor::new(input), &mut output)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        let guesses: Vec<u32> = session.rounds().iter().map(|r| r.guesses).collect();
+        assert_eq!(guesses, vec![2, 1]);
+        assert!(output.contains("Please answer y or n\n"));
+        assert!(output.contains("Rounds played: 2\nGuesses: average 1.5, best 1, worst 2\n"));
+        assert!(output.ends_with("1 | # 1\n2 | # 1\n"));
+    }
+
+    #[test]
+    fn play_again_answers_follow_the_language() {
+        let options = Options { lang: Lang::French, ..Options::default() };
+        let mut output = Vec::new();
+
+        let session = play(&mut FixedSecret(42), &options, Cursor::new("42\ny\noui\n42\nnon\n"), &mut output)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert_eq!(session.rounds().len(), 2);
+        assert!(output.contains("Rejouer ? (o/n)\nRépondez o ou n\n"));
+        assert!(output.contains("Parties jouées : 2\n"));
+    }
+
+    #[test]
+    fn running_out_of_input_ends_the_session() {
+        let mut output = Vec::new();
+
+        let session = play(&mut FixedSecret(42), &Options::default(), Cursor::new("42\ny\n1\n"), &mut output)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert_eq!(session.rounds().len(), 2);
+        assert!(!session.rounds()[1].won);
+        assert!(output.contains("The number was 42\n"));
+        assert!(output.contains("Rounds played: 2\nGuesses: average 1.0, best 1, worst 1\n"));
+    }
+}
@@ -0,0 +1,298 @@
+use std::cmp::Ordering;
+use std::io::{self, BufRead, Write};
+
+use crate::game::{Interface, Options, Outcome, Stats, HIGH, LOW};
+use crate::hints::Tracker;
+use crate::i18n::Catalogue;
+use crate::input::parse_guess;
+use crate::secret::SecretSource;
+
+// A full screen version of the game drawn with ANSI escape codes, so it needs
+// nothing more than a terminal that understands them. The whole screen is
+// cleared and drawn again after every line of input.
+
+const CLEAR: &str = "\x1b[2J\x1b[H";
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const BLUE: &str = "\x1b[34m";
+
+// How many columns the number line takes up.
+const WIDTH: u32 = 50;
+
+// How many of the latest guesses the history panel shows.
+const HISTORY: usize = 10;
+
+// The terminal interface only makes sense when a person is watching, so it
+// falls back to plain output when stdout is redirected to a file or a pipe.
+pub fn choose(requested: bool, is_terminal: bool) -> Interface {
+    if requested && is_terminal {
+        Interface::Terminal
+    } else {
+        Interface::Plain
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
+    Start,
+    TooBig(u32),
+    TooSmall(u32),
+    Invalid(String),
+    Won(u32),
+    Lost(u32),
+}
+
+// Everything shown on one screen.
+pub struct View<'a> {
+    pub catalogue: &'a Catalogue,
+    pub tracker: &'a Tracker,
+    pub secret: u32,
+    pub message: Message,
+    // Hints and warnings about the latest guess.
+    pub notes: Vec<String>,
+    pub remaining: Option<u32>,
+}
+
+// A bar from LOW to HIGH where the part the number can still be in is
+// highlighted, so it shrinks as the guesses close in.
+pub fn number_line(low: u32, high: u32) -> String {
+    let span = HIGH - LOW + 1;
+    let mut line = format!("{:>3} ", LOW);
+    let mut inside = None;
+
+    for column in 0..WIDTH {
+        // The numbers this column stands for.
+        let first = LOW + column * span / WIDTH;
+        let last = (LOW + (column + 1) * span / WIDTH).saturating_sub(1).max(first);
+
+        let possible = first <= high && last >= low;
+
+        if inside != Some(possible) {
+            line += if possible { GREEN } else { DIM };
+            inside = Some(possible);
+        }
+
+        line.push(if possible { '█' } else { '·' });
+    }
+
+    line + RESET + &format!(" {}", HIGH)
+}
+
+pub fn render(view: &View) -> String {
+    let catalogue = view.catalogue;
+    let (low, high) = view.tracker.interval();
+    let mut screen = format!("{}{}{}{}\n\n", CLEAR, BOLD, catalogue.get("game.title"), RESET);
+
+    screen += &number_line(low, high);
+    screen += &format!(
+        "\n    {}\n\n",
+        catalogue.format("hint.interval", &[("low", &low), ("high", &high)])
+    );
+
+    let (colour, message) = match view.message {
+        Message::Start => ("", catalogue.get("game.prompt").to_string()),
+        Message::TooBig(guess) => (RED, catalogue.format("tui.too_big", &[("guess", &guess)])),
+        Message::TooSmall(guess) => (BLUE, catalogue.format("tui.too_small", &[("guess", &guess)])),
+        Message::Invalid(ref reason) => {
+            (YELLOW, catalogue.format("game.invalid", &[("reason", reason)]))
+        }
+        Message::Won(guess) => (GREEN, catalogue.format("tui.won", &[("guess", &guess)])),
+        Message::Lost(secret) => {
+            (RED, catalogue.format("game.out_of_guesses", &[("secret", &secret)]))
+        }
+    };
+
+    screen += &format!("{}{}{}", colour, message, RESET);
+    screen += "\n";
+
+    for note in &view.notes {
+        screen += &format!("{}{}{}\n", DIM, note, RESET);
+    }
+
+    screen += &format!("\n{}{}{}\n", BOLD, catalogue.get("tui.history"), RESET);
+
+    let guesses = view.tracker.guesses();
+
+    for (i, &guess) in guesses.iter().enumerate().skip(guesses.len().saturating_sub(HISTORY)) {
+        let (colour, verdict) = match guess.cmp(&view.secret) {
+            Ordering::Less => (BLUE, "tui.verdict_small"),
+            Ordering::Greater => (RED, "tui.verdict_big"),
+            Ordering::Equal => (GREEN, "tui.verdict_right"),
+        };
+
+        screen += &format!("{:>3}. {:>3} {}{}{}\n", i + 1, guess, colour, catalogue.get(verdict), RESET);
+    }
+
+    if let Some(remaining) = view.remaining {
+        screen += &format!("\n{}\n", catalogue.format("tui.remaining", &[("remaining", &remaining)]));
+    }
+
+    screen
+}
+
+// The same game as game::play, drawn full screen.
+pub fn play<S, R, W>(source: &mut S, options: &Options, input: R, output: &mut W) -> io::Result<Outcome>
+where
+    S: SecretSource + ?Sized,
+    R: BufRead,
+    W: Write,
+{
+    let catalogue = options.lang.catalogue();
+    let secret = source.secret(LOW, HIGH);
+    let mut tracker = Tracker::new(options.hints, LOW, HIGH);
+    let mut stats = Stats::default();
+    let mut message = Message::Start;
+    let mut notes = Vec::new();
+    let mut lines = input.lines();
+
+    loop {
+        let remaining = options.max_guesses.map(|max| max.saturating_sub(stats.guesses));
+        let view = View { catalogue, tracker: &tracker, secret, message, notes, remaining };
+
+        write!(output, "{}", render(&view))?;
+
+        let outcome = match view.message {
+            Message::Won(_) => Some(Outcome::Won { stats }),
+            Message::Lost(_) => Some(Outcome::Lost { secret, stats }),
+            _ => None,
+        };
+
+        if let Some(outcome) = outcome {
+            return Ok(outcome);
+        }
+
+        write!(output, "\n> ")?;
+        output.flush()?;
+
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => {
+                writeln!(output)?;
+                return Ok(Outcome::GaveUp { secret, stats });
+            }
+        };
+
+        let guess = match parse_guess(&line, options.lenient, LOW, HIGH) {
+            Ok(guess) => guess,
+            Err(e) => {
+                stats.invalid += 1;
+                message = Message::Invalid(e.message(catalogue));
+                notes = Vec::new();
+                continue;
+            }
+        };
+
+        stats.guesses += 1;
+        notes = tracker.warnings(guess).iter().map(|w| w.message(catalogue)).collect();
+
+        let hints = tracker.record(guess, secret);
+        notes.extend(hints.iter().map(|h| h.message(catalogue)));
+
+        message = match guess.cmp(&secret) {
+            Ordering::Equal => Message::Won(guess),
+            _ if options.max_guesses.is_some_and(|max| stats.guesses >= max) => Message::Lost(secret),
+            Ordering::Less => Message::TooSmall(guess),
+            Ordering::Greater => Message::TooBig(guess),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hints::Hints;
+    use crate::secret::FixedSecret;
+    use std::io::Cursor;
+
+    // The screen with the escape codes taken out.
+    fn plain(screen: &str) -> String {
+        let mut text = String::new();
+        let mut chars = screen.chars();
+
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|c| c.is_ascii_alphabetic());
+            } else {
+                text.push(c);
+            }
+        }
+
+        text
+    }
+
+    fn run(options: &Options, input: &str) -> (Outcome, String) {
+        let mut output = Vec::new();
+        let outcome = play(&mut FixedSecret(42), options, Cursor::new(input), &mut output).unwrap();
+
+        (outcome, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn the_terminal_interface_needs_a_terminal() {
+        assert_eq!(choose(true, true), Interface::Terminal);
+        assert_eq!(choose(true, false), Interface::Plain);
+        assert_eq!(choose(false, true), Interface::Plain);
+    }
+
+    #[test]
+    fn number_line_shrinks_to_the_interval() {
+        let full = plain(&number_line(LOW, HIGH));
+        assert_eq!(full, format!("  1 {} 100", "█".repeat(50)));
+
+        // Each column stands for two numbers, so 34 to 61 covers columns 16
+        // to 30.
+        let narrowed = plain(&number_line(34, 61));
+        assert_eq!(
+            narrowed,
+            format!("  1 {}{}{} 100", "·".repeat(16), "█".repeat(15), "·".repeat(19))
+        );
+
+        assert_eq!(plain(&number_line(100, 100)).matches('█').count(), 1);
+    }
+
+    #[test]
+    fn every_guess_redraws_the_screen() {
+        let options = Options { interface: Interface::Terminal, ..Options::default() };
+        let (outcome, output) = run(&options, "50\nfifty\n20\n42\n");
+
+        assert_eq!(outcome, Outcome::Won { stats: Stats { guesses: 3, invalid: 1 } });
+        assert_eq!(output.matches(CLEAR).count(), 5);
+
+        let last = plain(output.rsplit(CLEAR).next().unwrap());
+        assert!(last.contains("The number is between 42 and 42\n"));
+        assert!(last.contains("42 is right, you win!"));
+        assert!(last.contains("  1.  50 too big\n  2.  20 too small\n  3.  42 right\n"));
+        assert!(plain(&output).contains("Invalid guess, \"fifty\" is not a number"));
+    }
+
+    #[test]
+    fn messages_are_coloured() {
+        let options = Options { interface: Interface::Terminal, ..Options::default() };
+        let (_, output) = run(&options, "50\n20\n");
+
+        assert!(output.contains(&format!("{}50 is too big!{}", RED, RESET)));
+        assert!(output.contains(&format!("{}20 is too small!{}", BLUE, RESET)));
+    }
+
+    #[test]
+    fn attempts_remaining_count_down() {
+        let options = Options {
+            interface: Interface::Terminal,
+            max_guesses: Some(2),
+            hints: Hints::all(),
+            ..Options::default()
+        };
+        let (outcome, output) = run(&options, "50\n60\n");
+        let output = plain(&output);
+
+        assert_eq!(outcome, Outcome::Lost { secret: 42, stats: Stats { guesses: 2, invalid: 0 } });
+        assert!(output.contains("Attempts remaining: 2\n"));
+        assert!(output.contains("Attempts remaining: 1\n"));
+        assert!(output.contains("Out of guesses, the number was 42\n60 can't be right"));
+        assert!(output.contains("Colder!\n"));
+    }
+}
@@ -0,0 +1,269 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use crate::i18n::Lang;
+use crate::secret::SecretSource;
+
+// Words are compared a char at a time rather than a byte at a time. As the
+// strings chapter explains, a String is UTF-8 so a letter such as é takes more
+// than one byte, and counting or indexing bytes would split it in two.
+
+pub const DEFAULT_ATTEMPTS: u32 = 6;
+
+// The list that comes with the game. It's built into the binary, so it's there
+// wherever the game is installed or run from.
+pub const WORDS: &str = include_str!("../words.txt");
+
+// Reads a word list with one word per line, ignoring blank lines and lines
+// starting with #. Words are lowercased so guesses can be in any case.
+pub fn load_words(path: &Path) -> io::Result<Vec<String>> {
+    let words = parse_words(&fs::read_to_string(path)?);
+
+    if words.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "the word list is empty"));
+    }
+
+    Ok(words)
+}
+
+pub fn parse_words(list: &str) -> Vec<String> {
+    list.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_lowercase)
+        .collect()
+}
+
+pub fn pick<'a, S: SecretSource + ?Sized>(words: &'a [String], source: &mut S) -> &'a str {
+    assert!(!words.is_empty(), "no words to pick from");
+
+    &words[source.secret(0, words.len() as u32 - 1) as usize]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mark {
+    // The letter is in the word at this position.
+    Correct,
+    // The letter is in the word, somewhere else.
+    Present,
+    Absent,
+}
+
+impl Mark {
+    fn symbol(self) -> char {
+        match self {
+            Mark::Correct => '=',
+            Mark::Present => '~',
+            Mark::Absent => '.',
+        }
+    }
+}
+
+// Marks each letter of a guess against the secret, which must have the same
+// number of letters.
+//
+// A letter only counts as present as many times as it is left over in the
+// secret after the correct letters have been taken out, so guessing "eerie"
+// against "theme" marks a single e present rather than all three.
+pub fn feedback(secret: &[char], guess: &[char]) -> Vec<Mark> {
+    assert_eq!(secret.len(), guess.len(), "guess and secret differ in length");
+
+    let mut marks = vec![Mark::Absent; guess.len()];
+    let mut unused: Vec<Option<char>> = secret.iter().map(|&c| Some(c)).collect();
+
+    for (i, (&s, &g)) in secret.iter().zip(guess).enumerate() {
+        if s == g {
+            marks[i] = Mark::Correct;
+            unused[i] = None;
+        }
+    }
+
+    for (i, &g) in guess.iter().enumerate() {
+        if marks[i] == Mark::Correct {
+            continue;
+        }
+
+        if let Some(slot) = unused.iter_mut().find(|slot| **slot == Some(g)) {
+            marks[i] = Mark::Present;
+            *slot = None;
+        }
+    }
+
+    marks
+}
+
+// The marks as a line of symbols that sits under the guess, one per letter.
+pub struct Marks<'a>(pub &'a [Mark]);
+
+impl fmt::Display for Marks<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.iter().try_for_each(|mark| write!(f, "{}", mark.symbol()))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Won { attempts: u32 },
+    // Every attempt was used up.
+    Lost { secret: String },
+    // Input ran out before the attempts were used.
+    GaveUp { secret: String },
+}
+
+// Plays one game with a word picked from words.
+pub fn play<S, R, W>(
+    source: &mut S,
+    words: &[String],
+    attempts: u32,
+    lang: Lang,
+    input: R,
+    output: &mut W,
+) -> io::Result<Outcome>
+where
+    S: SecretSource + ?Sized,
+    R: BufRead,
+    W: Write,
+{
+    let catalogue = lang.catalogue();
+    let secret: Vec<char> = pick(words, source).chars().collect();
+    let mut lines = input.lines();
+    let mut used = 0;
+
+    writeln!(output, "{}", catalogue.format("words.title", &[("length", &secret.len())]))?;
+    writeln!(output, "{}", catalogue.get("words.legend"))?;
+
+    while used < attempts {
+        writeln!(output, "{}", catalogue.format("words.prompt", &[("attempts", &(attempts - used))]))?;
+
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => return Ok(Outcome::GaveUp { secret: secret.iter().collect() }),
+        };
+
+        let guess: Vec<char> = line.trim().to_lowercase().chars().collect();
+
+        if guess.len() != secret.len() {
+            let reason = catalogue.format("words.wrong_length", &[("length", &secret.len())]);
+
+            writeln!(output, "{}", catalogue.format("game.invalid", &[("reason", &reason)]))?;
+            continue;
+        }
+
+        used += 1;
+
+        let marks = feedback(&secret, &guess);
+
+        writeln!(output, "{}", guess.iter().collect::<String>())?;
+        writeln!(output, "{}", Marks(&marks))?;
+
+        if marks.iter().all(|&m| m == Mark::Correct) {
+            writeln!(output, "{}", catalogue.get("game.win"))?;
+            return Ok(Outcome::Won { attempts: used });
+        }
+    }
+
+    let secret: String = secret.iter().collect();
+
+    writeln!(output, "{}", catalogue.format("words.lost", &[("secret", &secret)]))?;
+
+    Ok(Outcome::Lost { secret })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secret::{FixedSecret, XorShift};
+    use std::io::Cursor;
+
+    use super::Mark::{Absent as A, Correct as C, Present as P};
+
+    fn marks(secret: &str, guess: &str) -> Vec<Mark> {
+        let secret: Vec<char> = secret.chars().collect();
+        let guess: Vec<char> = guess.chars().collect();
+
+        feedback(&secret, &guess)
+    }
+
+    fn words() -> Vec<String> {
+        parse_words(WORDS)
+    }
+
+    #[test]
+    fn word_lists_skip_comments_and_blank_lines() {
+        let list = parse_words("# fruit\n\nApple\n  pear  \n#\nCAFÉ\n");
+
+        assert_eq!(list, vec!["apple", "pear", "café"]);
+        assert!(words().len() > 10);
+    }
+
+    #[test]
+    fn letters_are_marked() {
+        assert_eq!(marks("crane", "crane"), vec![C, C, C, C, C]);
+        assert_eq!(marks("crane", "nacre"), vec![P, P, P, P, C]);
+        assert_eq!(marks("crane", "ghost"), vec![A, A, A, A, A]);
+    }
+
+    #[test]
+    fn repeated_letters_are_only_matched_once() {
+        assert_eq!(marks("theme", "eerie"), vec![P, A, A, A, C]);
+        assert_eq!(marks("abbey", "babes"), vec![P, P, C, C, A]);
+    }
+
+    #[test]
+    fn letters_are_chars_not_bytes() {
+        // é is two bytes but one letter, so café and cafe both have four.
+        assert_eq!("café".len(), 5);
+        assert_eq!(marks("café", "cafe"), vec![C, C, C, A]);
+        assert_eq!(marks("naïve", "ïnave"), vec![P, P, P, C, C]);
+    }
+
+    #[test]
+    fn marks_line_up_under_the_guess() {
+        assert_eq!(Marks(&[C, P, A]).to_string(), "=~.");
+    }
+
+    #[test]
+    fn a_fixed_seed_picks_the_same_word() {
+        let words = words();
+
+        let first = pick(&words, &mut XorShift::new(2024));
+        let second = pick(&words, &mut XorShift::new(2024));
+
+        assert_eq!(first, second);
+        assert_eq!(pick(&words, &mut FixedSecret(0)), "apple");
+    }
+
+    #[test]
+    fn a_seeded_game_plays_the_same_every_time() {
+        let words = words();
+        let secret = pick(&words, &mut XorShift::new(7)).to_string();
+        let input = format!("zzzzzzzzzzzzzzzzzzzzz\n{}\n", secret.to_uppercase());
+
+        let mut first = Vec::new();
+        let mut second = Vec::new();
+
+        let outcome = play(&mut XorShift::new(7), &words, 6, Lang::English, Cursor::new(&input), &mut first).unwrap();
+        play(&mut XorShift::new(7), &words, 6, Lang::English, Cursor::new(&input), &mut second).unwrap();
+
+        assert_eq!(outcome, Outcome::Won { attempts: 1 });
+        assert_eq!(first, second);
+        assert!(String::from_utf8(first).unwrap().contains("Invalid guess, the word has"));
+    }
+
+    #[test]
+    fn attempts_are_limited() {
+        let words = parse_words("smörgås");
+        let mut output = Vec::new();
+
+        let outcome = play(&mut FixedSecret(0), &words, 2, Lang::English, Cursor::new("smorgas\nsmårgös\nsmörgås\n"), &mut output)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert_eq!(outcome, Outcome::Lost { secret: "smörgås".to_string() });
+        assert!(output.contains("smorgas\n==.==.=\n"));
+        assert!(output.contains("smårgös\n==~==~=\n"));
+        assert!(output.ends_with("Out of attempts, the word was smörgås\n"));
+    }
+}
@@ -1,3 +1,197 @@
+// A greeter that started life as println!("Hello world!"), which is still
+// what it prints when run without any arguments.
+
+mod template;
+
+use std::env;
+use std::process;
+
+use cmdline::{Arg, Command, Error};
+use template::Template;
+
+const DEFAULT_TEMPLATE: &str = "{greeting} {name}!";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    names: Vec<String>,
+    greeting: String,
+    format: Format,
+    template: String,
+}
+
+fn command() -> Command {
+    Command::new("hello_world")
+        .about("Greets whoever it is told to")
+        .arg(
+            Arg::option("name")
+                .value_name("NAME")
+                .repeatable()
+                .help("who to greet, may be given more than once")
+                .default("world"),
+        )
+        .arg(Arg::option("greeting").value_name("TEXT").help("the greeting to use").default("Hello"))
+        .arg(
+            Arg::option("format")
+                .help("how to print the greetings")
+                .choices(&["text", "json"])
+                .default("text"),
+        )
+        .arg(
+            Arg::option("template")
+                .value_name("TEXT")
+                .help("how each greeting is written, use {{ and }} for literal braces")
+                .default(DEFAULT_TEMPLATE),
+        )
+}
+
+fn parse_args<I, S>(args: I) -> Result<Args, Error>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let matches = command().parse(args)?;
+
+    let format = match matches.value::<String>("format")?.as_deref() {
+        Some("json") => Format::Json,
+        _ => Format::Text,
+    };
+
+    Ok(Args {
+        names: matches.values("name")?,
+        greeting: matches.value("greeting")?.unwrap_or_default(),
+        format,
+        template: matches.value("template")?.unwrap_or_default(),
+    })
+}
+
+// Quotes s as a JSON string.
+fn json_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+fn greetings(args: &Args) -> Result<Vec<String>, template::TemplateError> {
+    let template = Template::parse(&args.template)?;
+
+    args.names
+        .iter()
+        .map(|name| template.render(&[("greeting", &args.greeting), ("name", name)]))
+        .collect()
+}
+
+fn render(args: &Args) -> Result<String, template::TemplateError> {
+    let messages = greetings(args)?;
+
+    Ok(match args.format {
+        Format::Text => messages.join("\n"),
+        Format::Json => {
+            let entries: Vec<String> = args.names
+                .iter()
+                .zip(&messages)
+                .map(|(name, message)| {
+                    format!(
+                        "{{\"name\": {}, \"greeting\": {}, \"message\": {}}}",
+                        json_string(name),
+                        json_string(&args.greeting),
+                        json_string(message)
+                    )
+                })
+                .collect();
+
+            format!("[{}]", entries.join(", "))
+        }
+    })
+}
+
 fn main() {
-    println!("Hello world!");
+    let args = parse_args(env::args().skip(1)).unwrap_or_else(|e| e.exit());
+
+    match render(&args) {
+        Ok(output) => println!("{}", output),
+        Err(e) => {
+            eprintln!("invalid template {:?}: {}", args.template, e);
+            process::exit(2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<Args, String> {
+        parse_args(args.iter().copied()).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn no_arguments_still_says_hello_world() {
+        assert_eq!(render(&args(&[]).unwrap()).unwrap(), "Hello world!");
+    }
+
+    #[test]
+    fn names_can_be_repeated() {
+        let parsed = args(&["--name", "Alice", "--greeting", "Hi", "--name", "Bob"]).unwrap();
+
+        assert_eq!(parsed.names, vec!["Alice", "Bob"]);
+        assert_eq!(render(&parsed).unwrap(), "Hi Alice!\nHi Bob!");
+    }
+
+    #[test]
+    fn templates_change_the_layout() {
+        let parsed = args(&["--template", "{{{greeting}}}, {name}.", "--name", "Ferris"]).unwrap();
+
+        assert_eq!(render(&parsed).unwrap(), "{Hello}, Ferris.");
+    }
+
+    #[test]
+    fn unknown_placeholders_are_errors() {
+        let parsed = args(&["--template", "{greting} {name}"]).unwrap();
+
+        assert_eq!(
+            render(&parsed).unwrap_err().to_string(),
+            "column 1: unknown placeholder {greting}, expected one of {greeting}, {name}"
+        );
+    }
+
+    #[test]
+    fn json_output_is_escaped() {
+        let parsed = args(&["--format", "json", "--name", "\"Bob\"", "--greeting", "Hi\tthere"]).unwrap();
+
+        assert_eq!(
+            render(&parsed).unwrap(),
+            r#"[{"name": "\"Bob\"", "greeting": "Hi\tthere", "message": "Hi\tthere \"Bob\"!"}]"#
+        );
+        assert_eq!(json_string("a\\b\u{1}"), r#""a\\b\u0001""#);
+    }
+
+    #[test]
+    fn bad_arguments_are_explained() {
+        assert_eq!(args(&["--name"]).unwrap_err(), "--name needs a value, NAME");
+        assert_eq!(
+            args(&["--format", "xml"]).unwrap_err(),
+            "invalid value \"xml\" for --format, expected one of text, json"
+        );
+        assert_eq!(args(&["--greting", "Hi"]).unwrap_err(), "unknown option --greting, did you mean --greeting?");
+    }
 }
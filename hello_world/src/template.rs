@@ -0,0 +1,199 @@
+use std::error::Error;
+use std::fmt;
+
+// A tiny template language. {name} is replaced by the value of the variable
+// name, and {{ and }} stand for literal braces.
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Text(String),
+    Variable { name: String, column: usize },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+// Columns count chars from 1, so they match what the user typed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateError {
+    Unclosed { column: usize },
+    UnmatchedClose { column: usize },
+    EmptyPlaceholder { column: usize },
+    UnknownPlaceholder { name: String, column: usize, known: Vec<String> },
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TemplateError::Unclosed { column } => {
+                write!(f, "column {}: {{ is never closed, use {{{{ for a literal brace", column)
+            }
+            TemplateError::UnmatchedClose { column } => {
+                write!(f, "column {}: }} has no matching {{, use }}}} for a literal brace", column)
+            }
+            TemplateError::EmptyPlaceholder { column } => {
+                write!(f, "column {}: empty placeholder {{}}", column)
+            }
+            TemplateError::UnknownPlaceholder { ref name, column, ref known } => {
+                write!(
+                    f,
+                    "column {}: unknown placeholder {{{}}}, expected one of {}",
+                    column,
+                    name,
+                    known.iter().map(|k| format!("{{{}}}", k)).collect::<Vec<_>>().join(", ")
+                )
+            }
+        }
+    }
+}
+
+impl Error for TemplateError {}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Template, TemplateError> {
+        let mut segments = Vec::new();
+        let mut text = String::new();
+        let mut chars = source.chars().enumerate().peekable();
+
+        while let Some((i, c)) = chars.next() {
+            let column = i + 1;
+
+            match c {
+                '{' if chars.peek().map(|&(_, c)| c) == Some('{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek().map(|&(_, c)| c) == Some('}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+
+                    loop {
+                        match chars.next() {
+                            Some((_, '}')) => break,
+                            Some((_, '{')) | None => return Err(TemplateError::Unclosed { column }),
+                            Some((_, c)) => name.push(c),
+                        }
+                    }
+
+                    let name = name.trim().to_string();
+
+                    if name.is_empty() {
+                        return Err(TemplateError::EmptyPlaceholder { column });
+                    }
+
+                    if !text.is_empty() {
+                        segments.push(Segment::Text(std::mem::take(&mut text)));
+                    }
+
+                    segments.push(Segment::Variable { name, column });
+                }
+                '}' => return Err(TemplateError::UnmatchedClose { column }),
+                c => text.push(c),
+            }
+        }
+
+        if !text.is_empty() {
+            segments.push(Segment::Text(text));
+        }
+
+        Ok(Template { segments })
+    }
+
+    // Checks that every placeholder is one of the known variables, so a typo
+    // is reported before anything is rendered.
+    pub fn check(&self, known: &[&str]) -> Result<(), TemplateError> {
+        for segment in &self.segments {
+            if let Segment::Variable { ref name, column } = *segment {
+                if !known.contains(&name.as_str()) {
+                    return Err(TemplateError::UnknownPlaceholder {
+                        name: name.clone(),
+                        column,
+                        known: known.iter().map(|k| k.to_string()).collect(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn render(&self, variables: &[(&str, &str)]) -> Result<String, TemplateError> {
+        let known: Vec<&str> = variables.iter().map(|&(name, _)| name).collect();
+
+        self.check(&known)?;
+
+        let mut output = String::new();
+
+        for segment in &self.segments {
+            match *segment {
+                Segment::Text(ref text) => output.push_str(text),
+                Segment::Variable { ref name, .. } => {
+                    let &(_, value) = variables.iter().find(|&&(n, _)| n == name).unwrap();
+                    output.push_str(value);
+                }
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(template: &str) -> Result<String, TemplateError> {
+        Template::parse(template)?.render(&[("greeting", "Hello"), ("name", "Ferris")])
+    }
+
+    #[test]
+    fn variables_are_substituted() {
+        assert_eq!(render("{greeting}, {name}!"), Ok("Hello, Ferris!".to_string()));
+        assert_eq!(render("{name}{name}"), Ok("FerrisFerris".to_string()));
+        assert_eq!(render("{ name }"), Ok("Ferris".to_string()));
+        assert_eq!(render("no variables"), Ok("no variables".to_string()));
+        assert_eq!(render(""), Ok(String::new()));
+    }
+
+    #[test]
+    fn doubled_braces_are_literal() {
+        assert_eq!(render("{{name}} is {name}"), Ok("{name} is Ferris".to_string()));
+        assert_eq!(render("}}{{"), Ok("}{".to_string()));
+    }
+
+    #[test]
+    fn values_are_not_treated_as_templates() {
+        let template = Template::parse("{name}").unwrap();
+
+        assert_eq!(template.render(&[("name", "{greeting}")]), Ok("{greeting}".to_string()));
+    }
+
+    #[test]
+    fn unknown_placeholders_are_reported() {
+        assert_eq!(
+            render("{greeting}, {nmae}!"),
+            Err(TemplateError::UnknownPlaceholder {
+                name: "nmae".to_string(),
+                column: 13,
+                known: vec!["greeting".to_string(), "name".to_string()],
+            })
+        );
+        assert_eq!(
+            render("{nmae}").unwrap_err().to_string(),
+            "column 1: unknown placeholder {nmae}, expected one of {greeting}, {name}"
+        );
+    }
+
+    #[test]
+    fn malformed_templates_are_reported() {
+        assert_eq!(render("Hi {name"), Err(TemplateError::Unclosed { column: 4 }));
+        assert_eq!(render("{a{b}"), Err(TemplateError::Unclosed { column: 1 }));
+        assert_eq!(render("Hi }"), Err(TemplateError::UnmatchedClose { column: 4 }));
+        assert_eq!(render("é{}"), Err(TemplateError::EmptyPlaceholder { column: 2 }));
+    }
+}
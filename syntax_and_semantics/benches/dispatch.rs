@@ -0,0 +1,192 @@
+// Measures the claims in the trait_objects chapter, that static dispatch is
+// fast since calls can be inlined while trait objects need slower virtual
+// calls, and that static dispatch costs code size instead.
+//
+//  cargo bench -p syntax_and_semantics --bench dispatch
+//
+// This uses a small timing harness built on std rather than the unstable
+// built in one, so it runs on stable Rust. When run by cargo test, which
+// doesn't pass --bench, everything is run once as a quick check that it works.
+
+use std::env;
+use std::fs;
+use std::hint::black_box;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use syntax_and_semantics::dispatch::{self, AnyFoo, Foo};
+
+const ITEMS: usize = 100_000;
+
+struct Config {
+    samples: usize,
+    items: usize,
+    code_size: bool,
+}
+
+// Runs f once to warm up, then times it samples times and returns the median,
+// which is less affected by the odd slow run than the mean.
+fn time<F: FnMut() -> u64>(config: &Config, mut f: F) -> Duration {
+    black_box(f());
+
+    let mut times: Vec<Duration> = (0..config.samples)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+
+    times.sort();
+    times[times.len() / 2]
+}
+
+fn report(config: &Config, name: &str, median: Duration, baseline: Duration) {
+    let per_item = median.as_secs_f64() * 1e9 / config.items as f64;
+    let relative = median.as_secs_f64() / baseline.as_secs_f64();
+
+    println!("  {:28} {:8.3} ns/item  {:5.2}x", name, per_item, relative);
+}
+
+fn homogeneous(config: &Config) {
+    let values: Vec<u32> = (0..config.items as u32).collect();
+    let boxed: Vec<Box<dyn Foo>> = values.iter().map(|&x| Box::new(x) as Box<dyn Foo>).collect();
+    let enums: Vec<AnyFoo> = values.iter().map(|&x| AnyFoo::U32(x)).collect();
+
+    println!("Every item a u32");
+
+    let generic = time(config, || dispatch::sum_static(black_box(&values)));
+    report(config, "generic, Vec<u32>", generic, generic);
+    report(config, "dyn, Vec<Box<dyn Foo>>", time(config, || dispatch::sum_dyn(black_box(&boxed))), generic);
+    report(config, "enum, Vec<AnyFoo>", time(config, || dispatch::sum_enum(black_box(&enums))), generic);
+    println!();
+}
+
+fn heterogeneous(config: &Config) {
+    let (split, boxed, enums) = dispatch::mixed(config.items);
+
+    println!("A mix of u8, u32 and String");
+
+    let generic = time(config, || black_box(&split).sum());
+    report(config, "generic, a Vec per type", generic, generic);
+    report(config, "dyn, Vec<Box<dyn Foo>>", time(config, || dispatch::sum_dyn(black_box(&boxed))), generic);
+    report(config, "enum, Vec<AnyFoo>", time(config, || dispatch::sum_enum(black_box(&enums))), generic);
+    println!();
+}
+
+// A program with count types implementing Foo, each of which is passed to
+// work, either as a generic or as a trait object.
+fn program(count: usize, generic: bool) -> String {
+    let mut source = String::from("trait Foo { fn method(&self) -> u64; }\n");
+
+    for i in 0..count {
+        source += &format!(
+            "struct T{i}(u64);\nimpl Foo for T{i} {{ fn method(&self) -> u64 {{ self.0.rotate_left({r}) ^ {i} }} }}\n",
+            i = i,
+            r = i % 63 + 1
+        );
+    }
+
+    // Enough work that each copy of work is the size of a real function,
+    // rather than a couple of instructions.
+    let body = "let mut total = 0u64;
+    for (i, item) in items.iter().enumerate() {
+        let x = item.method();
+        total = match x % 8 {
+            0 => total.wrapping_mul(31) ^ x,
+            1 => total.wrapping_add(x >> (i % 7)),
+            2 => total.rotate_right(x as u32 % 64).wrapping_sub(i as u64),
+            3 => total ^ x.wrapping_mul(0x9e37_79b9_7f4a_7c15),
+            4 => total.wrapping_add(x.count_ones() as u64 * 17),
+            5 => (total / (x | 1)).wrapping_add(x),
+            6 => total.wrapping_mul(x | 1).rotate_left(7),
+            _ => total.wrapping_sub(x.leading_zeros() as u64) ^ (i as u64),
+        };
+    }
+    total";
+
+    if generic {
+        source += &format!("#[inline(never)]\nfn work<T: Foo>(items: &[T]) -> u64 {{\n    {}\n}}\n", body);
+    } else {
+        source += &format!("#[inline(never)]\nfn work(items: &[&dyn Foo]) -> u64 {{\n    {}\n}}\n", body);
+    }
+
+    // The values come from the command line so that nothing can be worked out
+    // at compile time.
+    source += "fn main() {\n    let n = std::env::args().count() as u64;\n    let mut total = 0;\n";
+
+    for i in 0..count {
+        if generic {
+            source += &format!("    total ^= work(&[T{i}(n), T{i}(n + 1)]);\n", i = i);
+        } else {
+            source += &format!("    total ^= work(&[&T{i}(n) as &dyn Foo, &T{i}(n + 1)]);\n", i = i);
+        }
+    }
+
+    source += "    std::process::exit(total as i32);\n}\n";
+    source
+}
+
+// Compiles a program with optimisations and returns the size of its object
+// file, which unlike the linked binary doesn't include the standard library.
+fn object_size(dir: &Path, name: &str, source: &str) -> Result<u64, String> {
+    let source_path = dir.join(format!("{}.rs", name));
+    let object_path = dir.join(format!("{}.o", name));
+
+    fs::write(&source_path, source).map_err(|e| e.to_string())?;
+
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let output = Command::new(rustc)
+        .args(["--edition", "2021", "-C", "opt-level=3", "--emit=obj", "-o"])
+        .arg(&object_path)
+        .arg(&source_path)
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+    }
+
+    fs::metadata(&object_path).map(|m| m.len()).map_err(|e| e.to_string())
+}
+
+fn code_size() -> Result<(), String> {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("dispatch");
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    println!("Object file size with work used by N types");
+    println!("  {:>5} {:>10} {:>10} {:>10}", "N", "generic", "dyn", "difference");
+
+    for count in [1, 4, 16, 64] {
+        let generic = object_size(&dir, &format!("generic_{}", count), &program(count, true))?;
+        let dynamic = object_size(&dir, &format!("dyn_{}", count), &program(count, false))?;
+
+        println!("  {:>5} {:>10} {:>10} {:>+10}", count, generic, dynamic, generic as i64 - dynamic as i64);
+    }
+
+    println!();
+
+    Ok(())
+}
+
+fn main() {
+    let benchmarking = env::args().any(|arg| arg == "--bench");
+
+    let config = if benchmarking {
+        Config { samples: 25, items: ITEMS, code_size: true }
+    } else {
+        Config { samples: 1, items: 1000, code_size: false }
+    };
+
+    println!("{} items, median of {} runs\n", config.items, config.samples);
+
+    homogeneous(&config);
+    heterogeneous(&config);
+
+    if config.code_size {
+        if let Err(e) = code_size() {
+            eprintln!("Failed to compare code sizes: {}", e);
+        }
+    }
+}
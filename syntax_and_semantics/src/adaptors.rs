@@ -0,0 +1,578 @@
+use std::cmp;
+
+// Custom iterator adaptors, written directly against the Iterator trait.
+//
+// Bringing IteratorExt into scope adds the adaptors as methods on every
+// iterator, in the same way that map and filter are provided by Iterator
+// itself.
+pub trait IteratorExt: Iterator + Sized {
+    // Groups items into vectors of size n. The final chunk is shorter if the
+    // number of items isn't a multiple of n.
+    fn chunked(self, n: usize) -> Chunked<Self> {
+        assert!(n > 0, "chunk size must be greater than zero");
+        Chunked { iter: self, size: n }
+    }
+
+    // Alternates between items of this iterator and other, starting with this
+    // one. Once either runs out the rest of the other is yielded in order.
+    fn interleave<J>(self, other: J) -> Interleave<Self, J::IntoIter>
+        where J: IntoIterator<Item = Self::Item>
+    {
+        Interleave { a: self, b: other.into_iter(), a_next: true }
+    }
+
+    // Drops consecutive items that have the same key as the item before them,
+    // keeping the first item of each run.
+    fn dedup_by_key<K, F>(self, key: F) -> DedupByKey<Self, K, F>
+        where K: PartialEq, F: FnMut(&Self::Item) -> K
+    {
+        DedupByKey { iter: self, key, front: None, back: None }
+    }
+
+    // Folds each item into an accumulator, yielding every intermediate value
+    // for as long as it satisfies the predicate.
+    fn scan_while<St, F, P>(self, initial: St, fold: F, predicate: P)
+        -> ScanWhile<Self, St, F, P>
+        where St: Clone,
+              F: FnMut(St, Self::Item) -> St,
+              P: FnMut(&St) -> bool
+    {
+        ScanWhile { iter: self, state: Some(initial), fold, predicate }
+    }
+}
+
+impl<I: Iterator> IteratorExt for I {}
+
+pub struct Chunked<I> {
+    iter: I,
+    size: usize,
+}
+
+impl<I: Iterator> Iterator for Chunked<I> {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Vec<I::Item>> {
+        let chunk: Vec<I::Item> = self.iter.by_ref().take(self.size).collect();
+
+        if chunk.is_empty() { None } else { Some(chunk) }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let chunks = |n: usize| n.div_ceil(self.size);
+
+        (chunks(lower), upper.map(chunks))
+    }
+}
+
+// Taking chunks from the back requires knowing how many items remain, since
+// the last chunk is the short one.
+impl<I> DoubleEndedIterator for Chunked<I>
+    where I: DoubleEndedIterator + ExactSizeIterator
+{
+    fn next_back(&mut self) -> Option<Vec<I::Item>> {
+        let remaining = self.iter.len();
+
+        if remaining == 0 {
+            return None;
+        }
+
+        let last = match remaining % self.size {
+            0 => self.size,
+            n => n,
+        };
+
+        let mut chunk: Vec<I::Item> =
+            self.iter.by_ref().rev().take(last).collect();
+        chunk.reverse();
+
+        Some(chunk)
+    }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for Chunked<I> {}
+
+pub struct Interleave<I, J> {
+    a: I,
+    b: J,
+    a_next: bool,
+}
+
+impl<I, J> Iterator for Interleave<I, J>
+    where I: Iterator, J: Iterator<Item = I::Item>
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let a_next = self.a_next;
+        self.a_next = !a_next;
+
+        // If the iterator whose turn it is has run out, fall back to the other.
+        if a_next {
+            self.a.next().or_else(|| self.b.next())
+        } else {
+            self.b.next().or_else(|| self.a.next())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lower, a_upper) = self.a.size_hint();
+        let (b_lower, b_upper) = self.b.size_hint();
+
+        let upper = match (a_upper, b_upper) {
+            (Some(a), Some(b)) => a.checked_add(b),
+            _                  => None,
+        };
+
+        (a_lower.saturating_add(b_lower), upper)
+    }
+}
+
+// Working back from the end, the longer iterator supplies the last item. When
+// both have the same length, the last item comes from whichever iterator is
+// not due next from the front.
+impl<I, J> DoubleEndedIterator for Interleave<I, J>
+    where I: DoubleEndedIterator + ExactSizeIterator,
+          J: DoubleEndedIterator<Item = I::Item> + ExactSizeIterator
+{
+    fn next_back(&mut self) -> Option<I::Item> {
+        match self.a.len().cmp(&self.b.len()) {
+            cmp::Ordering::Greater => self.a.next_back(),
+            cmp::Ordering::Less    => self.b.next_back(),
+            cmp::Ordering::Equal   => {
+                if self.a_next { self.b.next_back() } else { self.a.next_back() }
+            }
+        }
+    }
+}
+
+impl<I, J> ExactSizeIterator for Interleave<I, J>
+    where I: ExactSizeIterator, J: ExactSizeIterator<Item = I::Item> {}
+
+pub struct DedupByKey<I: Iterator, K, F> {
+    iter: I,
+    key: F,
+    // Key of the last item yielded from the front.
+    front: Option<K>,
+    // Earliest item seen so far of the run currently being read from the
+    // back, along with its key.
+    back: Option<(K, I::Item)>,
+}
+
+impl<I, K, F> Iterator for DedupByKey<I, K, F>
+    where I: Iterator, K: PartialEq, F: FnMut(&I::Item) -> K
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        for item in self.iter.by_ref() {
+            let key = (self.key)(&item);
+
+            if self.front.as_ref() != Some(&key) {
+                self.front = Some(key);
+                return Some(item);
+            }
+        }
+
+        // Anything left is an item held back by next_back.
+        match self.back.take() {
+            Some((key, item)) => {
+                if self.front.as_ref() == Some(&key) {
+                    None
+                } else {
+                    self.front = Some(key);
+                    Some(item)
+                }
+            }
+            None => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let held = if self.back.is_some() { 1 } else { 0 };
+
+        // Every remaining item could be a duplicate of the previous one. Once
+        // something has been yielded from the front that includes the first,
+        // otherwise at least one will be yielded if any remain.
+        let lower = if self.front.is_some() { 0 } else { cmp::min(lower.saturating_add(held), 1) };
+
+        (lower, upper.and_then(|u| u.checked_add(held)))
+    }
+}
+
+// From the back we can't tell whether an item is the first of its run until
+// we've seen the item before it, so one item is held back in self.back.
+impl<I, K, F> DoubleEndedIterator for DedupByKey<I, K, F>
+    where I: DoubleEndedIterator, K: PartialEq, F: FnMut(&I::Item) -> K
+{
+    fn next_back(&mut self) -> Option<I::Item> {
+        while let Some(item) = self.iter.next_back() {
+            let key = (self.key)(&item);
+
+            match self.back.take() {
+                Some((held_key, held)) => {
+                    if held_key == key {
+                        // Same run, so the earlier item replaces the held one.
+                        self.back = Some((key, item));
+                    } else {
+                        // The held item started its run.
+                        self.back = Some((key, item));
+                        return Some(held);
+                    }
+                }
+                None => self.back = Some((key, item)),
+            }
+        }
+
+        // Everything has been read. The held item is only a duplicate if it
+        // continues the run last yielded from the front.
+        match self.back.take() {
+            Some((key, item)) => {
+                if self.front.as_ref() == Some(&key) { None } else { Some(item) }
+            }
+            None => None,
+        }
+    }
+}
+
+pub struct ScanWhile<I, St, F, P> {
+    iter: I,
+    // None once the predicate has failed, so the iterator stays finished.
+    state: Option<St>,
+    fold: F,
+    predicate: P,
+}
+
+impl<I, St, F, P> Iterator for ScanWhile<I, St, F, P>
+    where I: Iterator,
+          St: Clone,
+          F: FnMut(St, I::Item) -> St,
+          P: FnMut(&St) -> bool
+{
+    type Item = St;
+
+    fn next(&mut self) -> Option<St> {
+        let state = self.state.take()?;
+        let item = self.iter.next()?;
+        let next = (self.fold)(state, item);
+
+        if (self.predicate)(&next) {
+            self.state = Some(next.clone());
+            Some(next)
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.state {
+            Some(_) => (0, self.iter.size_hint().1),
+            None    => (0, Some(0)),
+        }
+    }
+}
+
+// Each value depends on every value before it, so neither ScanWhile nor
+// Fibonacci can sensibly be iterated from the back.
+
+// Yields the Fibonacci sequence 0, 1, 1, 2, 3, 5... until the next value would
+// overflow a u64.
+pub fn fibonacci() -> Fibonacci {
+    Fibonacci { current: Some(0), next: Some(1) }
+}
+
+pub struct Fibonacci {
+    current: Option<u64>,
+    next: Option<u64>,
+}
+
+impl Iterator for Fibonacci {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let current = self.current?;
+
+        self.current = self.next;
+        self.next = self.next.and_then(|next| next.checked_add(current));
+
+        Some(current)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // F(93) is the largest Fibonacci number that fits in a u64, so there
+        // are 94 values in total.
+        match (self.current, self.next) {
+            (None, _)       => (0, Some(0)),
+            (Some(_), None) => (1, Some(1)),
+            (Some(_), _)    => (2, Some(94)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunked_matches_for_loop() {
+        let items: Vec<i32> = (1..11).collect();
+
+        let mut expected = Vec::new();
+        let mut chunk = Vec::new();
+        for item in &items {
+            chunk.push(*item);
+            if chunk.len() == 3 {
+                expected.push(chunk);
+                chunk = Vec::new();
+            }
+        }
+        if !chunk.is_empty() {
+            expected.push(chunk);
+        }
+
+        let chunks: Vec<Vec<i32>> = items.iter().cloned().chunked(3).collect();
+
+        assert_eq!(chunks, expected);
+        assert_eq!(chunks, vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9], vec![10]]);
+    }
+
+    #[test]
+    fn chunked_size_hint_rounds_up() {
+        assert_eq!((0..10).chunked(3).size_hint(), (4, Some(4)));
+        assert_eq!((0..9).chunked(3).size_hint(), (3, Some(3)));
+        assert_eq!((0..0).chunked(3).size_hint(), (0, Some(0)));
+        assert_eq!((0..10).chunked(3).len(), 4);
+    }
+
+    #[test]
+    fn chunked_from_back_starts_with_short_chunk() {
+        let mut forward: Vec<Vec<i32>> = (1..11).chunked(3).collect();
+        forward.reverse();
+
+        let backward: Vec<Vec<i32>> = (1..11).chunked(3).rev().collect();
+
+        assert_eq!(backward, forward);
+    }
+
+    #[test]
+    fn chunked_from_both_ends() {
+        let mut chunks = (1..11).chunked(4);
+
+        assert_eq!(chunks.next(), Some(vec![1, 2, 3, 4]));
+        assert_eq!(chunks.next_back(), Some(vec![9, 10]));
+        assert_eq!(chunks.next_back(), Some(vec![5, 6, 7, 8]));
+        assert_eq!(chunks.next(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size")]
+    fn chunked_rejects_zero() {
+        (0..10).chunked(0);
+    }
+
+    fn interleave_by_hand(a: &[i32], b: &[i32]) -> Vec<i32> {
+        let mut result = Vec::new();
+        for i in 0..cmp::max(a.len(), b.len()) {
+            if i < a.len() { result.push(a[i]); }
+            if i < b.len() { result.push(b[i]); }
+        }
+        result
+    }
+
+    #[test]
+    fn interleave_matches_for_loop() {
+        let cases: Vec<(Vec<i32>, Vec<i32>)> = vec![
+            (vec![1, 3, 5], vec![2, 4, 6]),
+            (vec![1, 3, 5, 7, 9], vec![2, 4]),
+            (vec![1], vec![2, 4, 6, 8]),
+            (vec![], vec![2, 4]),
+            (vec![], vec![]),
+        ];
+
+        for (a, b) in cases {
+            let expected = interleave_by_hand(&a, &b);
+            let actual: Vec<i32> =
+                a.iter().cloned().interleave(b.iter().cloned()).collect();
+
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn interleave_reversed_matches_forward() {
+        let cases: Vec<(Vec<i32>, Vec<i32>)> = vec![
+            (vec![1, 3, 5], vec![2, 4, 6]),
+            (vec![1, 3, 5, 7, 9], vec![2, 4]),
+            (vec![1], vec![2, 4, 6, 8]),
+        ];
+
+        for (a, b) in cases {
+            let mut expected = interleave_by_hand(&a, &b);
+            expected.reverse();
+
+            let actual: Vec<i32> =
+                a.iter().cloned().interleave(b.iter().cloned()).rev().collect();
+
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn interleave_from_both_ends() {
+        let mut it = vec![1, 3, 5].into_iter().interleave(vec![2, 4, 6]);
+
+        assert_eq!(it.len(), 6);
+        assert_eq!(it.next(), Some(1));
+        assert_eq!(it.next_back(), Some(6));
+        assert_eq!(it.next_back(), Some(5));
+        assert_eq!(it.next(), Some(2));
+        assert_eq!(it.size_hint(), (2, Some(2)));
+        assert_eq!(it.collect::<Vec<_>>(), vec![3, 4]);
+    }
+
+    fn dedup_by_hand(words: &[&'static str]) -> Vec<&'static str> {
+        let mut result = Vec::new();
+        let mut last: Option<char> = None;
+        for word in words {
+            let initial = word.chars().next();
+            if initial != last {
+                result.push(*word);
+                last = initial;
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn dedup_by_key_matches_for_loop() {
+        let words = ["apple", "avocado", "banana", "blueberry", "apricot", "cherry"];
+
+        let expected = dedup_by_hand(&words);
+        let actual: Vec<&str> = words
+            .iter()
+            .cloned()
+            .dedup_by_key(|w| w.chars().next())
+            .collect();
+
+        assert_eq!(actual, expected);
+        assert_eq!(actual, vec!["apple", "banana", "apricot", "cherry"]);
+    }
+
+    #[test]
+    fn dedup_by_key_reversed_matches_forward() {
+        let words = ["apple", "avocado", "banana", "blueberry", "apricot", "cherry", "cranberry"];
+
+        let mut expected = dedup_by_hand(&words);
+        expected.reverse();
+
+        let actual: Vec<&str> = words
+            .iter()
+            .cloned()
+            .dedup_by_key(|w| w.chars().next())
+            .rev()
+            .collect();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn dedup_by_key_from_both_ends() {
+        let mut it = vec![1, 1, 2, 2, 2, 3, 3].into_iter().dedup_by_key(|&n| n);
+
+        assert_eq!(it.next(), Some(1));
+        assert_eq!(it.next_back(), Some(3));
+        assert_eq!(it.next(), Some(2));
+        assert_eq!(it.next_back(), None);
+        assert_eq!(it.next(), None);
+
+        // A run split between the two ends is only yielded once.
+        let mut it = vec![1, 1, 1].into_iter().dedup_by_key(|&n| n);
+
+        assert_eq!(it.next(), Some(1));
+        assert_eq!(it.next_back(), None);
+    }
+
+    #[test]
+    fn dedup_by_key_size_hint() {
+        assert_eq!((0..10).dedup_by_key(|&n| n / 3).size_hint(), (1, Some(10)));
+        assert_eq!((0..0).dedup_by_key(|&n| n).size_hint(), (0, Some(0)));
+
+        // The remaining 1s are duplicates of the one already yielded.
+        let mut it = [1, 1, 1].into_iter().dedup_by_key(|&n| n);
+        it.next();
+        assert_eq!(it.size_hint(), (0, Some(2)));
+        assert_eq!(it.next(), None);
+
+        // Chain saturates its hint at usize::MAX, which the held item mustn't
+        // overflow.
+        let mut it = (0..usize::MAX).chain(0..usize::MAX).dedup_by_key(|&n| n);
+        it.next_back();
+        assert_eq!(it.size_hint(), (1, None));
+    }
+
+    #[test]
+    fn scan_while_matches_for_loop() {
+        let items = [10, 20, 30, 40, 50];
+
+        let mut expected = Vec::new();
+        let mut total = 0;
+        for item in &items {
+            total += *item;
+            if total >= 100 { break; }
+            expected.push(total);
+        }
+
+        let actual: Vec<i32> = items
+            .iter()
+            .scan_while(0, |acc, &n| acc + n, |&acc| acc < 100)
+            .collect();
+
+        assert_eq!(actual, expected);
+        assert_eq!(actual, vec![10, 30, 60]);
+    }
+
+    #[test]
+    fn scan_while_stays_finished() {
+        let mut it = vec![1, 100, 1].into_iter()
+            .scan_while(0, |acc, n| acc + n, |&acc| acc < 50);
+
+        assert_eq!(it.next(), Some(1));
+        assert_eq!(it.next(), None);
+        assert_eq!(it.size_hint(), (0, Some(0)));
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn fibonacci_matches_for_loop() {
+        let mut expected = vec![0u64, 1];
+        for i in 2..20 {
+            let next = expected[i - 1] + expected[i - 2];
+            expected.push(next);
+        }
+
+        let actual: Vec<u64> = fibonacci().take(20).collect();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn fibonacci_stops_before_overflow() {
+        let all: Vec<u64> = fibonacci().collect();
+
+        assert_eq!(all.len(), 94);
+        assert_eq!(*all.last().unwrap(), 12200160415121876738);
+        assert_eq!(fibonacci().size_hint(), (2, Some(94)));
+    }
+
+    #[test]
+    fn adaptors_compose() {
+        let chunks: Vec<Vec<u64>> = fibonacci()
+            .take(10)
+            .dedup_by_key(|&n| n)
+            .chunked(3)
+            .collect();
+
+        assert_eq!(chunks, vec![vec![0, 1, 2], vec![3, 5, 8], vec![13, 21, 34]]);
+    }
+}
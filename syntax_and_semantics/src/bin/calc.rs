@@ -0,0 +1,42 @@
+use std::env;
+use std::io;
+use std::process;
+
+use cmdline::{Command, Error};
+use syntax_and_semantics::expr::repl;
+
+fn command() -> Command {
+    Command::new("calc").about("Evaluates arithmetic expressions, one per line, with variables")
+}
+
+fn parse_args<I, S>(args: I) -> Result<(), Error>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    command().parse(args)?;
+
+    Ok(())
+}
+
+fn main() {
+    parse_args(env::args().skip(1)).unwrap_or_else(|e| e.exit());
+
+    let stdin = io::stdin();
+
+    if let Err(e) = repl::run(stdin.lock(), &mut io::stdout()) {
+        eprintln!("Failed to run the calculator: {}", e);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn takes_no_arguments() {
+        assert!(parse_args(Vec::<String>::new()).is_ok());
+        assert!(parse_args(["1 + 1"]).is_err());
+    }
+}
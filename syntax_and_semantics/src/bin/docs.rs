@@ -0,0 +1,130 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command as Process};
+
+use cmdline::{Arg, Command, Error};
+use syntax_and_semantics::docs::{self, Page};
+use syntax_and_semantics::examples::{source, CHAPTERS};
+
+#[derive(Debug, PartialEq)]
+enum Action {
+    // Write the docs to the given directory, capturing each chapter's output
+    // unless told not to.
+    Write { out: PathBuf, output: bool },
+    // Run a single chapter. Chapters print straight to stdout, so this is how
+    // their output is captured, by running this binary again.
+    Run(&'static str),
+}
+
+fn command() -> Command {
+    Command::new("docs")
+        .about("Generates Markdown and HTML pages from the chapters' sources")
+        .arg(
+            Arg::option("out")
+                .value_name("DIR")
+                .help("where to write the markdown and html directories")
+                .default("target/docs"),
+        )
+        .arg(Arg::flag("no-output").help("leave out what each chapter prints"))
+        .arg(Arg::option("run").value_name("CHAPTER").help("just run CHAPTER, used to capture its output"))
+}
+
+fn parse_args<I, S>(args: I) -> Result<Action, Error>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let matches = command().parse(args)?;
+
+    if let Some(name) = matches.value::<String>("run")? {
+        return CHAPTERS
+            .iter()
+            .find(|&&(chapter, _)| chapter == name)
+            .map(|&(chapter, _)| Action::Run(chapter))
+            .ok_or_else(|| matches.error(&format!("unknown chapter {:?}", name)));
+    }
+
+    Ok(Action::Write {
+        out: matches.value("out")?.unwrap_or_default(),
+        output: !matches.flag("no-output"),
+    })
+}
+
+// Runs a chapter in a separate process and returns what it printed.
+fn capture(chapter: &str) -> io::Result<String> {
+    let result = Process::new(env::current_exe()?).args(["--run", chapter]).output()?;
+
+    if !result.status.success() {
+        let message = format!("{} failed: {}", chapter, String::from_utf8_lossy(&result.stderr).trim());
+        return Err(io::Error::other(message));
+    }
+
+    Ok(String::from_utf8_lossy(&result.stdout).into_owned())
+}
+
+fn write(out: &Path, output: bool) -> io::Result<()> {
+    let markdown = out.join("markdown");
+    let html = out.join("html");
+
+    fs::create_dir_all(&markdown)?;
+    fs::create_dir_all(&html)?;
+
+    let names: Vec<&str> = CHAPTERS.iter().map(|&(name, _)| name).collect();
+
+    for (i, &chapter) in names.iter().enumerate() {
+        let page = Page {
+            chapter,
+            blocks: docs::blocks(source(chapter).unwrap_or_default()),
+            output: if output { Some(capture(chapter)?) } else { None },
+            previous: i.checked_sub(1).map(|i| names[i]),
+            next: names.get(i + 1).copied(),
+        };
+
+        fs::write(markdown.join(format!("{}.md", chapter)), page.markdown())?;
+        fs::write(html.join(format!("{}.html", chapter)), page.html())?;
+    }
+
+    fs::write(markdown.join("index.md"), docs::markdown_index(&names))?;
+    fs::write(html.join("index.html"), docs::html_index(&names))?;
+
+    println!("Wrote {} chapters to {} and {}", names.len(), markdown.display(), html.display());
+
+    Ok(())
+}
+
+fn main() {
+    match parse_args(env::args().skip(1)).unwrap_or_else(|e| e.exit()) {
+        Action::Run(name) => {
+            if let Some(&(_, chapter)) = CHAPTERS.iter().find(|&&(chapter, _)| chapter == name) {
+                chapter();
+            }
+        }
+        Action::Write { out, output } => {
+            if let Err(e) = write(&out, output) {
+                eprintln!("Failed to write the docs to {}: {}", out.display(), e);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_to_target_by_default() {
+        assert_eq!(
+            parse_args(Vec::<String>::new()).unwrap(),
+            Action::Write { out: PathBuf::from("target/docs"), output: true }
+        );
+        assert_eq!(
+            parse_args(["--no-output", "--out", "site"]).unwrap(),
+            Action::Write { out: PathBuf::from("site"), output: false }
+        );
+        assert_eq!(parse_args(["--run", "loops"]).unwrap(), Action::Run("loops"));
+        assert_eq!(parse_args(["--run", "loopz"]).unwrap_err().to_string(), "unknown chapter \"loopz\"");
+    }
+}
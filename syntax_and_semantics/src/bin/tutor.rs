@@ -0,0 +1,88 @@
+use std::env;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use cmdline::{Arg, Command, Error};
+use syntax_and_semantics::examples::CHAPTERS;
+use syntax_and_semantics::tutor::{self, Progress};
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    progress: PathBuf,
+    restart: bool,
+    status: bool,
+}
+
+fn command() -> Command {
+    Command::new("tutor")
+        .about("Works through the Syntax and Semantics chapters with a quiz after each")
+        .arg(
+            Arg::option("progress")
+                .value_name("FILE")
+                .help("where to keep track of the finished chapters")
+                .default("tutor_progress.txt"),
+        )
+        .arg(Arg::flag("restart").help("forget any progress and start from the first chapter"))
+        .arg(Arg::flag("status").help("show the finished chapters rather than carrying on"))
+}
+
+fn parse_args<I, S>(args: I) -> Result<Args, Error>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let matches = command().parse(args)?;
+
+    Ok(Args {
+        progress: matches.value("progress")?.unwrap_or_default(),
+        restart: matches.flag("restart"),
+        status: matches.flag("status"),
+    })
+}
+
+fn fail(path: &Path, e: io::Error) -> ! {
+    eprintln!("Failed to use {}: {}", path.display(), e);
+    process::exit(1);
+}
+
+fn main() {
+    let args = parse_args(env::args().skip(1)).unwrap_or_else(|e| e.exit());
+
+    let mut progress = if args.restart {
+        Progress::default()
+    } else {
+        Progress::load(&args.progress).unwrap_or_else(|e| fail(&args.progress, e))
+    };
+
+    if args.status {
+        for &(name, _) in CHAPTERS {
+            match progress.get(name) {
+                Some(record) => println!("{:32} {}", name, record),
+                None => println!("{:32} -", name),
+            }
+        }
+        return;
+    }
+
+    let stdin = io::stdin();
+    let save = |p: &Progress| p.save(&args.progress);
+
+    if let Err(e) = tutor::play(CHAPTERS, &mut progress, save, stdin.lock(), &mut io::stdout()) {
+        eprintln!("Failed to run the tutor: {}", e);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_is_kept_in_the_current_directory() {
+        let args = parse_args(Vec::<String>::new()).unwrap();
+
+        assert_eq!(args, Args { progress: PathBuf::from("tutor_progress.txt"), restart: false, status: false });
+        assert!(parse_args(["--restart", "--progress", "mine.txt"]).unwrap().restart);
+    }
+}
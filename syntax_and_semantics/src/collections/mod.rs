@@ -0,0 +1,14 @@
+// Generic collections, written out by hand to show what the generics and
+// ownership chapters look like in a larger piece of code. The standard
+// library's Vec, VecDeque and BTreeSet do all of this better.
+//
+// Each collection owns what's put in it. Values move in when added and move
+// back out when removed, iter borrows them, and into_iter gives them up one at
+// a time, consuming the collection.
+mod queue;
+mod stack;
+mod tree;
+
+pub use self::queue::Queue;
+pub use self::stack::Stack;
+pub use self::tree::BinaryTree;
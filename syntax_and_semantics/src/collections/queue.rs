@@ -0,0 +1,269 @@
+use std::fmt;
+use std::iter::FromIterator;
+
+// A first in, first out queue kept in a ring buffer.
+//
+// The values sit in a Vec used as a circle, starting at head and wrapping
+// round past the end. Popping moves head on rather than shifting everything
+// down, so both ends are O(1). Empty slots hold None, which lets a value be
+// moved out of its slot with take.
+#[derive(Clone)]
+pub struct Queue<T> {
+    slots: Vec<Option<T>>,
+    head: usize,
+    len: usize,
+}
+
+impl<T> Queue<T> {
+    pub fn new() -> Queue<T> {
+        Queue::with_capacity(0)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Queue<T> {
+        let mut slots = Vec::with_capacity(capacity);
+        slots.resize_with(capacity, || None);
+
+        Queue { slots, head: 0, len: 0 }
+    }
+
+    // Adds a value to the back, making room first if the buffer is full.
+    pub fn push(&mut self, value: T) {
+        if self.len == self.slots.len() {
+            self.grow();
+        }
+
+        let back = self.slot(self.len);
+        self.slots[back] = Some(value);
+        self.len += 1;
+    }
+
+    // Removes the value at the front.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let value = self.slots[self.head].take();
+        self.head = self.slot(1);
+        self.len -= 1;
+
+        value
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.iter().next()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // How many values fit before the buffer has to grow.
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|v| v == value)
+    }
+
+    // Iterates from the front to the back, the order pop would give.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { queue: self, index: 0 }
+    }
+
+    // The slot holding the value index places from the front.
+    fn slot(&self, index: usize) -> usize {
+        (self.head + index) % self.slots.len()
+    }
+
+    // Doubles the buffer, moving the values to its start so they no longer
+    // wrap round.
+    fn grow(&mut self) {
+        let capacity = (self.slots.len() * 2).max(4);
+        let mut slots = Vec::with_capacity(capacity);
+
+        for index in 0..self.len {
+            let slot = self.slot(index);
+            slots.push(self.slots[slot].take());
+        }
+        slots.resize_with(capacity, || None);
+
+        self.slots = slots;
+        self.head = 0;
+    }
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Queue<T> {
+        Queue::new()
+    }
+}
+
+pub struct Iter<'a, T> {
+    queue: &'a Queue<T>,
+    index: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.index == self.queue.len {
+            return None;
+        }
+
+        let slot = self.queue.slot(self.index);
+        self.index += 1;
+
+        self.queue.slots[slot].as_ref()
+    }
+}
+
+pub struct IntoIter<T> {
+    queue: Queue<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.queue.pop()
+    }
+}
+
+impl<T> IntoIterator for Queue<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { queue: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Queue<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T> FromIterator<T> for Queue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Queue<T> {
+        let mut queue = Queue::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+impl<T> Extend<T> for Queue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+// Lists the values from the front to the back, however they're laid out in
+// the buffer.
+impl<T: fmt::Debug> fmt::Debug for Queue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::rc::Rc;
+
+    #[test]
+    fn first_in_first_out() {
+        let mut queue = Queue::new();
+
+        queue.push(1);
+        queue.push(2);
+        queue.push(3);
+
+        assert_eq!(queue.peek(), Some(&1));
+        assert_eq!(queue.pop(), Some(1));
+        assert_eq!(queue.pop(), Some(2));
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.pop(), Some(3));
+        assert_eq!(queue.pop(), None);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn wraps_round_the_buffer() {
+        let mut queue = Queue::with_capacity(4);
+
+        queue.extend([1, 2, 3]);
+        queue.pop();
+        queue.pop();
+
+        // 4 and 5 go in the last slot and the first, so the values wrap.
+        queue.extend([4, 5]);
+
+        assert_eq!(queue.capacity(), 4);
+        assert_eq!(queue.slots, vec![Some(5), None, Some(3), Some(4)]);
+        assert_eq!(format!("{:?}", queue), "[3, 4, 5]");
+    }
+
+    #[test]
+    fn grows_when_full_and_keeps_the_order() {
+        let mut queue = Queue::with_capacity(4);
+
+        queue.extend([1, 2, 3, 4]);
+        queue.pop();
+        queue.extend([5, 6]);
+
+        assert_eq!(queue.capacity(), 8);
+        assert_eq!(queue.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4, 5, 6]);
+        assert!(queue.contains(&6));
+        assert!(!queue.contains(&1));
+        assert_eq!(queue.into_iter().collect::<Vec<_>>(), vec![2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn popping_empties_the_slot() {
+        let mut queue = Queue::with_capacity(2);
+
+        queue.push(String::from("front"));
+        queue.push(String::from("behind"));
+
+        // take leaves None behind, so the queue no longer holds the value it
+        // handed out.
+        assert_eq!(queue.pop().as_deref(), Some("front"));
+        assert_eq!(queue.slots, vec![None, Some(String::from("behind"))]);
+    }
+
+    #[test]
+    fn every_value_is_dropped_once() {
+        let value = Rc::new(());
+
+        {
+            let mut queue = Queue::with_capacity(2);
+
+            for _ in 0..5 {
+                queue.push(Rc::clone(&value));
+            }
+            drop(queue.pop());
+
+            assert_eq!(Rc::strong_count(&value), 5);
+        }
+
+        // The values still in the queue are dropped with it.
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+}
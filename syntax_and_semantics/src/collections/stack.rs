@@ -0,0 +1,205 @@
+use std::fmt;
+use std::iter::{FromIterator, Rev};
+use std::slice;
+use std::vec;
+
+// A last in, first out stack, kept in a Vec with the top at the end.
+#[derive(Clone, PartialEq)]
+pub struct Stack<T> {
+    items: Vec<T>,
+}
+
+impl<T> Stack<T> {
+    pub fn new() -> Stack<T> {
+        Stack { items: Vec::new() }
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.items.push(value);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.items.pop()
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.items.last()
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.items.last_mut()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.items.contains(value)
+    }
+
+    // Iterates from the top down, the order pop would give.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { inner: self.items.iter().rev() }
+    }
+}
+
+// Written out rather than derived, since deriving Default would only give it to
+// stacks of values that have a default themselves.
+impl<T> Default for Stack<T> {
+    fn default() -> Stack<T> {
+        Stack::new()
+    }
+}
+
+pub struct Iter<'a, T> {
+    inner: Rev<slice::Iter<'a, T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next()
+    }
+}
+
+pub struct IntoIter<T> {
+    inner: Rev<vec::IntoIter<T>>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.inner.next()
+    }
+}
+
+impl<T> IntoIterator for Stack<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { inner: self.items.into_iter().rev() }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Stack<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+// Pushes the values in order, so the last one ends up on top.
+impl<T> FromIterator<T> for Stack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Stack<T> {
+        let mut stack = Stack::new();
+        stack.extend(iter);
+        stack
+    }
+}
+
+impl<T> Extend<T> for Stack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+// Lists the values from the top down.
+impl<T: fmt::Debug> fmt::Debug for Stack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_in_first_out() {
+        let mut stack = Stack::new();
+
+        stack.push(1);
+        stack.push(2);
+        stack.push(3);
+
+        assert_eq!(stack.peek(), Some(&3));
+        assert_eq!(stack.pop(), Some(3));
+        assert_eq!(stack.pop(), Some(2));
+        assert_eq!(stack.len(), 1);
+        assert_eq!(stack.pop(), Some(1));
+        assert_eq!(stack.pop(), None);
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn collect_and_iterate() {
+        let stack: Stack<i32> = (1..=3).collect();
+
+        assert!(stack.contains(&2));
+        assert!(!stack.contains(&4));
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+        assert_eq!(format!("{:?}", stack), "[3, 2, 1]");
+        assert_eq!(stack.into_iter().collect::<Vec<_>>(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn popping_hands_the_top_value_over() {
+        let mut stack: Stack<String> = ["bottom", "top"].iter().map(|s| s.to_string()).collect();
+
+        // The popped String is the caller's now, and changing it leaves what's
+        // still on the stack alone.
+        let mut top = stack.pop().unwrap();
+        top.push('!');
+
+        assert_eq!(top, "top!");
+        assert_eq!(stack.items, vec!["bottom"]);
+    }
+
+    #[test]
+    fn borrowing_leaves_the_stack_usable() {
+        let mut stack: Stack<String> = ["a", "b"].iter().map(|s| s.to_string()).collect();
+
+        for s in &stack {
+            assert_eq!(s.len(), 1);
+        }
+
+        stack.peek_mut().unwrap().push('!');
+
+        assert_eq!(stack.peek().map(String::as_str), Some("b!"));
+    }
+
+    #[test]
+    fn default_needs_nothing_of_the_values() {
+        struct NoDefault;
+
+        let stack: Stack<NoDefault> = Stack::default();
+
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn copy_values_stay_usable() {
+        let mut stack = Stack::new();
+        let n = 12;
+
+        stack.push(n);
+
+        assert_eq!(n, 12);
+        assert_eq!(stack.peek(), Some(&12));
+    }
+}
@@ -0,0 +1,528 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::FromIterator;
+
+// A binary search tree, holding each value at most once.
+//
+// Every value in a node's left subtree is less than the node's and every
+// value in its right subtree is greater, so a search only follows one path
+// down. The tree isn't rebalanced, so values added in order make it as deep
+// as a list. Nothing walks the tree by recursing, as a tree that deep would
+// overflow the stack.
+pub struct BinaryTree<T> {
+    root: Link<T>,
+    len: usize,
+}
+
+// A subtree, which is empty or owns its top node.
+type Link<T> = Option<Box<Node<T>>>;
+
+struct Node<T> {
+    value: T,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+impl<T: Ord> BinaryTree<T> {
+    pub fn new() -> BinaryTree<T> {
+        BinaryTree { root: None, len: 0 }
+    }
+
+    // Adds a value, returning false and dropping it if it's already there.
+    pub fn insert(&mut self, value: T) -> bool {
+        let mut link = &mut self.root;
+
+        while let Some(node) = link {
+            link = match value.cmp(&node.value) {
+                Ordering::Less => &mut node.left,
+                Ordering::Greater => &mut node.right,
+                Ordering::Equal => return false,
+            };
+        }
+
+        *link = Some(Box::new(Node { value, left: None, right: None }));
+        self.len += 1;
+
+        true
+    }
+
+    // Removes a value, handing back the one the tree owned.
+    pub fn remove(&mut self, value: &T) -> Option<T> {
+        let removed = remove(&mut self.root, value)?;
+        self.len -= 1;
+
+        Some(removed)
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let mut link = &self.root;
+
+        while let Some(node) = link {
+            link = match value.cmp(&node.value) {
+                Ordering::Less => &node.left,
+                Ordering::Greater => &node.right,
+                Ordering::Equal => return true,
+            };
+        }
+
+        false
+    }
+
+    // The smallest value, found by always going left.
+    pub fn min(&self) -> Option<&T> {
+        self.iter().next()
+    }
+
+    // The largest value, found by always going right.
+    pub fn max(&self) -> Option<&T> {
+        let mut node = self.root.as_deref()?;
+
+        while let Some(right) = node.right.as_deref() {
+            node = right;
+        }
+
+        Some(&node.value)
+    }
+}
+
+impl<T> BinaryTree<T> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Visits the left subtree, then the node, then the right subtree, which
+    // gives the values in sorted order.
+    pub fn iter(&self) -> InOrder<'_, T> {
+        let mut iter = InOrder { stack: Vec::new() };
+        iter.push_left(self.root.as_deref());
+        iter
+    }
+
+    // Visits each node before its subtrees, the order that would rebuild the
+    // same tree if the values were inserted again.
+    pub fn pre_order(&self) -> PreOrder<'_, T> {
+        PreOrder { stack: self.root.as_deref().into_iter().collect() }
+    }
+
+    // Visits each node after its subtrees, so children always come before
+    // their parents.
+    pub fn post_order(&self) -> PostOrder<'_, T> {
+        PostOrder { stack: self.root.as_deref().map(|node| (node, false)).into_iter().collect() }
+    }
+}
+
+// Removes value from the subtree, replacing its node with whatever can take
+// its place.
+fn remove<T: Ord>(mut link: &mut Link<T>, value: &T) -> Option<T> {
+    // The comparison is made through a shared borrow, so that link is free to
+    // be moved on or taken from afterwards.
+    loop {
+        match value.cmp(&link.as_ref()?.value) {
+            Ordering::Less => link = &mut link.as_mut()?.left,
+            Ordering::Greater => link = &mut link.as_mut()?.right,
+            Ordering::Equal => break,
+        }
+    }
+
+    let mut node = link.take()?;
+
+    *link = match (node.left.take(), node.right.take()) {
+        (None, None) => None,
+        (Some(child), None) | (None, Some(child)) => Some(child),
+        // With two children, the smallest value on the right is greater than
+        // everything on the left and less than the rest of the right, so its
+        // node can stand in for the removed one.
+        (Some(left), Some(right)) => {
+            let (mut successor, rest) = take_min(right);
+            successor.left = Some(left);
+            successor.right = rest;
+            Some(successor)
+        }
+    };
+
+    Some(node.value)
+}
+
+// Splits the smallest node from a subtree, returning it along with what's left
+// of the subtree. The smallest node is the one reached by always going left,
+// and its right subtree takes its place.
+fn take_min<T>(node: Box<Node<T>>) -> (Box<Node<T>>, Link<T>) {
+    let mut rest = Some(node);
+    let mut link = &mut rest;
+
+    while link.as_ref().is_some_and(|node| node.left.is_some()) {
+        link = &mut link.as_mut().unwrap().left;
+    }
+
+    let mut min = link.take().unwrap();
+    *link = min.right.take();
+
+    (min, rest)
+}
+
+// The iterators keep a stack of the nodes still to come back to, as there are
+// no links back up the tree.
+pub struct InOrder<'a, T> {
+    stack: Vec<&'a Node<T>>,
+}
+
+impl<'a, T> InOrder<'a, T> {
+    fn push_left(&mut self, mut node: Option<&'a Node<T>>) {
+        while let Some(n) = node {
+            self.stack.push(n);
+            node = n.left.as_deref();
+        }
+    }
+}
+
+impl<'a, T> Iterator for InOrder<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.stack.pop()?;
+        self.push_left(node.right.as_deref());
+
+        Some(&node.value)
+    }
+}
+
+pub struct PreOrder<'a, T> {
+    stack: Vec<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for PreOrder<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.stack.pop()?;
+
+        // Right goes on first so that left comes off first.
+        self.stack.extend(node.right.as_deref());
+        self.stack.extend(node.left.as_deref());
+
+        Some(&node.value)
+    }
+}
+
+// Each node is on the stack twice, first to put its children on above it and
+// then, once they're done, to be visited.
+pub struct PostOrder<'a, T> {
+    stack: Vec<(&'a Node<T>, bool)>,
+}
+
+impl<'a, T> Iterator for PostOrder<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        loop {
+            match self.stack.pop()? {
+                (node, true) => return Some(&node.value),
+                (node, false) => {
+                    self.stack.push((node, true));
+                    self.stack.extend(node.right.as_deref().map(|n| (n, false)));
+                    self.stack.extend(node.left.as_deref().map(|n| (n, false)));
+                }
+            }
+        }
+    }
+}
+
+// Gives up the values in sorted order. The stack owns the nodes still to come
+// back to, each with its left subtree already taken.
+pub struct IntoIter<T> {
+    stack: Vec<Box<Node<T>>>,
+}
+
+impl<T> IntoIter<T> {
+    fn push_left(&mut self, mut link: Link<T>) {
+        while let Some(mut node) = link {
+            link = node.left.take();
+            self.stack.push(node);
+        }
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let mut node = self.stack.pop()?;
+        self.push_left(node.right.take());
+
+        Some(node.value)
+    }
+}
+
+impl<T> IntoIterator for BinaryTree<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(mut self) -> IntoIter<T> {
+        let mut iter = IntoIter { stack: Vec::new() };
+        iter.push_left(self.root.take());
+        iter
+    }
+}
+
+// An unfinished iterator still owns the right subtrees of the nodes on its
+// stack, so the remaining values are taken out one at a time rather than
+// dropped all at once.
+impl<T> Drop for IntoIter<T> {
+    fn drop(&mut self) {
+        while self.next().is_some() {}
+    }
+}
+
+impl<'a, T> IntoIterator for &'a BinaryTree<T> {
+    type Item = &'a T;
+    type IntoIter = InOrder<'a, T>;
+
+    fn into_iter(self) -> InOrder<'a, T> {
+        self.iter()
+    }
+}
+
+// The default drop would drop the root, which drops its subtrees and so on,
+// recursing once per level. Instead the nodes are unlinked onto a stack and
+// dropped one at a time.
+impl<T> Drop for BinaryTree<T> {
+    fn drop(&mut self) {
+        let mut stack: Vec<Box<Node<T>>> = self.root.take().into_iter().collect();
+
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.left.take());
+            stack.extend(node.right.take());
+        }
+    }
+}
+
+// Copies the nodes in post-order, so both subtrees of a node have been built,
+// and are on top of the built stack, by the time the node itself is copied.
+impl<T: Clone> Clone for BinaryTree<T> {
+    fn clone(&self) -> BinaryTree<T> {
+        // Each entry is a subtree still to copy, which may be empty, along
+        // with whether its children have been dealt with.
+        let mut stack = vec![(self.root.as_deref(), false)];
+        let mut built: Vec<Link<T>> = Vec::new();
+
+        while let Some(entry) = stack.pop() {
+            match entry {
+                (None, _) => built.push(None),
+                (Some(node), true) => {
+                    let right = built.pop().expect("right subtree was built");
+                    let left = built.pop().expect("left subtree was built");
+
+                    built.push(Some(Box::new(Node { value: node.value.clone(), left, right })));
+                }
+                (Some(node), false) => {
+                    stack.push((Some(node), true));
+                    stack.push((node.right.as_deref(), false));
+                    stack.push((node.left.as_deref(), false));
+                }
+            }
+        }
+
+        BinaryTree { root: built.pop().flatten(), len: self.len }
+    }
+}
+
+impl<T: Ord> Default for BinaryTree<T> {
+    fn default() -> BinaryTree<T> {
+        BinaryTree::new()
+    }
+}
+
+// Inserts the values in order, dropping any repeats.
+impl<T: Ord> FromIterator<T> for BinaryTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> BinaryTree<T> {
+        let mut tree = BinaryTree::new();
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<T: Ord> Extend<T> for BinaryTree<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+// Lists the values in sorted order, like a set.
+impl<T: fmt::Debug> fmt::Debug for BinaryTree<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //        5
+    //      /   \
+    //     3     8
+    //    / \     \
+    //   1   4     9
+    fn example() -> BinaryTree<i32> {
+        [5, 3, 8, 1, 4, 9].into_iter().collect()
+    }
+
+    fn values<'a, I: Iterator<Item = &'a i32>>(iter: I) -> Vec<i32> {
+        iter.copied().collect()
+    }
+
+    #[test]
+    fn traversal_orders() {
+        let tree = example();
+
+        assert_eq!(values(tree.iter()), vec![1, 3, 4, 5, 8, 9]);
+        assert_eq!(values(tree.pre_order()), vec![5, 3, 1, 4, 8, 9]);
+        assert_eq!(values(tree.post_order()), vec![1, 4, 3, 9, 8, 5]);
+        assert_eq!(tree.into_iter().collect::<Vec<_>>(), vec![1, 3, 4, 5, 8, 9]);
+    }
+
+    #[test]
+    fn empty_tree() {
+        let tree: BinaryTree<i32> = BinaryTree::new();
+
+        assert!(tree.is_empty());
+        assert_eq!(tree.min(), None);
+        assert_eq!(tree.max(), None);
+        assert_eq!(tree.pre_order().next(), None);
+        assert_eq!(tree.post_order().next(), None);
+        assert_eq!(format!("{:?}", tree), "{}");
+    }
+
+    #[test]
+    fn insert_and_search() {
+        let mut tree = example();
+
+        assert!(tree.contains(&4));
+        assert!(!tree.contains(&7));
+        assert!(!tree.insert(4));
+        assert!(tree.insert(7));
+        assert!(tree.contains(&7));
+        assert_eq!(tree.len(), 7);
+        assert_eq!(tree.min(), Some(&1));
+        assert_eq!(tree.max(), Some(&9));
+        assert_eq!(format!("{:?}", tree), "{1, 3, 4, 5, 7, 8, 9}");
+    }
+
+    #[test]
+    fn removing_keeps_the_order() {
+        // A leaf, a node with one child and nodes with two children, the root
+        // last.
+        for (value, pre_order) in [
+            (1, vec![5, 3, 4, 8, 9]),
+            (8, vec![5, 3, 1, 4, 9]),
+            (3, vec![5, 4, 1, 8, 9]),
+            (5, vec![8, 3, 1, 4, 9]),
+        ] {
+            let mut tree = example();
+
+            assert_eq!(tree.remove(&value), Some(value));
+            assert_eq!(values(tree.pre_order()), pre_order, "removing {}", value);
+            assert_eq!(tree.len(), 5);
+            assert!(!tree.contains(&value));
+        }
+
+        let mut tree = example();
+
+        assert_eq!(tree.remove(&7), None);
+        assert_eq!(tree.len(), 6);
+    }
+
+    // Ordered by key alone, so two entries can compare equal while still
+    // being told apart by their names.
+    #[derive(Debug)]
+    struct Entry {
+        key: i32,
+        name: &'static str,
+    }
+
+    impl PartialEq for Entry {
+        fn eq(&self, other: &Entry) -> bool {
+            self.key == other.key
+        }
+    }
+
+    impl Eq for Entry {}
+
+    impl PartialOrd for Entry {
+        fn partial_cmp(&self, other: &Entry) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Entry {
+        fn cmp(&self, other: &Entry) -> Ordering {
+            self.key.cmp(&other.key)
+        }
+    }
+
+    #[test]
+    fn removing_gives_back_the_value_inserted() {
+        let entry = |key, name| Entry { key, name };
+        let mut tree: BinaryTree<Entry> = [entry(5, "five"), entry(3, "three"), entry(8, "eight")].into_iter().collect();
+
+        // The first of two equal values is the one kept.
+        assert!(!tree.insert(entry(5, "another five")));
+
+        // 5 has two children, so 8's node takes its place. What comes back is
+        // still the value that was inserted as 5, not the probe or 8.
+        let removed = tree.remove(&entry(5, "probe")).unwrap();
+
+        assert_eq!(removed.name, "five");
+        assert_eq!(tree.iter().map(|e| e.name).collect::<Vec<_>>(), vec!["three", "eight"]);
+    }
+
+    #[test]
+    fn a_rejected_value_is_dropped() {
+        use std::rc::Rc;
+
+        let value = Rc::new(1);
+        let mut tree = BinaryTree::new();
+
+        tree.insert(Rc::clone(&value));
+        tree.insert(Rc::clone(&value));
+
+        assert_eq!(Rc::strong_count(&value), 2);
+    }
+
+    // Sorted input makes a tree as deep as a list, which recursing through
+    // would overflow the stack. Miri is far slower, so the tree is kept short
+    // there.
+    #[test]
+    fn a_deep_tree_is_walked_without_recursing() {
+        const LONG: usize = if cfg!(miri) { 100 } else { 20_000 };
+
+        let mut tree: BinaryTree<usize> = (0..LONG).collect();
+        let copy = tree.clone();
+
+        assert_eq!(tree.remove(&(LONG - 1)), Some(LONG - 1));
+        assert_eq!(tree.max(), Some(&(LONG - 2)));
+        assert_eq!(copy.len(), LONG);
+        assert_eq!(copy.max(), Some(&(LONG - 1)));
+
+        let mut iter = copy.into_iter();
+        assert_eq!(iter.next(), Some(0));
+        drop(iter);
+    }
+
+    #[test]
+    fn clones_are_independent() {
+        let tree = example();
+        let mut copy = tree.clone();
+
+        copy.remove(&5);
+
+        assert!(tree.contains(&5));
+        assert!(!copy.contains(&5));
+    }
+}
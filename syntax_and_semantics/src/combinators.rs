@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// Functions that build and return closures.
+//
+// A closure's type has no name, so a function returning one says so with
+// impl Fn, or boxes it as a Box<dyn Fn> if different closures may be returned.
+// Either way the closure must be a move closure, as anything it borrowed from
+// the function would be gone by the time it's called.
+
+// Returns a closure that adds n to its argument.
+pub fn make_adder(n: i32) -> impl Fn(i32) -> i32 {
+    move |x| x + n
+}
+
+// The boxed form, which can return a different closure depending on op.
+pub fn make_operation(op: char, n: i32) -> Option<Box<dyn Fn(i32) -> i32>> {
+    match op {
+        '+' => Some(Box::new(move |x| x + n)),
+        '-' => Some(Box::new(move |x| x - n)),
+        '*' => Some(Box::new(move |x| x * n)),
+        _ => None,
+    }
+}
+
+// Returns a closure that counts up from 1 each time it's called. It changes
+// the count it owns, so it's FnMut rather than Fn.
+pub fn make_counter() -> impl FnMut() -> u32 {
+    let mut count = 0;
+
+    move || {
+        count += 1;
+        count
+    }
+}
+
+// Returns a closure that applies f and then g.
+pub fn compose<A, B, C, F, G>(f: F, g: G) -> impl Fn(A) -> C
+where
+    F: Fn(A) -> B,
+    G: Fn(B) -> C,
+{
+    move |x| g(f(x))
+}
+
+// Returns a closure that remembers the result for each argument it's been
+// called with, so f is only called once per argument. The cache lives inside
+// the closure, which is why the result is FnMut.
+pub fn memoize<A, R, F>(f: F) -> impl FnMut(A) -> R
+where
+    A: Eq + Hash + Clone,
+    R: Clone,
+    F: Fn(A) -> R,
+{
+    let mut cache = HashMap::new();
+
+    move |arg: A| cache.entry(arg.clone()).or_insert_with(|| f(arg)).clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cell::Cell;
+
+    // These take the closure by the trait they need, to show which closures
+    // each will accept.
+    fn call_twice<F: Fn() -> u32>(f: F) -> u32 {
+        f() + f()
+    }
+
+    fn call_twice_mut<F: FnMut() -> u32>(mut f: F) -> u32 {
+        f() + f()
+    }
+
+    fn call_once<F: FnOnce() -> String>(f: F) -> String {
+        f()
+    }
+
+    #[test]
+    fn adders_capture_their_argument() {
+        let add_two = make_adder(2);
+        let add_ten = make_adder(10);
+
+        assert_eq!(add_two(1), 3);
+        assert_eq!(add_ten(1), 11);
+        // An Fn can be called any number of times.
+        assert_eq!(add_two(add_two(0)), 4);
+    }
+
+    #[test]
+    fn boxed_closures_can_differ() {
+        let ops: Vec<_> = ['+', '-', '*'].iter().map(|&op| make_operation(op, 3).unwrap()).collect();
+
+        assert_eq!(ops.iter().map(|op| op(6)).collect::<Vec<_>>(), vec![9, 3, 18]);
+        assert!(make_operation('/', 3).is_none());
+    }
+
+    #[test]
+    fn compose_applies_in_order() {
+        let double_then_describe = compose(|x: i32| x * 2, |x: i32| format!("got {}", x));
+        let add_then_double = compose(make_adder(1), |x| x * 2);
+
+        assert_eq!(double_then_describe(5), "got 10");
+        assert_eq!(add_then_double(5), 12);
+    }
+
+    #[test]
+    fn memoize_calls_once_per_argument() {
+        let calls = Cell::new(0);
+        let mut square = memoize(|x: u64| {
+            calls.set(calls.get() + 1);
+            x * x
+        });
+
+        assert_eq!(square(4), 16);
+        assert_eq!(square(4), 16);
+        assert_eq!(square(5), 25);
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn fn_closures_only_read_what_they_capture() {
+        let n = 21;
+
+        // A closure that only reads can be passed where FnMut or FnOnce is
+        // expected too, as every Fn is also FnMut and FnOnce.
+        assert_eq!(call_twice(|| n), 42);
+        assert_eq!(call_twice_mut(|| n), 42);
+        assert_eq!(call_once(|| n.to_string()), "21");
+    }
+
+    #[test]
+    fn fn_mut_closures_change_what_they_capture() {
+        let mut counter = make_counter();
+
+        assert_eq!(counter(), 1);
+        assert_eq!(counter(), 2);
+
+        // A borrowed closure can be passed on without giving it away.
+        assert_eq!(call_twice_mut(&mut counter), 3 + 4);
+        assert_eq!(counter(), 5);
+
+        let mut total = 0;
+        call_twice_mut(|| {
+            total += 1;
+            total
+        });
+        assert_eq!(total, 2);
+    }
+
+    #[test]
+    fn fn_once_closures_can_move_out_what_they_capture() {
+        let name = String::from("Ferris");
+
+        // Returning name moves it out of the closure, so it can only be
+        // called once. See tests/compile_fail for calling it twice.
+        let take_name = move || name;
+
+        assert_eq!(call_once(take_name), "Ferris");
+    }
+}
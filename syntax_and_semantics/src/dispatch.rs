@@ -0,0 +1,143 @@
+// Three ways of calling a method on a value whose type varies, as measured by
+// benches/dispatch.rs.
+//
+// The trait_objects chapter compares static dispatch through generics with
+// dynamic dispatch through trait objects. A third option is an enum with a
+// variant per type, which dispatches with a match. Its set of types is closed,
+// but the match can be inlined just like a generic call.
+//
+// Foo::method is deliberately cheap, so that the cost of the call itself isn't
+// hidden behind the work done inside it.
+
+pub trait Foo {
+    fn method(&self) -> u64;
+}
+
+impl Foo for u8 {
+    fn method(&self) -> u64 {
+        *self as u64
+    }
+}
+
+impl Foo for u32 {
+    fn method(&self) -> u64 {
+        (*self as u64) * 3
+    }
+}
+
+impl Foo for String {
+    fn method(&self) -> u64 {
+        self.len() as u64
+    }
+}
+
+pub enum AnyFoo {
+    U8(u8),
+    U32(u32),
+    Text(String),
+}
+
+impl Foo for AnyFoo {
+    fn method(&self) -> u64 {
+        match self {
+            AnyFoo::U8(x) => x.method(),
+            AnyFoo::U32(x) => x.method(),
+            AnyFoo::Text(x) => x.method(),
+        }
+    }
+}
+
+// Static dispatch. A copy is compiled for every T it's used with, and the call
+// to method can be inlined into each.
+pub fn do_something<T: Foo>(x: &T) -> u64 {
+    x.method()
+}
+
+// Dynamic dispatch. There's a single copy, which looks method up in the
+// vtable on every call.
+pub fn do_something_dyn(x: &dyn Foo) -> u64 {
+    x.method()
+}
+
+pub fn sum_static<T: Foo>(items: &[T]) -> u64 {
+    items.iter().map(do_something).fold(0, u64::wrapping_add)
+}
+
+pub fn sum_dyn(items: &[Box<dyn Foo>]) -> u64 {
+    items.iter().map(|x| do_something_dyn(x.as_ref())).fold(0, u64::wrapping_add)
+}
+
+pub fn sum_enum(items: &[AnyFoo]) -> u64 {
+    items.iter().map(do_something).fold(0, u64::wrapping_add)
+}
+
+// A mix of types can't go in one Vec<T>, so to keep static dispatch the
+// values are kept in a Vec per type instead.
+#[derive(Default)]
+pub struct Mixed {
+    pub u8s: Vec<u8>,
+    pub u32s: Vec<u32>,
+    pub texts: Vec<String>,
+}
+
+impl Mixed {
+    pub fn sum(&self) -> u64 {
+        sum_static(&self.u8s).wrapping_add(sum_static(&self.u32s)).wrapping_add(sum_static(&self.texts))
+    }
+}
+
+// The same values, n of them, in each of the three forms, cycling between the
+// types.
+pub fn mixed(n: usize) -> (Mixed, Vec<Box<dyn Foo>>, Vec<AnyFoo>) {
+    let mut split = Mixed::default();
+    let mut boxed: Vec<Box<dyn Foo>> = Vec::with_capacity(n);
+    let mut enums = Vec::with_capacity(n);
+
+    for i in 0..n {
+        match i % 3 {
+            0 => {
+                split.u8s.push(i as u8);
+                boxed.push(Box::new(i as u8));
+                enums.push(AnyFoo::U8(i as u8));
+            }
+            1 => {
+                split.u32s.push(i as u32);
+                boxed.push(Box::new(i as u32));
+                enums.push(AnyFoo::U32(i as u32));
+            }
+            _ => {
+                let text = "x".repeat(i % 7);
+                split.texts.push(text.clone());
+                boxed.push(Box::new(text.clone()));
+                enums.push(AnyFoo::Text(text));
+            }
+        }
+    }
+
+    (split, boxed, enums)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_kind_of_dispatch_agrees() {
+        let values: Vec<u32> = (0..100).collect();
+        let boxed: Vec<Box<dyn Foo>> = values.iter().map(|&x| Box::new(x) as Box<dyn Foo>).collect();
+        let enums: Vec<AnyFoo> = values.iter().map(|&x| AnyFoo::U32(x)).collect();
+
+        assert_eq!(sum_static(&values), 3 * 4950);
+        assert_eq!(sum_dyn(&boxed), 3 * 4950);
+        assert_eq!(sum_enum(&enums), 3 * 4950);
+    }
+
+    #[test]
+    fn mixed_collections_hold_the_same_values() {
+        let (split, boxed, enums) = mixed(1000);
+
+        assert_eq!(split.u8s.len() + split.u32s.len() + split.texts.len(), 1000);
+        assert_eq!(split.sum(), sum_dyn(&boxed));
+        assert_eq!(split.sum(), sum_enum(&enums));
+    }
+}
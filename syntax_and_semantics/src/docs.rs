@@ -0,0 +1,536 @@
+// Turns the chapters into documentation pages.
+//
+// A chapter's source alternates between runs of comments, which explain
+// what's going on, and runs of code. Each comment run becomes a paragraph and
+// each code run a block of code, so a page reads in the same order as the
+// source.
+use std::mem;
+
+#[derive(Debug, PartialEq)]
+pub enum Block {
+    Prose(String),
+    Code(String),
+}
+
+// Splits a chapter's source into blocks.
+//
+// Inner attributes, and the comments just above them explaining why they're
+// there, are about the examples' lints rather than Rust so they're left out.
+// So are the tests at the end of the file.
+pub fn blocks(source: &str) -> Vec<Block> {
+    let mut lines: Vec<&str> = Vec::new();
+
+    for line in source.lines().take_while(|line| !line.starts_with("#[cfg(test)]")) {
+        if line.starts_with("#![") {
+            while lines.last().is_some_and(|last| last.starts_with("//")) {
+                lines.pop();
+            }
+        } else {
+            lines.push(line);
+        }
+    }
+
+    let mut classified = Vec::new();
+
+    for run in lines.chunk_by(|a, b| comment_text(a).is_some() == comment_text(b).is_some()) {
+        if comment_text(run[0]).is_some() {
+            classified.extend(classify_comments(run.iter().filter_map(|line| comment_text(line)).collect()));
+        } else {
+            classified.extend(run.iter().map(|&line| if line.trim().is_empty() { Line::Blank } else { Line::Code(line) }));
+        }
+    }
+
+    let mut blocks = Vec::new();
+    let mut prose: Vec<&str> = Vec::new();
+    let mut code: Vec<&str> = Vec::new();
+    // Whether the code being gathered was quoted in a comment, so it isn't
+    // run together with the source's own code.
+    let mut quoting = false;
+
+    for line in classified {
+        match line {
+            Line::Prose(text) => {
+                end_code(&mut code, &mut blocks);
+                prose.push(text);
+            }
+            Line::Quoted(text) | Line::Code(text) => {
+                end_prose(&mut prose, &mut blocks);
+
+                let quoted = matches!(line, Line::Quoted(_));
+                if quoted != quoting {
+                    end_code(&mut code, &mut blocks);
+                    quoting = quoted;
+                }
+                code.push(text);
+            }
+            Line::Break => {
+                end_prose(&mut prose, &mut blocks);
+
+                if quoting && !code.is_empty() {
+                    code.push("");
+                }
+            }
+            Line::Blank => {
+                end_prose(&mut prose, &mut blocks);
+
+                // Blank lines are only kept between lines of code.
+                if quoting {
+                    end_code(&mut code, &mut blocks);
+                } else if !code.is_empty() {
+                    code.push("");
+                }
+            }
+        }
+    }
+
+    end_prose(&mut prose, &mut blocks);
+    end_code(&mut code, &mut blocks);
+
+    blocks
+}
+
+// A line of a chapter, once comments are told apart from code.
+enum Line<'a> {
+    Prose(&'a str),
+    // Code quoted in a comment, without the comment's own indent.
+    Quoted(&'a str),
+    Code(&'a str),
+    // An empty comment, which ends a paragraph.
+    Break,
+    Blank,
+}
+
+// The text of a comment line, without the // or the space after it. Any
+// further indent is kept, as it says whether the line is code.
+fn comment_text(line: &str) -> Option<&str> {
+    let text = line.trim_start().strip_prefix("//")?;
+
+    Some(text.strip_prefix(' ').unwrap_or(text).trim_end())
+}
+
+// Whether a line starts an item of a list, such as "o item", "- item" or
+// "1 item".
+fn is_list_item(text: &str) -> bool {
+    text.split_once(' ').is_some_and(|(marker, _)| {
+        marker == "o" || marker == "-" || marker.trim_end_matches('.').parse::<u32>().is_ok()
+    })
+}
+
+// Sorts a run of comment lines into prose and quoted code.
+//
+// Code is quoted in comments by indenting it beyond the rest of the run.
+// Lists are indented too, as in
+//
+//  o a list item
+//    and its continuation
+//
+// so their lines stay prose, as do lines lined up with a word of the prose
+// line before them, as in
+//
+// E.g. &T to *const T
+//      &mut T to *mut T
+//
+// The braces around quoted code are usually at the run's own indent, so lines
+// that look like code next to quoted lines are taken as code as well.
+fn classify_comments(texts: Vec<&str>) -> Vec<Line<'_>> {
+    let indent = |text: &str| text.len() - text.trim_start().len();
+    let base = texts.iter().filter(|text| !text.is_empty()).map(|text| indent(text)).min().unwrap_or(0);
+
+    let looks_like_code = |text: &str| {
+        let text = text.trim();
+        text.ends_with(['{', '}', ';']) || text.starts_with('}')
+    };
+
+    // Whether text starts below a word, other than the first, of the line
+    // before it.
+    let lined_up = |before: &str, text: &str| {
+        let column = indent(text);
+        let bytes = before.as_bytes();
+
+        indent(before) == base
+            && !looks_like_code(before)
+            && column > base
+            && column < bytes.len()
+            && bytes[column - 1] == b' '
+            && bytes[column] != b' '
+    };
+
+    let mut quoted = vec![false; texts.len()];
+    let mut listed = vec![false; texts.len()];
+    let mut item_indent = None;
+
+    for (i, text) in texts.iter().enumerate() {
+        if text.is_empty() {
+            item_indent = None;
+        } else if is_list_item(text.trim_start()) {
+            item_indent = Some(indent(text));
+            listed[i] = true;
+        } else if item_indent.is_some_and(|item| indent(text) > item) {
+            listed[i] = true;
+        } else {
+            item_indent = None;
+            quoted[i] = indent(text) > base && !(i > 0 && lined_up(texts[i - 1], text));
+        }
+    }
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+
+        for i in 0..texts.len() {
+            let beside_code = (i > 0 && quoted[i - 1]) || quoted.get(i + 1) == Some(&true);
+
+            if !quoted[i] && !listed[i] && beside_code && looks_like_code(texts[i]) {
+                quoted[i] = true;
+                changed = true;
+            }
+        }
+    }
+
+    texts
+        .iter()
+        .zip(quoted)
+        .map(|(&text, quoted)| match text {
+            "" => Line::Break,
+            _ if quoted => Line::Quoted(&text[base..]),
+            _ => Line::Prose(text.trim()),
+        })
+        .collect()
+}
+
+fn end_prose(prose: &mut Vec<&str>, blocks: &mut Vec<Block>) {
+    if !prose.is_empty() {
+        blocks.push(Block::Prose(mem::take(prose).join("\n")));
+    }
+}
+
+// Code inside the chapter's function is indented, so the indent common to the
+// whole run is removed.
+fn end_code(code: &mut Vec<&str>, blocks: &mut Vec<Block>) {
+    while code.last() == Some(&"") {
+        code.pop();
+    }
+
+    if code.is_empty() {
+        return;
+    }
+
+    let indent = code
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    let lines: Vec<&str> = mem::take(code)
+        .into_iter()
+        .map(|line| line.get(indent..).unwrap_or("").trim_end())
+        .collect();
+
+    blocks.push(Block::Code(lines.join("\n")));
+}
+
+// A chapter's name as a heading, e.g. if_let_while_let becomes If let while
+// let.
+pub fn title(chapter: &str) -> String {
+    let words = chapter.replace('_', " ");
+    let mut chars = words.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => words,
+    }
+}
+
+// One chapter's page, along with its neighbours so the pages can link to each
+// other.
+#[derive(Debug)]
+pub struct Page<'a> {
+    pub chapter: &'a str,
+    pub blocks: Vec<Block>,
+    // What the chapter printed when it was run.
+    pub output: Option<String>,
+    pub previous: Option<&'a str>,
+    pub next: Option<&'a str>,
+}
+
+impl<'a> Page<'a> {
+    pub fn markdown(&self) -> String {
+        let mut page = format!("# {}\n\n", escape_markdown(&title(self.chapter)));
+
+        for block in &self.blocks {
+            match block {
+                Block::Prose(text) => page += &format!("{}\n\n", markdown_prose(text)),
+                Block::Code(code) => page += &format!("```rust\n{}\n```\n\n", code),
+            }
+        }
+
+        if let Some(output) = &self.output {
+            page += &format!("## Output\n\n```text\n{}\n```\n\n", output.trim_end());
+        }
+
+        page += &self.links(|chapter| format!("{}.md", chapter), |text, href| format!("[{}]({})", text, href));
+        page.push('\n');
+
+        page
+    }
+
+    pub fn html(&self) -> String {
+        let mut body = format!("<h1>{}</h1>\n", escape_html(&title(self.chapter)));
+
+        for block in &self.blocks {
+            match block {
+                Block::Prose(text) => body += &format!("<p>{}</p>\n", escape_html(text)),
+                Block::Code(code) => body += &format!("<pre><code>{}</code></pre>\n", escape_html(code)),
+            }
+        }
+
+        if let Some(output) = &self.output {
+            body += &format!(
+                "<h2>Output</h2>\n<pre class=\"output\">{}</pre>\n",
+                escape_html(output.trim_end())
+            );
+        }
+
+        let links = self.links(
+            |chapter| format!("{}.html", chapter),
+            |text, href| format!("<a href=\"{}\">{}</a>", href, escape_html(text)),
+        );
+        body += &format!("<nav>{}</nav>\n", links);
+
+        html_page(&title(self.chapter), &body)
+    }
+
+    // Links to the previous chapter, the index and the next chapter, joined
+    // with |.
+    fn links<H, L>(&self, href: H, link: L) -> String
+    where
+        H: Fn(&str) -> String,
+        L: Fn(&str, &str) -> String,
+    {
+        let mut links = Vec::new();
+
+        if let Some(previous) = self.previous {
+            links.push(link(&format!("Previous: {}", title(previous)), &href(previous)));
+        }
+
+        links.push(link("Index", &href("index")));
+
+        if let Some(next) = self.next {
+            links.push(link(&format!("Next: {}", title(next)), &href(next)));
+        }
+
+        links.join(" | ")
+    }
+}
+
+pub fn markdown_index(chapters: &[&str]) -> String {
+    let mut index = String::from("# Syntax and Semantics\n\n");
+
+    for (number, chapter) in chapters.iter().enumerate() {
+        index += &format!("{}. [{}]({}.md)\n", number + 1, escape_markdown(&title(chapter)), chapter);
+    }
+
+    index
+}
+
+pub fn html_index(chapters: &[&str]) -> String {
+    let mut body = String::from("<h1>Syntax and Semantics</h1>\n<ol>\n");
+
+    for chapter in chapters {
+        body += &format!("<li><a href=\"{}.html\">{}</a></li>\n", chapter, escape_html(&title(chapter)));
+    }
+
+    body += "</ol>\n";
+
+    html_page("Syntax and Semantics", &body)
+}
+
+// The styles are inlined so the pages work without anything else, even when
+// opened straight from disk.
+const STYLE: &str = "\
+body { max-width: 50em; margin: 2em auto; padding: 0 1em; font-family: sans-serif; line-height: 1.5; }
+p { white-space: pre-line; }
+pre { background: #f4f4f4; padding: 0.75em; overflow-x: auto; }
+pre.output { background: #222; color: #eee; }
+nav { margin-top: 2em; border-top: 1px solid #ccc; padding-top: 0.5em; }";
+
+fn html_page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{}</title>\n<style>\n{}\n</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title),
+        STYLE,
+        body
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+// Comments are laid out a line at a time, with lists and examples on lines of
+// their own, so each line ends in a hard break rather than being run into the
+// next. This keeps the layout the HTML gets from white-space: pre-line.
+fn markdown_prose(text: &str) -> String {
+    text.lines().map(escape_line).collect::<Vec<_>>().join("\\\n")
+}
+
+// Escapes a line of prose, including what would start a Markdown list when at
+// the start of the line, such as - item or 1. item.
+fn escape_line(line: &str) -> String {
+    let escaped = escape_markdown(line);
+    let digits = escaped.len() - escaped.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+
+    if escaped.starts_with(['-', '+']) {
+        format!("\\{}", escaped)
+    } else if digits > 0 && escaped[digits..].starts_with(['.', ')']) {
+        format!("{}\\{}", &escaped[..digits], &escaped[digits..])
+    } else {
+        escaped
+    }
+}
+
+// Comments are written as plain text, so anything Markdown would treat as
+// formatting is escaped. Without this Box<dyn Foo> would disappear as an HTML
+// tag and snake_case names could turn italic.
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::examples::{source, CHAPTERS};
+
+    const SOURCE: &str = "\
+// Explains the lint.
+#![allow(dead_code)]
+
+use std::fmt;
+
+pub fn example() {
+    // A comment
+    // over two lines.
+    let x = 1; // stays with the code
+
+    let y = 2;
+
+    // Another.
+    //
+    // And another, quoting
+    //
+    // if x < y {
+    //     println!(\"less\");
+    // }
+    //
+    //  o a list
+    //    item
+    //
+    // E.g. x to y
+    //      y to x
+    println!(\"{}\", x + y);
+}
+
+#[cfg(test)]
+mod tests {}
+";
+
+    fn page(blocks: Vec<Block>) -> Page<'static> {
+        Page { chapter: "if_let", blocks, output: Some("1\n\n".to_string()), previous: Some("loops"), next: None }
+    }
+
+    #[test]
+    fn comments_and_code_alternate() {
+        assert_eq!(blocks(SOURCE), vec![
+            Block::Code("use std::fmt;\n\npub fn example() {".to_string()),
+            Block::Prose("A comment\nover two lines.".to_string()),
+            Block::Code("let x = 1; // stays with the code\n\nlet y = 2;".to_string()),
+            Block::Prose("Another.".to_string()),
+            Block::Prose("And another, quoting".to_string()),
+            Block::Code("if x < y {\n    println!(\"less\");\n}".to_string()),
+            Block::Prose("o a list\nitem".to_string()),
+            Block::Prose("E.g. x to y\ny to x".to_string()),
+            Block::Code("    println!(\"{}\", x + y);\n}".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn every_chapter_has_prose_and_code() {
+        for &(chapter, _) in CHAPTERS {
+            let blocks = blocks(source(chapter).unwrap());
+
+            assert!(blocks.iter().any(|b| matches!(b, Block::Prose(_))), "no prose in {}", chapter);
+            assert!(blocks.iter().any(|b| matches!(b, Block::Code(_))), "no code in {}", chapter);
+            assert!(!blocks.iter().any(|b| matches!(b, Block::Code(c) if c.contains("#[cfg(test)]"))));
+        }
+    }
+
+    #[test]
+    fn titles() {
+        assert_eq!(title("if_let_while_let"), "If let while let");
+        assert_eq!(title("drop"), "Drop");
+    }
+
+    #[test]
+    fn markdown_page() {
+        let page = page(vec![Block::Prose("Box<dyn Foo> and a_b".to_string()), Block::Code("let x = 1;".to_string())]);
+
+        assert_eq!(
+            page.markdown(),
+            "# If let\n\n\
+             Box\\<dyn Foo\\> and a\\_b\n\n\
+             ```rust\nlet x = 1;\n```\n\n\
+             ## Output\n\n```text\n1\n```\n\n\
+             [Previous: Loops](loops.md) | [Index](index.md)\n"
+        );
+    }
+
+    #[test]
+    fn markdown_prose_keeps_its_lines() {
+        let page = page(vec![Block::Prose("Three kinds:\n- one\n2. two\no three".to_string())]);
+
+        assert!(page.markdown().contains("Three kinds:\\\n\\- one\\\n2\\. two\\\no three\n\n"));
+    }
+
+    #[test]
+    fn html_page_is_escaped_and_self_contained() {
+        let page = page(vec![Block::Prose("a < b".to_string()), Block::Code("x && y".to_string())]);
+        let html = page.html();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>If let</title>"));
+        assert!(html.contains("<p>a &lt; b</p>\n<pre><code>x &amp;&amp; y</code></pre>\n"));
+        assert!(html.contains("<pre class=\"output\">1</pre>"));
+        assert!(html.contains("<nav><a href=\"loops.html\">Previous: Loops</a> | <a href=\"index.html\">Index</a></nav>"));
+        assert!(!html.contains("src=") && !html.contains("stylesheet"));
+    }
+
+    #[test]
+    fn indexes_list_every_chapter_in_order() {
+        assert_eq!(markdown_index(&["loops", "if_let"]), "# Syntax and Semantics\n\n1. [Loops](loops.md)\n2. [If let](if_let.md)\n");
+        assert!(html_index(&["loops"]).contains("<ol>\n<li><a href=\"loops.html\">Loops</a></li>\n</ol>"));
+    }
+}
@@ -0,0 +1,244 @@
+use std::fmt;
+
+// Calls back closures when something happens.
+//
+// Listeners are boxed closures, so any closure taking a &E can be registered
+// whatever it captures. They are FnMut rather than Fn so that a listener can
+// keep state of its own between events, such as a count.
+//
+//  let mut clicks = EventEmitter::new();
+//  let subscription = clicks.on(|click: &Click| println!("clicked {}", click.x));
+//  clicks.emit(&Click { x: 1 });
+//  clicks.off(subscription);
+pub struct EventEmitter<E> {
+    // Kept in the order they're called in, highest priority first.
+    listeners: Vec<Listener<E>>,
+    next_id: u64,
+}
+
+struct Listener<E> {
+    id: u64,
+    priority: i32,
+    once: bool,
+    callback: Box<dyn FnMut(&E)>,
+}
+
+// Returned when a listener is registered, and used to remove it again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Subscription(u64);
+
+impl<E> EventEmitter<E> {
+    pub fn new() -> EventEmitter<E> {
+        EventEmitter { listeners: Vec::new(), next_id: 0 }
+    }
+
+    // Registers a listener that's called for every event.
+    pub fn on<F>(&mut self, listener: F) -> Subscription
+    where
+        F: FnMut(&E) + 'static,
+    {
+        self.on_with_priority(0, listener)
+    }
+
+    // Listeners with a higher priority are called first. Those with the same
+    // priority are called in the order they were registered.
+    pub fn on_with_priority<F>(&mut self, priority: i32, listener: F) -> Subscription
+    where
+        F: FnMut(&E) + 'static,
+    {
+        self.add(priority, false, Box::new(listener))
+    }
+
+    // Registers a listener that's only called for the next event, after which
+    // it's removed.
+    pub fn once<F>(&mut self, listener: F) -> Subscription
+    where
+        F: FnOnce(&E) + 'static,
+    {
+        self.once_with_priority(0, listener)
+    }
+
+    pub fn once_with_priority<F>(&mut self, priority: i32, listener: F) -> Subscription
+    where
+        F: FnOnce(&E) + 'static,
+    {
+        // An FnOnce can't be stored as an FnMut, since calling it uses it up.
+        // Wrapping it in an Option lets the FnMut take it out on the first
+        // call, leaving None behind.
+        let mut listener = Some(listener);
+
+        self.add(
+            priority,
+            true,
+            Box::new(move |event| {
+                if let Some(listener) = listener.take() {
+                    listener(event);
+                }
+            }),
+        )
+    }
+
+    fn add(&mut self, priority: i32, once: bool, callback: Box<dyn FnMut(&E)>) -> Subscription {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let position = self.listeners.iter().position(|l| l.priority < priority).unwrap_or(self.listeners.len());
+        self.listeners.insert(position, Listener { id, priority, once, callback });
+
+        Subscription(id)
+    }
+
+    // Removes a listener, returning whether it was still registered. One-shot
+    // listeners are removed by themselves once they've been called.
+    pub fn off(&mut self, subscription: Subscription) -> bool {
+        let before = self.listeners.len();
+        self.listeners.retain(|l| l.id != subscription.0);
+
+        self.listeners.len() != before
+    }
+
+    // Calls every listener with the event, returning how many were called.
+    pub fn emit(&mut self, event: &E) -> usize {
+        for listener in &mut self.listeners {
+            (listener.callback)(event);
+        }
+
+        let called = self.listeners.len();
+        self.listeners.retain(|l| !l.once);
+
+        called
+    }
+
+    pub fn len(&self) -> usize {
+        self.listeners.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.listeners.is_empty()
+    }
+}
+
+impl<E> Default for EventEmitter<E> {
+    fn default() -> EventEmitter<E> {
+        EventEmitter::new()
+    }
+}
+
+// Closures can't be printed, so only the number of listeners is shown.
+impl<E> fmt::Debug for EventEmitter<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EventEmitter").field("listeners", &self.listeners.len()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    // A shared log that listeners write to, since they must be 'static and so
+    // can't borrow a local Vec.
+    fn log() -> Rc<RefCell<Vec<String>>> {
+        Rc::new(RefCell::new(Vec::new()))
+    }
+
+    fn logger(log: &Rc<RefCell<Vec<String>>>, name: &'static str) -> impl FnMut(&i32) + 'static {
+        let log = Rc::clone(log);
+
+        move |event| log.borrow_mut().push(format!("{} {}", name, event))
+    }
+
+    #[test]
+    fn every_listener_sees_every_event() {
+        let log = log();
+        let mut emitter = EventEmitter::new();
+
+        emitter.on(logger(&log, "a"));
+        emitter.on(logger(&log, "b"));
+
+        assert_eq!(emitter.emit(&1), 2);
+        assert_eq!(emitter.emit(&2), 2);
+        assert_eq!(*log.borrow(), vec!["a 1", "b 1", "a 2", "b 2"]);
+    }
+
+    #[test]
+    fn unsubscribed_listeners_are_not_called() {
+        let log = log();
+        let mut emitter = EventEmitter::new();
+
+        let a = emitter.on(logger(&log, "a"));
+        emitter.on(logger(&log, "b"));
+
+        assert!(emitter.off(a));
+        assert!(!emitter.off(a));
+
+        emitter.emit(&1);
+        assert_eq!(*log.borrow(), vec!["b 1"]);
+    }
+
+    #[test]
+    fn higher_priorities_are_called_first() {
+        let log = log();
+        let mut emitter = EventEmitter::new();
+
+        emitter.on(logger(&log, "normal"));
+        emitter.on_with_priority(-1, logger(&log, "low"));
+        emitter.on_with_priority(10, logger(&log, "high"));
+        emitter.on(logger(&log, "normal again"));
+
+        emitter.emit(&0);
+        assert_eq!(*log.borrow(), vec!["high 0", "normal 0", "normal again 0", "low 0"]);
+    }
+
+    #[test]
+    fn once_listeners_are_called_once() {
+        let log = log();
+        let mut emitter = EventEmitter::new();
+
+        emitter.on(logger(&log, "always"));
+        emitter.once(logger(&log, "once"));
+
+        assert_eq!(emitter.emit(&1), 2);
+        assert_eq!(emitter.emit(&2), 1);
+        assert_eq!(emitter.len(), 1);
+        assert_eq!(*log.borrow(), vec!["always 1", "once 1", "always 2"]);
+    }
+
+    #[test]
+    fn once_listeners_can_give_away_what_they_own() {
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let mut emitter = EventEmitter::new();
+
+        // The message is moved out of the closure when it's called, which is
+        // only possible because it's an FnOnce.
+        let message = String::from("hello");
+        let inbox = Rc::clone(&received);
+        emitter.once(move |_: &()| inbox.borrow_mut().push(message));
+
+        emitter.emit(&());
+        emitter.emit(&());
+
+        assert_eq!(*received.borrow(), vec!["hello"]);
+    }
+
+    #[test]
+    fn listeners_keep_their_own_state() {
+        let total = Rc::new(RefCell::new(0));
+        let mut emitter = EventEmitter::new();
+
+        let mut count = 0;
+        let seen = Rc::clone(&total);
+        emitter.on(move |_: &&str| {
+            count += 1;
+            *seen.borrow_mut() = count;
+        });
+
+        emitter.emit(&"a");
+        emitter.emit(&"b");
+
+        assert_eq!(*total.borrow(), 2);
+        assert_eq!(format!("{:?}", emitter), "EventEmitter { listeners: 1 }");
+    }
+}
@@ -13,10 +13,17 @@ pub fn associated_types() {
         type N;
         type E;
 
-        fn has_edge(&self, &Self::N, &Self::N) -> bool;
-        fn edges(&self, &Self::N) -> Vec<Self::E>;
+        fn has_edge(&self, start: &Self::N, end: &Self::N) -> bool;
+        fn edges(&self, node: &Self::N) -> Vec<Self::E>;
     }
 
+    // Note that trait methods used to allow anonymous parameters, so has_edge
+    // could be declared as
+    //
+    //  fn has_edge(&self, &Self::N, &Self::N) -> bool;
+    //
+    // Since the 2018 edition every parameter must be named, or use _.
+
     // Associated types are declared are declared within the body of the trait
     // using the type keyword.
 
@@ -58,10 +65,57 @@ pub fn associated_types() {
     // For example we can provide the Node and Edge concrete types as follows.
     let graph = MyGraph;
 
-    let obj = Box::new(graph) as Box<Graph<N=Node, E=Edge>>;
+    let obj = Box::new(graph) as Box<dyn Graph<N=Node, E=Edge>>;
+
+    // Prior to the 2021 edition this could also be written without dyn, as
+    // Box<Graph<N=Node, E=Edge>>. See trait objects.
+
+    println!(
+        "graph has edge: {}, with {} edges from the node",
+        obj.has_edge(&Node, &Node),
+        obj.edges(&Node).len()
+    );
 
     // Without the concrete types the compiler is unable to determine which
     // impl should be used.
 
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    trait Graph {
+        type N;
+        type E;
+
+        fn has_edge(&self, start: &Self::N, end: &Self::N) -> bool;
+        fn edges(&self, node: &Self::N) -> Vec<Self::E>;
+    }
+
+    struct Node;
+
+    struct Edge;
+
+    struct MyGraph;
+
+    impl Graph for MyGraph {
+        type N = Node;
+        type E = Edge;
+
+        fn has_edge(&self, _: &Node, _: &Node) -> bool {
+            true
+        }
+
+        fn edges(&self, _: &Node) -> Vec<Edge> {
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn trait_object_with_concrete_associated_types() {
+        let obj = Box::new(MyGraph) as Box<dyn Graph<N = Node, E = Edge>>;
+
+        assert!(obj.has_edge(&Node, &Node));
+        assert!(obj.edges(&Node).is_empty());
+    }
+}
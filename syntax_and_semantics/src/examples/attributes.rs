@@ -10,8 +10,8 @@ pub fn attributes() {
     println!();
 }
 
-// The following examples can be run with cargo test since they both use the
-// test attribute.
+// The first example can be run with cargo test since it uses the test
+// attribute.
 
 // Attributes can be applied to the next item, as follows...
 #[test]
@@ -20,8 +20,13 @@ pub fn some_test() {
 }
 
 // Or they can be declared within, and refer to, the item enclosing them.
-// For example....
+// For example, the following turns off clippy's warning about comparing a
+// value with itself, for this function only....
 pub fn another_test() {
-    #![test]
+    #![allow(clippy::eq_op)]
     assert_eq!(1, 1);
 }
+
+// This example used #![test], but test is now implemented as a macro attribute
+// and inner macro attributes are unstable, so it can no longer be used in this
+// position.
@@ -1,3 +1,6 @@
+use crate::combinators::{compose, make_adder, make_counter, make_operation};
+use crate::events::EventEmitter;
+
 pub fn closures() {
     println!("Closures\n");
 
@@ -52,7 +55,10 @@ pub fn closures() {
     // However, using the move keyword we get the following.
     let mut z = 5;
     {
-        let mut add_z = move |x| z += x;
+        let mut add_z = move |x| {
+            z += x;
+            println!("z inside move add_z(5) is {}", z);
+        };
         add_z(5);
     }
 
@@ -66,9 +72,121 @@ pub fn closures() {
     // that created it. This means that generally you cannot return a non-move
     // closure from a function.
 
-    // Further information about the underlying implementation and how to
-    // return a closure is available at
-    // https://doc.rust-lang.org/book/first-edition/closures.html
+    // Closure traits
+    // Every closure implements one or more of three traits, depending on what
+    // it does with the values it captures.
+    //  o Fn - only reads them, so it can be called any number of times, even
+    //    through a shared reference.
+    //  o FnMut - changes them, so calling it needs a mutable borrow.
+    //  o FnOnce - moves them out, so it can only be called once.
+    // Functions taking a closure name the trait they need as a bound, e.g.
+    // F: Fn(i32) -> i32, in the same way as any other generic.
+
+    // Returning closures
+    // Each closure has its own type which can't be written down, so a
+    // function returning one uses impl Fn for the return type. A move closure
+    // has to be used, so it owns what it captured.
+    let add_three = make_adder(3);
+
+    println!("make_adder(3) applied to 4 is {}", add_three(4));
+
+    // A closure that changes its state is returned as impl FnMut.
+    let mut counter = make_counter();
+    counter();
+
+    println!("counter after two calls is {}", counter());
+
+    // Functions can also take closures and return new ones built from them.
+    let add_then_double = compose(make_adder(1), |x| x * 2);
+
+    println!("compose(add one, double) applied to 5 is {}", add_then_double(5));
+
+    // Where different closures may be returned, they have different types,
+    // so they must be boxed as a Box<dyn Fn> trait object instead.
+    if let Some(times_four) = make_operation('*', 4) {
+        println!("make_operation('*', 4) applied to 5 is {}", times_four(5));
+    }
+
+    // Callbacks
+    // Boxed closures can also be stored, for example to call back later when
+    // something happens. EventEmitter keeps a list of listeners and calls
+    // each of them with every event.
+    let mut emitter = EventEmitter::new();
+
+    let subscription = emitter.on(|n: &i32| println!("listener got {}", n));
+    emitter.once(|n: &i32| println!("one-shot listener got {}", n));
+    emitter.on_with_priority(1, |n: &i32| println!("high priority listener got {}", n));
+
+    emitter.emit(&1);
+    emitter.emit(&2);
+    emitter.off(subscription);
+
+    println!("after unsubscribing, emit(3) called {} listener", emitter.emit(&3));
 
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn closures_with_and_without_annotations() {
+        let plus_one = |x: i32| x + 1;
+        let plus_two = |x| {
+            let mut result: i32 = x;
+
+            result += 1;
+            result += 1;
+
+            result
+        };
+
+        assert_eq!(plus_one(2), 3);
+        assert_eq!(plus_two(2), 4);
+    }
+
+    #[test]
+    fn closures_capture_their_environment() {
+        let num = 5;
+        let plus_num = |x| x + num;
+
+        assert_eq!(plus_num(2), 7);
+    }
+
+    #[test]
+    fn borrowing_closure_modifies_the_original() {
+        let mut y = 5;
+
+        {
+            let mut add_y = |x| y += x;
+            add_y(5);
+        }
+
+        assert_eq!(y, 10);
+    }
+
+    #[test]
+    fn move_closure_modifies_a_copy() {
+        let mut z = 5;
+
+        let mut add_z = move |x| {
+            z += x;
+            z
+        };
+
+        // The closure's own copy is updated on each call...
+        assert_eq!(add_z(5), 10);
+        assert_eq!(add_z(5), 15);
+
+        // ...while the binding in the enclosing scope is untouched.
+        assert_eq!(z, 5);
+    }
+
+    #[test]
+    fn move_closure_can_be_returned() {
+        fn make_adder(n: i32) -> Box<dyn Fn(i32) -> i32> {
+            Box::new(move |x| x + n)
+        }
+
+        assert_eq!(make_adder(3)(4), 7);
+    }
+}
@@ -98,6 +98,12 @@ pub fn coercion() {
     // as, Rust lets us.
 
     // Since this is an unsafe operation, we need an unsafe block.
+    //
+    // For this particular conversion the standard library now provides
+    // u32::from_ne_bytes, which does the same thing safely, so the compiler
+    // warns that the transmute is unnecessary. The lint is allowed here to
+    // keep the example.
+    #[allow(unnecessary_transmutes)]
     unsafe {
         let d: u32 = mem::transmute(c);
         println!("array of four bytes transmuted into u32 is: {}", d);
@@ -110,3 +116,42 @@ pub fn coercion() {
 
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    use std::mem;
+
+    #[test]
+    fn safe_casts() {
+        let x: i32 = 5;
+
+        assert_eq!(x as i64, 5i64);
+        assert_eq!(true as u8, 1);
+        assert_eq!(64 as char, '@');
+        // -56 is 0b11001000 in two's complement, which is 200 as a u8.
+        assert_eq!(-56i8 as u8, 200);
+    }
+
+    #[test]
+    fn pointer_to_integer_cast() {
+        let a = 300 as *const char;
+
+        assert_eq!(a as u32, 300);
+    }
+
+    #[test]
+    fn transmute_reinterprets_bytes() {
+        let c = [0u8, 0u8, 0u8, 0u8];
+        #[allow(unnecessary_transmutes)]
+        let d: u32 = unsafe { mem::transmute(c) };
+
+        assert_eq!(d, 0);
+
+        // The result for non-zero bytes depends on the platform's byte order.
+        let e = [1u8, 0u8, 0u8, 0u8];
+        #[allow(unnecessary_transmutes)]
+        let f: u32 = unsafe { mem::transmute(e) };
+
+        assert_eq!(f, u32::from_ne_bytes(e));
+    }
+}
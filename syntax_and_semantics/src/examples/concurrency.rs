@@ -0,0 +1,180 @@
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crate::thread_pool::{Collector, ThreadPool};
+
+pub fn concurrency() {
+    println!("Concurrency\n");
+
+    // Rust's ownership rules also make concurrent code safer, since the
+    // compiler can tell which values may be shared between threads.
+
+    // Two marker traits describe this.
+    //  o Send - ownership of a value of the type may be transferred to another
+    //    thread.
+    //  o Sync - a value of the type may be shared between threads via a
+    //    reference, i.e. &T is Send.
+    // Both are implemented automatically for types made up of Send and Sync
+    // members.
+
+    // Threads
+
+    // A thread is started with thread::spawn which takes a closure and returns
+    // a handle that can be used to wait for the thread to finish.
+    let handle = thread::spawn(|| {
+        "Hello from a thread!"
+    });
+
+    // join blocks until the thread completes and returns the closure's result.
+    println!("spawned thread returned '{}'", handle.join().unwrap());
+
+    // A spawned thread may outlive the function that started it, so a closure
+    // passed to spawn cannot borrow from the enclosing stack frame. Instead the
+    // move keyword, seen in the closures chapter, gives the closure ownership
+    // of the values it uses.
+    let v: Vec<i32> = (1..4).collect();
+
+    let handle = thread::spawn(move || {
+        v.iter().sum::<i32>()
+    });
+
+    // At this point v has been moved into the thread and can no longer be
+    // used here.
+    println!("sum computed by thread is {}", handle.join().unwrap());
+
+    println!();
+
+    // Shared state
+
+    // The mutability chapter used Arc, an atomically reference counted
+    // pointer, without explaining why. Unlike Rc, the count is updated
+    // atomically so an Arc can be cloned and sent to other threads.
+
+    // Arc only provides shared immutable access, so to modify the value a
+    // Mutex is also needed. Locking the mutex returns a guard which gives
+    // mutable access to the value, and releases the lock when dropped.
+    let counter = Arc::new(Mutex::new(0));
+
+    let handles: Vec<_> = (0..10)
+        .map(|_| {
+            let counter = Arc::clone(&counter);
+            thread::spawn(move || {
+                *counter.lock().unwrap() += 1;
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    println!("counter after ten threads is {}", *counter.lock().unwrap());
+
+    // Note that the compiler will reject an attempt to use an Rc here, since
+    // Rc is not Send.
+
+    println!();
+
+    // Channels
+
+    // Rather than sharing memory, threads can communicate by sending values
+    // over a channel. mpsc stands for multiple producer, single consumer, so
+    // the sending half can be cloned but the receiving half cannot.
+    let (tx, rx) = mpsc::channel();
+
+    for id in 0..3 {
+        let tx = tx.clone();
+        thread::spawn(move || {
+            tx.send(id * 10).unwrap();
+        });
+    }
+
+    // The original sender must be dropped, otherwise the receiver would wait
+    // forever for it to send something.
+    drop(tx);
+
+    // Iterating over the receiver ends once every sender has gone away.
+    let mut received: Vec<i32> = rx.iter().collect();
+    received.sort();
+
+    println!("received {:?} over the channel", received);
+
+    println!();
+
+    // Thread pools
+
+    // Spawning a thread per task is fine for a handful of tasks, but for many
+    // small tasks it's cheaper to reuse a fixed number of threads. The
+    // thread_pool module combines the pieces above. Jobs are boxed move
+    // closures sent over a channel, and the workers share the receiving end
+    // behind an Arc<Mutex<_>>.
+    let pool = ThreadPool::new(4);
+    let mut collector = Collector::new();
+
+    for n in 1..9 {
+        collector.submit(&pool, move || n * n);
+    }
+
+    // Each result is an Option, which is None if its job panicked.
+
+    println!(
+        "{} workers computed the squares {:?}",
+        pool.size(),
+        collector.wait()
+    );
+
+    // Dropping the pool waits for any outstanding jobs before stopping the
+    // workers, so nothing that was submitted is lost.
+    pool.shutdown();
+
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn join_returns_the_closure_result() {
+        let v: Vec<i32> = (1..4).collect();
+        let handle = thread::spawn(move || v.iter().sum::<i32>());
+
+        assert_eq!(handle.join().unwrap(), 6);
+    }
+
+    #[test]
+    fn mutex_counter_sees_every_increment() {
+        let counter = Arc::new(Mutex::new(0));
+
+        let handles: Vec<_> = (0..10)
+            .map(|_| {
+                let counter = Arc::clone(&counter);
+                thread::spawn(move || *counter.lock().unwrap() += 1)
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(*counter.lock().unwrap(), 10);
+    }
+
+    #[test]
+    fn receiver_iteration_ends_when_senders_drop() {
+        let (tx, rx) = mpsc::channel();
+
+        for id in 0..3 {
+            let tx = tx.clone();
+            thread::spawn(move || tx.send(id * 10).unwrap());
+        }
+
+        drop(tx);
+
+        let mut received: Vec<i32> = rx.iter().collect();
+        received.sort();
+
+        assert_eq!(received, vec![0, 10, 20]);
+    }
+}
@@ -25,10 +25,15 @@ pub fn const_and_static() {
     // Since both reading and mutating a static are not thread safe, both must
     // occur within an unsafe block.
 
+    // Note that R is copied into a local binding before printing, since
+    // println! would otherwise take a reference to the mutable static, which
+    // the compiler warns against.
     unsafe {
-        println!("static R, before mutation, has value: {}", R);
+        let before = R;
         R += 1;
-        println!("static R, after mutation, has value: {}", R);
+        let after = R;
+        println!("static R, before mutation, has value: {}", before);
+        println!("static R, after mutation, has value: {}", after);
     }
 
     // A static member must be Sync (a type for which it is safe to share
@@ -40,3 +45,23 @@ pub fn const_and_static() {
 
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    const N: i32 = 42;
+    static P: i32 = 24;
+
+    #[test]
+    fn const_and_static_values() {
+        assert_eq!(N, 42);
+        assert_eq!(P, 24);
+    }
+
+    #[test]
+    fn static_has_a_fixed_address() {
+        let first: *const i32 = &P;
+        let second: *const i32 = &P;
+
+        assert_eq!(first, second);
+    }
+}
@@ -1,3 +1,6 @@
+// The method call example below takes needless references on purpose.
+#![allow(clippy::needless_borrow)]
+
 use std::ops::Deref;
 use std::rc::Rc;
 
@@ -74,3 +77,51 @@ pub fn deref_coercions() {
 
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Deref;
+    use std::rc::Rc;
+
+    struct DerefExample<T> {
+        value: T,
+    }
+
+    impl<T> Deref for DerefExample<T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            &self.value
+        }
+    }
+
+    fn length(s: &str) -> usize {
+        s.len()
+    }
+
+    #[test]
+    fn star_uses_deref() {
+        let x = DerefExample { value: 'a' };
+
+        assert_eq!(*x, 'a');
+    }
+
+    #[test]
+    fn references_coerce_through_deref() {
+        let owned = "Hello".to_string();
+        let counted = Rc::new("Counted string".to_string());
+        let custom = DerefExample { value: "abc".to_string() };
+
+        assert_eq!(length(&owned), 5);
+        assert_eq!(length(&counted), 14);
+        // Two levels, DerefExample<String> to String to str.
+        assert_eq!(length(&custom), 3);
+    }
+
+    #[test]
+    fn method_calls_auto_dereference() {
+        let f = &&&5i32;
+
+        assert_eq!(f.pow(2), 25);
+    }
+}
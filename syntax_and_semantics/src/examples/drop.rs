@@ -38,3 +38,31 @@ pub fn drop() {
 
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    struct Firework<'a> {
+        strength: i32,
+        log: &'a RefCell<Vec<i32>>,
+    }
+
+    impl<'a> Drop for Firework<'a> {
+        fn drop(&mut self) {
+            self.log.borrow_mut().push(self.strength);
+        }
+    }
+
+    #[test]
+    fn values_are_dropped_in_reverse_order_of_declaration() {
+        let log = RefCell::new(Vec::new());
+
+        {
+            let _firecracker = Firework { strength: 1, log: &log };
+            let _tnt = Firework { strength: 100, log: &log };
+        }
+
+        assert_eq!(*log.borrow(), vec![100, 1]);
+    }
+}
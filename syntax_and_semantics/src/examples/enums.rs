@@ -30,3 +30,32 @@ pub fn enums() {
     println!();
 }
 
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn variant_names_are_scoped_to_their_enum() {
+        #[derive(Debug, PartialEq)]
+        enum Message {
+            Quit,
+        }
+
+        #[derive(Debug, PartialEq)]
+        enum Command {
+            Quit,
+        }
+
+        assert_eq!(Message::Quit, Message::Quit);
+        assert_eq!(Command::Quit, Command::Quit);
+    }
+
+    #[test]
+    fn values_are_extracted_by_matching() {
+        enum Message {
+            Move { x: i32, y: i32 },
+        }
+
+        let Message::Move { x, y } = Message::Move { x: 1, y: 2 };
+
+        assert_eq!((x, y), (1, 2));
+    }
+}
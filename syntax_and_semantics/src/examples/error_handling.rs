@@ -0,0 +1,492 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::num::ParseIntError;
+use std::path::Path;
+use std::str::{FromStr, ParseBoolError};
+
+pub fn error_handling() {
+    println!("Error handling\n");
+
+    // Rust distinguishes between unrecoverable errors, which panic, and
+    // recoverable errors which are returned as values. The guessing game
+    // called expect on a Result, which panics with the given message on Err.
+    // That's fine for a quick example, but most code should hand the error
+    // back to its caller instead.
+
+    // Option and Result
+
+    // Option<T> is used when a value may be absent, Result<T, E> when an
+    // operation may fail with an error of type E. Both can be matched on, as
+    // shown in the patterns chapter, but they also provide combinators that
+    // avoid a lot of nested matching.
+
+    // map transforms the success value, leaving None or Err untouched.
+    let doubled = "21".parse::<i32>().map(|n| n * 2);
+
+    println!("\"21\" parsed and doubled is {:?}", doubled);
+
+    // map_err transforms the error instead, for example to replace the
+    // standard library error with our own message.
+    let parsed: Result<i32, String> = "abc"
+        .parse::<i32>()
+        .map_err(|e| format!("could not parse 'abc': {}", e));
+
+    println!("map_err gave {:?}", parsed);
+
+    // ok_or turns an Option into a Result by supplying the error to use if
+    // there is no value.
+    let first_word = "".split_whitespace().next().ok_or("no words found");
+
+    println!("ok_or on an empty string gave {:?}", first_word);
+
+    // and_then chains another fallible step, which only runs if the previous
+    // one succeeded. Unlike map, the closure itself returns a Result.
+    let halved = "42"
+        .parse::<i32>()
+        .map_err(|e| e.to_string())
+        .and_then(|n| if n % 2 == 0 { Ok(n / 2) } else { Err(format!("{} is odd", n)) });
+
+    println!("\"42\" parsed and halved is {:?}", halved);
+
+    // unwrap_or supplies a default if there is no value.
+    println!("\"x\" parsed with a default is {}", "x".parse::<i32>().unwrap_or(0));
+
+    println!();
+
+    // The ? operator
+
+    // Returning early on every error would be tedious with match. Instead the
+    // ? operator returns the error from the enclosing function, or evaluates
+    // to the success value. See sum_of_pair below.
+    println!("sum_of_pair(\"1,2\") is {:?}", sum_of_pair("1,2"));
+    println!("sum_of_pair(\"1,x\") is {:?}", sum_of_pair("1,x"));
+    println!("sum_of_pair(\"1\") is {:?}", sum_of_pair("1"));
+
+    // ? also converts the error using From, so a function returning our own
+    // error type can use ? on any error that converts into it. ? works with
+    // Option too, returning None early.
+    println!("last_char_upper(\"abc\") is {:?}", last_char_upper("abc"));
+    println!("last_char_upper(\"\") is {:?}", last_char_upper(""));
+
+    println!();
+
+    // Custom error types
+
+    // A library should describe its errors with a type of its own. Usually
+    // this is an enum with a variant per kind of failure, implementing Display
+    // for a human readable message and the Error trait so it can be used
+    // wherever errors are expected. The ConfigError type below is an example.
+
+    // The worked example parses a simple config file made up of key = value
+    // lines. Errors are reported with the line they occurred on.
+    let source = "\
+# An example config
+name = example
+port = 8080
+verbose = true
+";
+
+    match parse_config(source) {
+        Ok(config) => println!("parsed config: {:?}", config),
+        Err(e)     => println!("failed to parse config: {}", e),
+    }
+
+    let broken = "name = example\nport = eighty\n";
+
+    match parse_config(broken) {
+        Ok(config) => println!("parsed config: {:?}", config),
+        Err(e)     => println!("failed to parse config: {}", e),
+    }
+
+    // Reading the file can fail as well. The io::Error is converted into a
+    // ConfigError by ? in load_config.
+    match load_config(Path::new("does/not/exist.conf")) {
+        Ok(config) => println!("loaded config: {:?}", config),
+        Err(e)     => println!("failed to load config: {}", e),
+    }
+
+    println!();
+
+    // Box<dyn Error>
+
+    // When a function can fail in several unrelated ways and the caller only
+    // needs to report the error, Box<dyn Error> can hold any error type. ?
+    // will box errors automatically. This is handy in main and in examples,
+    // though callers lose the ability to match on specific errors.
+    println!("shifted_port(source, \"10\") is {:?}", shifted_port(source, "10"));
+
+    for &(source, offset) in &[("port = x", "10"), (source, "ten"), (source, "60000")] {
+        if let Err(e) = shifted_port(source, offset) {
+            println!("shifted_port failed: {}", e);
+        }
+    }
+
+    // The original error can still be recovered with downcast_ref.
+    if let Err(e) = shifted_port("port = x", "10") {
+        if let Some(config_error) = e.downcast_ref::<ConfigError>() {
+            println!("downcast to ConfigError: {:?}", config_error);
+        }
+    }
+
+    println!();
+}
+
+// Each ? returns early on failure. The first on an Option so it needs ok_or to
+// become a Result, the others convert a ParseIntError into a String.
+fn sum_of_pair(input: &str) -> Result<i32, String> {
+    let mut parts = input.split(',');
+
+    let a = parts.next().ok_or("missing first value")?;
+    let b = parts.next().ok_or("missing second value")?;
+
+    let a: i32 = a.trim().parse().map_err(|e: ParseIntError| e.to_string())?;
+    let b: i32 = b.trim().parse().map_err(|e: ParseIntError| e.to_string())?;
+
+    Ok(a + b)
+}
+
+fn last_char_upper(input: &str) -> Option<char> {
+    let last = input.chars().last()?;
+    last.to_uppercase().next()
+}
+
+// The settings read from a config file.
+#[derive(Debug, PartialEq)]
+struct Config {
+    name: String,
+    port: u16,
+    verbose: bool,
+    // Optional, defaulting to 1.
+    workers: u32,
+}
+
+#[derive(Debug)]
+enum ConfigError {
+    // The file could not be read.
+    Io(io::Error),
+    // A line is not of the form key = value.
+    MissingSeparator { line: usize },
+    UnknownKey { line: usize, key: String },
+    DuplicateKey { line: usize, key: String },
+    InvalidNumber { line: usize, key: String, source: ParseIntError },
+    InvalidBool { line: usize, key: String, source: ParseBoolError },
+    // A required key was never set.
+    MissingKey(&'static str),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ConfigError::Io(ref e) =>
+                write!(f, "could not read config: {}", e),
+            ConfigError::MissingSeparator { line } =>
+                write!(f, "line {}: expected key = value", line),
+            ConfigError::UnknownKey { line, ref key } =>
+                write!(f, "line {}: unknown key '{}'", line, key),
+            ConfigError::DuplicateKey { line, ref key } =>
+                write!(f, "line {}: '{}' is already set", line, key),
+            ConfigError::InvalidNumber { line, ref key, ref source } =>
+                write!(f, "line {}: invalid number for '{}': {}", line, key, source),
+            ConfigError::InvalidBool { line, ref key, ref source } =>
+                write!(f, "line {}: invalid boolean for '{}': {}", line, key, source),
+            ConfigError::MissingKey(key) =>
+                write!(f, "missing required key '{}'", key),
+        }
+    }
+}
+
+// source exposes the underlying error, if any, so callers can walk the chain
+// of causes.
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            ConfigError::Io(ref e)                        => Some(e),
+            ConfigError::InvalidNumber { ref source, .. } => Some(source),
+            ConfigError::InvalidBool { ref source, .. }   => Some(source),
+            _                                             => None,
+        }
+    }
+}
+
+// This From impl is what allows ? to turn an io::Error into a ConfigError.
+impl From<io::Error> for ConfigError {
+    fn from(e: io::Error) -> ConfigError {
+        ConfigError::Io(e)
+    }
+}
+
+fn load_config(path: &Path) -> Result<Config, ConfigError> {
+    let source = fs::read_to_string(path)?;
+    parse_config(&source)
+}
+
+fn parse_config(source: &str) -> Result<Config, ConfigError> {
+    let mut name = None;
+    let mut port = None;
+    let mut verbose = None;
+    let mut workers = None;
+
+    for (index, text) in source.lines().enumerate() {
+        // Line numbers start at one for humans.
+        let line = index + 1;
+        let text = text.trim();
+
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+
+        let (key, value) = split_key_value(text)
+            .ok_or(ConfigError::MissingSeparator { line })?;
+
+        match key {
+            "name"    => set(&mut name, value.to_string(), line, key)?,
+            "port"    => set(&mut port, parse_number(value, line, key)?, line, key)?,
+            "workers" => set(&mut workers, parse_number(value, line, key)?, line, key)?,
+            "verbose" => {
+                let flag = value.parse().map_err(|source| {
+                    ConfigError::InvalidBool { line, key: key.to_string(), source }
+                })?;
+                set(&mut verbose, flag, line, key)?
+            }
+            _ => return Err(ConfigError::UnknownKey { line, key: key.to_string() }),
+        }
+    }
+
+    Ok(Config {
+        name: name.ok_or(ConfigError::MissingKey("name"))?,
+        port: port.ok_or(ConfigError::MissingKey("port"))?,
+        verbose: verbose.unwrap_or(false),
+        workers: workers.unwrap_or(1),
+    })
+}
+
+fn split_key_value(text: &str) -> Option<(&str, &str)> {
+    let separator = text.find('=')?;
+    let key = text[..separator].trim();
+    let value = text[separator + 1..].trim();
+
+    if key.is_empty() { None } else { Some((key, value)) }
+}
+
+fn parse_number<T>(value: &str, line: usize, key: &str) -> Result<T, ConfigError>
+    where T: FromStr<Err = ParseIntError>
+{
+    value.parse().map_err(|source| {
+        ConfigError::InvalidNumber { line, key: key.to_string(), source }
+    })
+}
+
+fn set<T>(slot: &mut Option<T>, value: T, line: usize, key: &str) -> Result<(), ConfigError> {
+    match *slot {
+        Some(_) => Err(ConfigError::DuplicateKey { line, key: key.to_string() }),
+        None    => {
+            *slot = Some(value);
+            Ok(())
+        }
+    }
+}
+
+// ConfigError, ParseIntError and a plain String message are all boxed by ?.
+fn shifted_port(source: &str, offset: &str) -> Result<u16, Box<dyn Error>> {
+    let config = parse_config(source)?;
+    let offset: u16 = offset.parse()?;
+
+    let port = config.port
+        .checked_add(offset)
+        .ok_or(format!("port {} plus {} is out of range", config.port, offset))?;
+
+    Ok(port)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    const VALID: &str = "name = example\nport = 8080\n";
+
+    #[test]
+    fn combinators() {
+        assert_eq!(sum_of_pair("1,2"), Ok(3));
+        assert_eq!(sum_of_pair(" 4 , 5 "), Ok(9));
+        assert_eq!(sum_of_pair(""), Err("missing second value".to_string()));
+        assert_eq!(sum_of_pair("1"), Err("missing second value".to_string()));
+        assert_eq!(sum_of_pair("1,x"), Err("invalid digit found in string".to_string()));
+
+        assert_eq!(last_char_upper("abc"), Some('C'));
+        assert_eq!(last_char_upper(""), None);
+    }
+
+    #[test]
+    fn parses_valid_config() {
+        let source = "\
+# comment
+
+  name =  example  \n\
+port=8080
+verbose = true
+workers = 4
+";
+
+        assert_eq!(parse_config(source).unwrap(), Config {
+            name: "example".to_string(),
+            port: 8080,
+            verbose: true,
+            workers: 4,
+        });
+    }
+
+    #[test]
+    fn optional_keys_have_defaults() {
+        let config = parse_config(VALID).unwrap();
+
+        assert!(!config.verbose);
+        assert_eq!(config.workers, 1);
+    }
+
+    #[test]
+    fn value_may_contain_separator() {
+        let config = parse_config("name = a=b\nport = 1\n").unwrap();
+        assert_eq!(config.name, "a=b");
+    }
+
+    #[test]
+    fn missing_separator() {
+        let e = parse_config("name = example\n\nport 8080\n").unwrap_err();
+
+        match e {
+            ConfigError::MissingSeparator { line: 3 } => (),
+            ref other => panic!("unexpected error {:?}", other),
+        }
+        assert_eq!(e.to_string(), "line 3: expected key = value");
+    }
+
+    #[test]
+    fn empty_key() {
+        match parse_config(" = example\n").unwrap_err() {
+            ConfigError::MissingSeparator { line: 1 } => (),
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unknown_key() {
+        let e = parse_config("name = example\nhost = localhost\n").unwrap_err();
+
+        match e {
+            ConfigError::UnknownKey { line: 2, ref key } if key == "host" => (),
+            ref other => panic!("unexpected error {:?}", other),
+        }
+        assert_eq!(e.to_string(), "line 2: unknown key 'host'");
+    }
+
+    #[test]
+    fn duplicate_key() {
+        let e = parse_config("port = 1\nname = a\nport = 2\n").unwrap_err();
+
+        match e {
+            ConfigError::DuplicateKey { line: 3, ref key } if key == "port" => (),
+            ref other => panic!("unexpected error {:?}", other),
+        }
+        assert_eq!(e.to_string(), "line 3: 'port' is already set");
+    }
+
+    #[test]
+    fn invalid_number() {
+        let e = parse_config("name = a\nport = eighty\n").unwrap_err();
+
+        match e {
+            ConfigError::InvalidNumber { line: 2, ref key, .. } if key == "port" => (),
+            ref other => panic!("unexpected error {:?}", other),
+        }
+        assert_eq!(
+            e.to_string(),
+            "line 2: invalid number for 'port': invalid digit found in string"
+        );
+        assert!(e.source().is_some());
+    }
+
+    #[test]
+    fn number_out_of_range() {
+        match parse_config("port = 70000\n").unwrap_err() {
+            ConfigError::InvalidNumber { line: 1, .. } => (),
+            other => panic!("unexpected error {:?}", other),
+        }
+
+        match parse_config("workers = -1\n").unwrap_err() {
+            ConfigError::InvalidNumber { line: 1, ref key, .. } if key == "workers" => (),
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn invalid_bool() {
+        let e = parse_config("verbose = yes\n").unwrap_err();
+
+        match e {
+            ConfigError::InvalidBool { line: 1, ref key, .. } if key == "verbose" => (),
+            ref other => panic!("unexpected error {:?}", other),
+        }
+        assert_eq!(
+            e.to_string(),
+            "line 1: invalid boolean for 'verbose': provided string was not `true` or `false`"
+        );
+        assert!(e.source().is_some());
+    }
+
+    #[test]
+    fn missing_keys() {
+        match parse_config("port = 1\n").unwrap_err() {
+            ConfigError::MissingKey("name") => (),
+            other => panic!("unexpected error {:?}", other),
+        }
+
+        let e = parse_config("# nothing here\nname = a\n").unwrap_err();
+
+        match e {
+            ConfigError::MissingKey("port") => (),
+            ref other => panic!("unexpected error {:?}", other),
+        }
+        assert_eq!(e.to_string(), "missing required key 'port'");
+        assert!(e.source().is_none());
+    }
+
+    #[test]
+    fn load_missing_file() {
+        let e = load_config(Path::new("does/not/exist.conf")).unwrap_err();
+
+        match e {
+            ConfigError::Io(ref io_error) => assert_eq!(io_error.kind(), io::ErrorKind::NotFound),
+            ref other => panic!("unexpected error {:?}", other),
+        }
+        assert!(e.to_string().starts_with("could not read config: "));
+        assert!(e.source().is_some());
+    }
+
+    #[test]
+    fn load_file() {
+        let path = env::temp_dir().join("error_handling_load_file.conf");
+        fs::write(&path, VALID).unwrap();
+
+        let config = load_config(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(config.unwrap().port, 8080);
+    }
+
+    #[test]
+    fn boxed_errors() {
+        assert_eq!(shifted_port(VALID, "10").unwrap(), 8090);
+
+        let e = shifted_port("port = x", "10").unwrap_err();
+        assert!(e.downcast_ref::<ConfigError>().is_some());
+
+        let e = shifted_port(VALID, "ten").unwrap_err();
+        assert!(e.downcast_ref::<ParseIntError>().is_some());
+
+        let e = shifted_port(VALID, "60000").unwrap_err();
+        assert_eq!(e.to_string(), "port 8080 plus 60000 is out of range");
+    }
+}
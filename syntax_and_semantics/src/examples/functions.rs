@@ -1,3 +1,6 @@
+// add_two deliberately uses return, which clippy would flag as unneeded.
+#![allow(clippy::needless_return)]
+
 pub fn functions() {
     println!("Functions\n");
 
@@ -11,7 +14,7 @@ pub fn functions() {
 
     // Function pointers.
     // We can create variable bindings to functions as follows.
-    let add_one_p: fn(i32) -> (i32) = add_one;
+    let add_one_p: fn(i32) -> i32 = add_one;
 
     println!("1 + 1 = {}", add_one_p(1));
 
@@ -48,3 +51,23 @@ fn add_one(x: i32) -> i32 {
 fn add_two(x: i32) -> i32 {
     return x + 2;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn functions_return_their_final_expression() {
+        assert_eq!(add_one(1), 2);
+        assert_eq!(add_two(1), 3);
+    }
+
+    #[test]
+    fn function_pointers() {
+        let add_one_p: fn(i32) -> i32 = add_one;
+        let add_two_p = add_two;
+
+        assert_eq!(add_one_p(1), 2);
+        assert_eq!(add_two_p(1), 3);
+    }
+}
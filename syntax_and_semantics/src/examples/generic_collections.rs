@@ -0,0 +1,120 @@
+use crate::collections::{BinaryTree, Queue, Stack};
+
+pub fn generic_collections() {
+    println!("Generic Collections\n");
+
+    // The generics chapter's Point<T> holds two values of any one type. The
+    // same idea scales up to collections, which hold any number of them. The
+    // collections module has three, each written with a type parameter T for
+    // what it holds.
+
+    // A Stack<T> gives values back in the reverse of the order they went in.
+    let mut stack = Stack::new();
+
+    stack.push("first");
+    stack.push("second");
+    stack.push("third");
+
+    println!("stack is {:?}, with {:?} on top", stack, stack.peek());
+    println!("popped {:?}", stack.pop());
+
+    // A Queue<T> gives them back in the same order. It keeps them in a ring
+    // buffer, a Vec used as a circle, so that taking from the front doesn't
+    // shift everything along.
+    let mut queue = Queue::new();
+
+    queue.push(1);
+    queue.push(2);
+    queue.push(3);
+
+    println!("queue is {:?}, with {:?} at the front", queue, queue.peek());
+    println!("popped {:?}", queue.pop());
+
+    // A BinaryTree<T> keeps its values sorted, which it can only do for types
+    // that can be compared. So its impl block asks for T: Ord, and trying to
+    // make a tree of something without an order won't compile.
+    let tree: BinaryTree<i32> = [5, 3, 8, 1, 4, 9].into_iter().collect();
+
+    println!("tree is {:?}, contains 4: {}", tree, tree.contains(&4));
+
+    // A tree can be walked in more than one order. In-order gives the sorted
+    // values, pre-order visits each node before its children and post-order
+    // visits it after them.
+    println!("in order:   {:?}", tree.iter().collect::<Vec<_>>());
+    println!("pre-order:  {:?}", tree.pre_order().collect::<Vec<_>>());
+    println!("post-order: {:?}", tree.post_order().collect::<Vec<_>>());
+
+    // Each collection implements FromIterator, which is what lets collect
+    // build one above, and IntoIterator, which lets a for loop walk it.
+
+    // Collections and ownership
+    // A collection owns what it holds. Pushing a String moves it in, so the
+    // original binding can't be used afterwards, and popping moves it back
+    // out again, without copying.
+    let mut names = Stack::new();
+    let name = String::from("Ferris");
+
+    names.push(name);
+
+    // The following, if uncommented, would fail at compile time.
+    // println!("{}", name);
+
+    // Iterating over a reference borrows the values, leaving the collection
+    // as it was...
+    for name in &names {
+        println!("borrowed {}", name);
+    }
+
+    // ...while iterating over the collection itself moves each value out and
+    // uses the collection up.
+    for name in names {
+        println!("took {}", name);
+    }
+
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collections_are_generic_over_what_they_hold() {
+        let stack: Stack<&str> = ["a", "b"].into_iter().collect();
+        let queue: Queue<char> = "ab".chars().collect();
+        let tree: BinaryTree<String> = ["b", "a"].iter().map(|s| s.to_string()).collect();
+
+        assert_eq!(stack.peek(), Some(&"b"));
+        assert_eq!(queue.peek(), Some(&'a'));
+        assert_eq!(tree.min().map(String::as_str), Some("a"));
+    }
+
+    #[test]
+    fn values_move_in_and_back_out() {
+        let mut names = Stack::new();
+        let name = String::from("Ferris");
+        let address = name.as_ptr();
+
+        // Popping hands back the same heap data that was pushed, nothing is
+        // copied.
+        names.push(name);
+        let popped = names.pop().unwrap();
+
+        assert_eq!(popped.as_ptr(), address);
+    }
+
+    #[test]
+    fn for_loops_borrow_or_consume() {
+        let queue: Queue<String> = ["x", "y"].iter().map(|s| s.to_string()).collect();
+        let mut lengths = 0;
+
+        for s in &queue {
+            lengths += s.len();
+        }
+
+        let owned: Vec<String> = queue.into_iter().collect();
+
+        assert_eq!(lengths, 2);
+        assert_eq!(owned, vec!["x", "y"]);
+    }
+}
@@ -1,7 +1,13 @@
 #![allow(unused_variables)]
 #![allow(dead_code)]
+// The generic functions deliberately use return, which clippy would flag as
+// unneeded.
+#![allow(clippy::needless_return)]
 
-use std;
+// Older editions needed use std; before std::mem::swap could be used in this
+// module. Since the 2018 edition that import is redundant.
+
+use crate::memo::Memo;
 
 pub fn generics() {
     println!("Generics\n");
@@ -17,28 +23,49 @@ pub fn generics() {
     println!("simple_generic_function returned {}", y);
     println!("multiple_typed_generic_function returned {}", z);
 
-    // Generic types can also be declared for structs.
-    struct Point<T> {
-        x: T,
-        y: T,
-    }
+    // Generic types can also be declared for structs, see Point below.
+    let mut int_point = Point { x: 12, y: 42 };
+    let mut float_point = Point { x: 1.2, y: 4.2 };
 
-    let int_point = Point { x: 12, y: 42 };
-    let float_point = Point { x: 1.2, y: 4.2 };
+    // It then follows that struct impls also support generic types, so swap
+    // can be called on both points.
+    int_point.swap();
+    float_point.swap();
 
-    // It then follows that struct impls also support generic types.
-    impl<T> Point<T> {
-        fn swap(&mut self) {
-            std::mem::swap(&mut self.x, &mut self.y);
-        }
-    }
+    println!("int_point after swap is ({}, {})", int_point.x, int_point.y);
+    println!("float_point after swap is ({}, {})", float_point.x, float_point.y);
 
     // Note that explicit type annotations will be required where the compiler
     // is unable to resolve ambiguous types.
 
+    // A struct may be generic over a closure's type as well. Every closure has
+    // its own type, so storing one in a struct needs a type parameter for it,
+    // constrained by one of the closure traits. Memo takes the closure type F
+    // along with the types of its argument and result.
+    let mut square = Memo::new(|&x: &u64| x * x);
+
+    square.get(12);
+    square.get(12);
+
+    println!("Memo of square after two calls with 12: {:?}", square.stats());
+
+    // The type parameters are worked out from the closure, so they rarely need
+    // writing out.
+
     println!();
 }
 
+struct Point<T> {
+    x: T,
+    y: T,
+}
+
+impl<T> Point<T> {
+    fn swap(&mut self) {
+        std::mem::swap(&mut self.x, &mut self.y);
+    }
+}
+
 fn simple_generic_function<T>(x: T) -> T {
     return x;
 }
@@ -46,3 +73,31 @@ fn simple_generic_function<T>(x: T) -> T {
 fn multiple_typed_generic_function<A, B>(a: A, b: B) -> A {
     return a;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generic_functions_return_their_argument() {
+        assert_eq!(simple_generic_function(12), 12);
+        assert_eq!(simple_generic_function("Foo"), "Foo");
+        assert_eq!(multiple_typed_generic_function(1, "Foo"), 1);
+    }
+
+    #[test]
+    fn swap_works_for_any_type() {
+        let mut int_point = Point { x: 12, y: 42 };
+        int_point.swap();
+        assert_eq!((int_point.x, int_point.y), (42, 12));
+
+        let mut float_point = Point { x: 1.2, y: 4.2 };
+        float_point.swap();
+        assert_eq!((float_point.x, float_point.y), (4.2, 1.2));
+
+        // The values are moved rather than copied, so non-Copy types work too.
+        let mut string_point = Point { x: "a".to_string(), y: "b".to_string() };
+        string_point.swap();
+        assert_eq!((string_point.x.as_str(), string_point.y.as_str()), ("b", "a"));
+    }
+}
@@ -22,3 +22,14 @@ pub fn if_examples() {
 
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn if_is_an_expression() {
+        let x = 5;
+        let y = if x == 5 { 10 } else { 0 };
+
+        assert_eq!(y, 10);
+    }
+}
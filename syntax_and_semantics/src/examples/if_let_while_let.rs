@@ -1,3 +1,10 @@
+// The long-winded alternatives to if let are shown deliberately, so clippy's
+// suggestions to shorten them are turned off.
+#![allow(clippy::single_match)]
+#![allow(clippy::unnecessary_unwrap)]
+#![allow(clippy::unnecessary_literal_unwrap)]
+#![allow(clippy::redundant_pattern_matching)]
+
 pub fn if_let_while_let() {
     println!("if let and while let\n");
 
@@ -53,3 +60,36 @@ pub fn if_let_while_let() {
 
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn if_let_binds_on_match() {
+        let something = Some(1);
+        let nothing: Option<i32> = None;
+        let mut seen = Vec::new();
+
+        if let Some(x) = something {
+            seen.push(x);
+        }
+
+        if let Some(x) = nothing {
+            seen.push(x);
+        }
+
+        assert_eq!(seen, vec![1]);
+    }
+
+    #[test]
+    fn while_let_loops_until_pattern_fails() {
+        let mut v = vec![1, 3, 5, 7, 11];
+        let mut popped = Vec::new();
+
+        while let Some(x) = v.pop() {
+            popped.push(x);
+        }
+
+        assert_eq!(popped, vec![11, 7, 5, 3, 1]);
+        assert!(v.is_empty());
+    }
+}
@@ -0,0 +1,199 @@
+use crate::adaptors::{fibonacci, IteratorExt};
+
+pub fn iterators() {
+    println!("Iterators\n");
+
+    // The loops chapter used for loops over ranges, and the lines and
+    // enumerate methods. All of these are built on the Iterator trait, which
+    // only requires a single method to be implemented.
+    //
+    // trait Iterator {
+    //     type Item;
+    //     fn next(&mut self) -> Option<Self::Item>;
+    // }
+    //
+    // next returns Some(item) until the iterator is exhausted, after which it
+    // returns None. Note the associated type Item, see associated types.
+
+    // We can call next ourselves. The iterator must be mutable since calling
+    // next changes its state.
+    let mut range = 0..3;
+
+    println!("range.next() returned {:?}", range.next());
+    println!("range.next() returned {:?}", range.next());
+    println!("range.next() returned {:?}", range.next());
+    println!("range.next() returned {:?}", range.next());
+
+    println!();
+
+    // A for loop is just a convenient way of calling next until None is
+    // returned. Any type can be iterated over by implementing the trait.
+    struct Countdown {
+        from: u32,
+    }
+
+    impl Iterator for Countdown {
+        type Item = u32;
+
+        fn next(&mut self) -> Option<u32> {
+            if self.from == 0 {
+                None
+            } else {
+                self.from -= 1;
+                Some(self.from + 1)
+            }
+        }
+    }
+
+    for n in (Countdown { from: 3 }) {
+        println!("countdown: {}", n);
+    }
+
+    println!();
+
+    // Consumers
+
+    // Iterators are lazy, nothing happens until a consumer asks for values.
+    // collect is the most common consumer, building a collection from the
+    // items. Since it can build many different collections, the target type
+    // usually has to be specified.
+    let squares: Vec<i32> = (1..6).map(|x| x * x).collect();
+
+    println!("squares collected into a vector: {:?}", squares);
+
+    // Other consumers reduce the items to a single value.
+    println!("sum of squares is {}", squares.iter().sum::<i32>());
+    println!(
+        "first square greater than 10 is {:?}",
+        squares.iter().find(|&&x| x > 10)
+    );
+
+    // fold is the most general, carrying an accumulator from item to item.
+    println!(
+        "squares joined using fold: {}",
+        squares.iter().fold(String::new(), |acc, x| acc + &x.to_string() + ";")
+    );
+
+    println!();
+
+    // Adaptors
+
+    // Adaptors take an iterator and return a new iterator, so they can be
+    // chained. Again, nothing is computed until the result is consumed. The
+    // following would do nothing, and the compiler warns that the result is
+    // unused.
+    //
+    // (1..100).map(|x| println!("{}", x));
+
+    let odd_squares: Vec<i32> = (1..)
+        .filter(|x| x % 2 == 1)
+        .map(|x| x * x)
+        .take(4)
+        .collect();
+
+    // Note that 1.. is infinite, which is fine since take stops asking for
+    // values once it has four.
+    println!("first four odd squares: {:?}", odd_squares);
+
+    let pairs: Vec<(char, i32)> = "abc".chars().zip(1..).collect();
+
+    println!("zip pairs up two iterators: {:?}", pairs);
+
+    // Some iterators can also be consumed from the back by implementing
+    // DoubleEndedIterator, which is what rev relies on.
+    let reversed: Vec<i32> = (1..6).rev().collect();
+
+    println!("rev of 1..6 is {:?}", reversed);
+
+    println!();
+
+    // IntoIterator
+
+    // The three ways of iterating over a vector shown in the vectors chapter
+    // are implementations of IntoIterator. for x in v calls v.into_iter(), so
+    // &v, &mut v and v each produce a different iterator, over &T, &mut T and
+    // T respectively.
+
+    // Writing our own adaptors
+
+    // The adaptors module defines adaptors in the same way as the standard
+    // library. Each is a struct wrapping another iterator, with an extension
+    // trait that adds a method to every Iterator to construct it.
+    let chunks: Vec<Vec<i32>> = (1..8).chunked(3).collect();
+
+    println!("(1..8).chunked(3) is {:?}", chunks);
+
+    let mixed: Vec<i32> = vec![1, 3, 5].into_iter().interleave(vec![2, 4]).collect();
+
+    println!("interleaving [1, 3, 5] and [2, 4] gives {:?}", mixed);
+
+    let words = ["apple", "avocado", "banana", "cherry", "cranberry"];
+    let one_per_letter: Vec<&str> = words
+        .iter()
+        .cloned()
+        .dedup_by_key(|w| w.chars().next())
+        .collect();
+
+    println!("first word for each initial is {:?}", one_per_letter);
+
+    let running_totals: Vec<i32> = (1..)
+        .scan_while(0, |total, n| total + n, |&total| total < 20)
+        .collect();
+
+    println!("running totals while below 20: {:?}", running_totals);
+
+    // An iterator doesn't need to be backed by a collection at all. This one
+    // generates values until they no longer fit in a u64.
+    let fib: Vec<u64> = fibonacci().take(10).collect();
+
+    println!("first ten Fibonacci numbers: {:?}", fib);
+
+    // Adaptors should also implement size_hint, which lets collect allocate
+    // the right amount of space up front, and DoubleEndedIterator where the
+    // items can be worked out from the back.
+    println!("(1..8).chunked(3).size_hint() is {:?}", (1..8).chunked(3).size_hint());
+    println!(
+        "(1..8).chunked(3).rev() is {:?}",
+        (1..8).chunked(3).rev().collect::<Vec<_>>()
+    );
+
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    #[test]
+    fn next_returns_none_when_exhausted() {
+        let mut range = 0..2;
+
+        assert_eq!(range.next(), Some(0));
+        assert_eq!(range.next(), Some(1));
+        assert_eq!(range.next(), None);
+    }
+
+    #[test]
+    fn adaptors_are_lazy() {
+        let calls = Cell::new(0);
+        let mapped = (1..100).inspect(|_| calls.set(calls.get() + 1));
+
+        assert_eq!(calls.get(), 0);
+
+        let taken: Vec<i32> = mapped.take(3).collect();
+
+        assert_eq!(taken, vec![1, 2, 3]);
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn infinite_ranges_with_take() {
+        let odd_squares: Vec<i32> = (1..)
+            .filter(|x| x % 2 == 1)
+            .map(|x| x * x)
+            .take(4)
+            .collect();
+
+        assert_eq!(odd_squares, vec![1, 9, 25, 49]);
+    }
+}
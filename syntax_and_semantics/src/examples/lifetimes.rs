@@ -1,3 +1,6 @@
+// less_than deliberately uses return, which clippy would flag as unneeded.
+#![allow(clippy::needless_return)]
+
 pub fn lifetimes() {
     println!("Lifetimes\n");
 
@@ -47,7 +50,28 @@ pub fn lifetimes() {
 // references, and the result.
 // In this case we declare that the lifetime of the result will share the outer
 // scope of the incoming data parameter.
+// Clippy points out that 'inner could be elided, since nothing else refers to
+// it, but it's kept here to show both lifetimes.
+#[allow(clippy::needless_lifetimes)]
 fn less_than<'outer, 'inner>(data: &'outer mut Vec<i32>, limit: &'inner i32) -> &'outer Vec<i32> {
     data.retain(|&d| d < *limit);
     return data;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn result_outlives_the_limit() {
+        let mut data = vec![1, 2, 3, 4, 5, 6, 7];
+        let filtered;
+
+        {
+            let limit = 5;
+            filtered = less_than(&mut data, &limit);
+        }
+
+        assert_eq!(*filtered, vec![1, 2, 3, 4]);
+    }
+}
@@ -71,14 +71,74 @@ pub fn loops() {
 
     // Labelling of nested loops is also supported. The labels can be passed to
     // break and continue. The following will only print the x and y values
-    // when both are equal.
+    // when both are even.
     'outer: for x in 1..11 {
         'inner: for y in 1..11 {
-            if x % 2 == 1 { continue; }
-            if y % 2 == 1 { continue; }
+            if x % 2 == 1 { continue 'outer; }
+            if y % 2 == 1 { continue 'inner; }
             println!("nested for loops with labels: x {}, y {}", x, y);
         }
     }
 
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn range_upper_bound_is_exclusive() {
+        let values: Vec<i32> = (0..10).collect();
+
+        assert_eq!(values.len(), 10);
+        assert_eq!(values.first(), Some(&0));
+        assert_eq!(values.last(), Some(&9));
+    }
+
+    #[test]
+    fn enumerate_counts_from_zero() {
+        let pairs: Vec<(usize, i32)> = (5..8).enumerate().collect();
+
+        assert_eq!(pairs, vec![(0, 5), (1, 6), (2, 7)]);
+    }
+
+    #[test]
+    fn break_ends_loop_early() {
+        let mut x = 0;
+        let limit = 5;
+
+        loop {
+            x += 1;
+            if x > limit { break; }
+        }
+
+        assert_eq!(x, 6);
+    }
+
+    #[test]
+    fn continue_skips_odd_values() {
+        let mut evens = Vec::new();
+
+        for x in 1..11 {
+            if x % 2 == 1 { continue; }
+            evens.push(x);
+        }
+
+        assert_eq!(evens, vec![2, 4, 6, 8, 10]);
+    }
+
+    #[test]
+    fn labelled_continue_skips_to_the_outer_loop() {
+        let mut pairs = Vec::new();
+
+        'outer: for x in 1..11 {
+            'inner: for y in 1..11 {
+                if x % 2 == 1 { continue 'outer; }
+                if y % 2 == 1 { continue 'inner; }
+                pairs.push((x, y));
+            }
+        }
+
+        assert_eq!(pairs.len(), 25);
+        assert!(pairs.iter().all(|&(x, y)| x % 2 == 0 && y % 2 == 0));
+    }
+}
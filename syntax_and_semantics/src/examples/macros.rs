@@ -1,6 +1,24 @@
 #![allow(unused_must_use)]
+// vecExample pushes each element to show how vec! might be implemented.
+#![allow(clippy::vec_init_then_push)]
 use std::fmt::Write;
 
+// Used in the Repetition section below.
+macro_rules! o_O {
+    (
+        $(
+            $x:expr; [ $( $y:expr ),* ]
+        );*
+    ) => {
+        &[ $($( $x + $y ),*),* ]
+    }
+}
+
+// Used in the Hygiene section below.
+macro_rules! five_times {
+    ($x:expr) => (5 * $x);
+}
+
 pub fn macros() {
     println!("Macros\n");
 
@@ -73,15 +91,9 @@ pub fn macros() {
     // The following macro illustrates the duplication of variables from outer
     // repetition levels.
 
-    macro_rules! o_O {
-        (
-            $(
-                $x:expr; [ $( $y:expr ),* ]
-            );*
-        ) => {
-            &[ $($( $x + $y ),*),* ]
-        }
-    }
+    // o_O is defined at the top of this file, outside of this function, so
+    // that the tests can use it too. Note that macro_rules! definitions are
+    // only visible to code that follows them in the source.
 
     let a: &[i32] = o_O!(10; [1, 2, 3]; 20; [4, 5, 6]);
 
@@ -95,9 +107,7 @@ pub fn macros() {
     // The following macro produces the desired results in Rust without the need
     // for workarounds.
 
-    macro_rules! five_times {
-        ($x:expr) => (5 * $x);
-    }
+    // five_times is also defined at the top of this file.
 
     assert_eq!(25, five_times!(2 + 3));
 
@@ -136,3 +146,32 @@ pub fn macros() {
 
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn outer_repetition_is_duplicated_for_each_inner_value() {
+        let a: &[i32] = o_O!(10; [1, 2, 3]; 20; [4, 5, 6]);
+        assert_eq!(a, [11, 12, 13, 24, 25, 26]);
+
+        let b: &[i32] = o_O!(100; [1]);
+        assert_eq!(b, [101]);
+    }
+
+    #[test]
+    fn macro_arguments_are_expanded_hygienically() {
+        // With text substitution this would be 5 * 2 + 3, i.e. 13.
+        assert_eq!(five_times!(2 + 3), 25);
+    }
+
+    #[test]
+    fn matcher_selects_rule_by_literal_tokens() {
+        macro_rules! foo {
+            (x => $e:expr) => (format!("mode X: {}", $e));
+            (y => $e:expr) => (format!("mode Y: {}", $e));
+        }
+
+        assert_eq!(foo!(x => 2), "mode X: 2");
+        assert_eq!(foo!(y => 3), "mode Y: 3");
+    }
+}
@@ -55,3 +55,40 @@ pub fn matching() {
 
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    fn number_name(x: i32) -> &'static str {
+        match x {
+            1 => "one",
+            2 => "two",
+            3 => "three",
+            _ => "more typing required",
+        }
+    }
+
+    #[test]
+    fn match_is_an_expression() {
+        assert_eq!(number_name(2), "two");
+        assert_eq!(number_name(42), "more typing required");
+    }
+
+    #[test]
+    fn match_binds_enum_values() {
+        enum Message {
+            ChangeColor(i32, i32, i32),
+            Move { x: i32, y: i32 },
+            Write(String),
+        }
+
+        let describe = |message: Message| match message {
+            Message::ChangeColor(r, g, b)   => format!("({}, {}, {})", r, g, b),
+            Message::Move { x, y: y_alias } => format!("move {} {}", x, y_alias),
+            Message::Write(message)         => message,
+        };
+
+        assert_eq!(describe(Message::Write("Hallo Welt!".to_owned())), "Hallo Welt!");
+        assert_eq!(describe(Message::Move { x: 1, y: 2 }), "move 1 2");
+        assert_eq!(describe(Message::ChangeColor(1, 2, 3)), "(1, 2, 3)");
+    }
+}
@@ -1,4 +1,5 @@
-use std;
+// Previously use std; would be required here to refer to std::f64::consts::PI.
+// Since the 2018 edition std is available to every module without an import.
 
 pub fn method_syntax() {
     println!("Method Syntax\n");
@@ -49,10 +50,12 @@ pub fn method_syntax() {
 
     impl Circle {
         fn new(x: f64, y: f64, radius: f64) -> Circle {
+            // When a variable has the same name as a field, the field init
+            // shorthand can be used, so x is equivalent to x: x.
             Circle {
-                x: x,
-                y: y,
-                radius: radius,
+                x,
+                y,
+                radius,
             }
         }
     }
@@ -119,3 +122,61 @@ pub fn method_syntax() {
 
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    struct Circle {
+        radius: f64,
+    }
+
+    impl Circle {
+        fn new(radius: f64) -> Circle {
+            Circle { radius }
+        }
+
+        fn area(&self) -> f64 {
+            PI * (self.radius * self.radius)
+        }
+
+        fn grow(&self, increment: f64) -> Circle {
+            Circle { radius: self.radius + increment }
+        }
+    }
+
+    #[test]
+    fn methods_can_be_chained() {
+        let c = Circle::new(2.0);
+
+        assert_eq!(c.grow(2.0).area(), Circle::new(4.0).area());
+        assert_eq!(c.grow(1.0).grow(1.0).radius, 4.0);
+        // grow borrows self, so the original circle is unchanged.
+        assert_eq!(c.radius, 2.0);
+    }
+
+    #[test]
+    fn builder_starts_from_defaults() {
+        struct CircleBuilder {
+            radius: f64,
+        }
+
+        impl CircleBuilder {
+            fn new() -> CircleBuilder {
+                CircleBuilder { radius: 1.0 }
+            }
+
+            fn radius(&mut self, radius: f64) -> &mut CircleBuilder {
+                self.radius = radius;
+                self
+            }
+
+            fn finalize(&self) -> Circle {
+                Circle::new(self.radius)
+            }
+        }
+
+        assert_eq!(CircleBuilder::new().finalize().radius, 1.0);
+        assert_eq!(CircleBuilder::new().radius(50.0).finalize().radius, 50.0);
+    }
+}
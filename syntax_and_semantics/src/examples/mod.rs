@@ -15,6 +15,7 @@ mod patterns;
 mod method_syntax;
 mod strings;
 mod generics;
+mod generic_collections;
 mod traits;
 mod drop;
 mod if_let_while_let;
@@ -31,6 +32,9 @@ mod operators_and_overloading;
 mod deref_coercions;
 mod macros;
 mod raw_pointers;
+mod concurrency;
+mod iterators;
+mod error_handling;
 
 pub use self::variable_bindings::*;
 pub use self::functions::*;
@@ -49,6 +53,7 @@ pub use self::patterns::*;
 pub use self::method_syntax::*;
 pub use self::strings::*;
 pub use self::generics::*;
+pub use self::generic_collections::*;
 pub use self::traits::*;
 pub use self::drop::*;
 pub use self::if_let_while_let::*;
@@ -65,3 +70,102 @@ pub use self::operators_and_overloading::*;
 pub use self::deref_coercions::*;
 pub use self::macros::*;
 pub use self::raw_pointers::*;
+pub use self::concurrency::*;
+pub use self::iterators::*;
+pub use self::error_handling::*;
+
+// A chapter's name and the function that runs it.
+pub type Chapter = (&'static str, fn());
+
+// Every chapter, in the order of the book.
+pub const CHAPTERS: &[Chapter] = &[
+    ("variable_bindings", variable_bindings),
+    ("functions", functions),
+    ("primitive_types", primitive_types),
+    ("if_examples", if_examples),
+    ("loops", loops),
+    ("vectors", vectors),
+    ("ownership", ownership),
+    ("references_and_borrowing", references_and_borrowing),
+    ("lifetimes", lifetimes),
+    ("mutability", mutability),
+    ("structs", structs),
+    ("enums", enums),
+    ("matching", matching),
+    ("patterns", patterns),
+    ("method_syntax", method_syntax),
+    ("strings", strings),
+    ("generics", generics),
+    ("generic_collections", generic_collections),
+    ("traits", traits),
+    ("drop", drop),
+    ("if_let_while_let", if_let_while_let),
+    ("trait_objects", trait_objects),
+    ("closures", closures),
+    ("universal_function_call_syntax", universal_function_call_syntax),
+    ("const_and_static", const_and_static),
+    ("attributes", attributes),
+    ("type_aliases", type_aliases),
+    ("coercion", coercion),
+    ("associated_types", associated_types),
+    ("unsized_types", unsized_types),
+    ("operators_and_overloading", operators_and_overloading),
+    ("deref_coercions", deref_coercions),
+    ("macros", macros),
+    ("raw_pointers", raw_pointers),
+    ("concurrency", concurrency),
+    ("iterators", iterators),
+    ("error_handling", error_handling),
+];
+
+macro_rules! sources {
+    ($($chapter:ident),* $(,)?) => {
+        &[$((stringify!($chapter), include_str!(concat!(stringify!($chapter), ".rs")))),*]
+    };
+}
+
+// The source of every chapter, compiled in so that the tutor and the docs can
+// show it wherever they're run from.
+const SOURCES: &[(&str, &str)] = sources!(
+    variable_bindings,
+    functions,
+    primitive_types,
+    if_examples,
+    loops,
+    vectors,
+    ownership,
+    references_and_borrowing,
+    lifetimes,
+    mutability,
+    structs,
+    enums,
+    matching,
+    patterns,
+    method_syntax,
+    strings,
+    generics,
+    generic_collections,
+    traits,
+    drop,
+    if_let_while_let,
+    trait_objects,
+    closures,
+    universal_function_call_syntax,
+    const_and_static,
+    attributes,
+    type_aliases,
+    coercion,
+    associated_types,
+    unsized_types,
+    operators_and_overloading,
+    deref_coercions,
+    macros,
+    raw_pointers,
+    concurrency,
+    iterators,
+    error_handling,
+);
+
+pub fn source(chapter: &str) -> Option<&'static str> {
+    SOURCES.iter().find(|&&(name, _)| name == chapter).map(|&(_, source)| source)
+}
@@ -73,3 +73,66 @@ pub fn mutability() {
 
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, RefCell};
+    use std::sync::Arc;
+
+    #[test]
+    fn mutable_reference_changes_the_binding() {
+        let mut x = 2;
+
+        {
+            let ref_x = &mut x;
+            *ref_x = 12;
+        }
+
+        assert_eq!(x, 12);
+    }
+
+    #[test]
+    fn arc_clones_share_the_same_value() {
+        let arc = Arc::new(5);
+        let arc_clone = arc.clone();
+
+        assert_eq!(*arc_clone, 5);
+        assert!(Arc::ptr_eq(&arc, &arc_clone));
+        assert_eq!(Arc::strong_count(&arc), 2);
+    }
+
+    #[test]
+    fn refcell_allows_interior_mutability() {
+        let ref_cell = RefCell::new(42);
+
+        {
+            let mut ref_to_cell = ref_cell.borrow_mut();
+            *ref_to_cell = 24;
+        }
+
+        assert_eq!(ref_cell.into_inner(), 24);
+    }
+
+    #[test]
+    fn refcell_checks_borrows_at_runtime() {
+        let ref_cell = RefCell::new(42);
+        let _first = ref_cell.borrow_mut();
+
+        assert!(ref_cell.try_borrow_mut().is_err());
+    }
+
+    #[test]
+    fn cell_emulates_field_level_mutability() {
+        struct Point {
+            a: i32,
+            b: Cell<i32>,
+        }
+
+        let point = Point { a: 5, b: Cell::new(6) };
+
+        point.b.set(7);
+
+        assert_eq!(point.a, 5);
+        assert_eq!(point.b.get(), 7);
+    }
+}
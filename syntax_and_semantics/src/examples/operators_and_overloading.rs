@@ -41,3 +41,30 @@ pub fn operators_and_overloading() {
 
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Add;
+
+    #[derive(Debug, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    impl Add for Point {
+        type Output = Point;
+
+        fn add(self, other: Point) -> Point {
+            Point { x: self.x + other.x, y: self.y + other.y }
+        }
+    }
+
+    #[test]
+    fn plus_calls_add() {
+        let p1 = Point { x: 1, y: 1 };
+        let p2 = Point { x: 2, y: 2 };
+
+        assert_eq!(p1 + p2, Point { x: 3, y: 3 });
+    }
+}
@@ -38,3 +38,27 @@ fn take(v: Vec<i32>) {
 fn double_i32(i: i32) -> i32 {
     i * 2
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copy_types_remain_usable_after_being_passed() {
+        let i: i32 = 12;
+
+        assert_eq!(double_i32(i), 24);
+        assert_eq!(i, 12);
+    }
+
+    #[test]
+    fn moving_a_vector_transfers_the_same_allocation() {
+        let v = vec![1, 2, 3];
+        let address = v.as_ptr();
+
+        // Ownership moves, the heap data itself is not copied.
+        let w = v;
+
+        assert_eq!(w.as_ptr(), address);
+    }
+}
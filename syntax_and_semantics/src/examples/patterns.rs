@@ -1,3 +1,10 @@
+// Each match below demonstrates a single kind of pattern, so many could be
+// written more simply as a let, and @ _ is used to show binding with @.
+#![allow(clippy::match_single_binding)]
+#![allow(clippy::redundant_pattern)]
+
+use crate::expr::{self, Statement};
+
 pub fn patterns() {
     println!("Patterns\n");
 
@@ -90,11 +97,15 @@ pub fn patterns() {
 
     println!("bar is now {}", bar);
 
-    // A range of values can be matched with ...
+    // A range of values can be matched with ..=
+    //
+    // Before the 2021 edition inclusive range patterns could also be written
+    // with three dots, e.g. 1 ... 10, which is now rejected in favour of ..=
+    // since it reads too much like the exclusive range 1..10.
     let baz = 10;
 
     match baz {
-        1 ... 10    => println!("{} is in the range 1 - 10 inclusive", baz),
+        1 ..= 10    => println!("{} is in the range 1 - 10 inclusive", baz),
         _           => println!("{} is outside of the range 1 - 10", baz),
     }
 
@@ -102,8 +113,8 @@ pub fn patterns() {
     let some_char = '€';
 
     match some_char {
-        'a' ... 'z' => println!("{} is a lowercase letter", some_char),
-        'A' ... 'Z' => println!("{} is an uppercase letter", some_char),
+        'a' ..= 'z' => println!("{} is a lowercase letter", some_char),
+        'A' ..= 'Z' => println!("{} is an uppercase letter", some_char),
         _           => println!("{} is not a letter in any case", some_char),
     }
 
@@ -112,7 +123,7 @@ pub fn patterns() {
     let another_char = 'c';
 
     match another_char {
-        lower @ 'a' ... 'z' => println!("{} is a lowercase letter", lower),
+        lower @ 'a' ..= 'z' => println!("{} is a lowercase letter", lower),
         other @ _           => println!("{} is not a letter in any case", other),
     }
 
@@ -134,7 +145,7 @@ pub fn patterns() {
     let z = 1;
 
     match z {
-        n @ 1 ... 5 | n @ 101 ... 105 => println!("{} in ranges 1-5 or 101-105", n),
+        n @ 1 ..= 5 | n @ 101 ..= 105 => println!("{} in ranges 1-5 or 101-105", n),
         n @ _                         => println!("{} not in any range", n),
     }
 
@@ -147,5 +158,120 @@ pub fn patterns() {
         _               => println!("Got something else"),
     }
 
+    // A larger example
+    // The expr module parses and evaluates arithmetic using all of the above.
+    // Its tokenizer matches characters with ranges and @, its parser matches
+    // on the next token, and its simplifier matches nested patterns such as
+    // (Op::Add, x, Num(0.0)) | (Op::Add, Num(0.0), x) to tidy a tree.
+    // It can be tried out with cargo run --bin calc.
+    match expr::parse("x * (2 + 3) + 0") {
+        Ok(Statement::Expr(e)) => println!("{} simplifies to {}", e, expr::simplify(&e)),
+        Ok(Statement::Assign(name, _)) => println!("Got an assignment to {}", name),
+        Err(e) => println!("Failed to parse: {}", e),
+    }
+
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn match_bindings_shadow_outer_bindings() {
+        let x = 1;
+        let c = 'c';
+
+        let inner = match c {
+            x => x,
+        };
+
+        assert_eq!(inner, 'c');
+        assert_eq!(x, 1);
+    }
+
+    #[test]
+    fn alternatives_and_ranges() {
+        let classify = |n: i32| match n {
+            1 | 2   => "one or two",
+            3..=10  => "three to ten",
+            _       => "other",
+        };
+
+        assert_eq!(classify(2), "one or two");
+        assert_eq!(classify(10), "three to ten");
+        assert_eq!(classify(11), "other");
+
+        let letter = |c: char| match c {
+            'a'..='z' => "lowercase",
+            'A'..='Z' => "uppercase",
+            _         => "not a letter",
+        };
+
+        assert_eq!(letter('c'), "lowercase");
+        assert_eq!(letter('C'), "uppercase");
+        assert_eq!(letter('€'), "not a letter");
+    }
+
+    #[test]
+    fn destructuring_structs() {
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        let point = Point { x: 1, y: 2 };
+
+        let Point { x: alias_x, y: alias_y } = point;
+        assert_eq!((alias_x, alias_y), (1, 2));
+
+        let Point { y, .. } = point;
+        assert_eq!(y, 2);
+    }
+
+    #[test]
+    fn ref_mut_modifies_the_matched_value() {
+        let mut bar = 2;
+
+        match bar {
+            ref mut x => *x += 1,
+        }
+
+        assert_eq!(bar, 3);
+    }
+
+    #[test]
+    fn at_bindings() {
+        struct Person {
+            name: Option<String>,
+        }
+
+        let person = Person { name: Some("Foo Bar".to_string()) };
+
+        let name = match person {
+            Person { name: Some(a) } => a,
+            _                        => String::new(),
+        };
+        assert_eq!(name, "Foo Bar");
+
+        let in_range = |z: i32| match z {
+            n @ 1..=5 | n @ 101..=105 => Some(n),
+            _                         => None,
+        };
+
+        assert_eq!(in_range(3), Some(3));
+        assert_eq!(in_range(103), Some(103));
+        assert_eq!(in_range(50), None);
+    }
+
+    #[test]
+    fn guards() {
+        let describe = |result: Result<i32, &'static str>| match result {
+            Ok(n) if n < 10 => "small",
+            Ok(_)           => "large",
+            Err(_)          => "error",
+        };
+
+        assert_eq!(describe(Ok(1)), "small");
+        assert_eq!(describe(Ok(10)), "large");
+        assert_eq!(describe(Err("oops")), "error");
+    }
+}
@@ -58,3 +58,43 @@ pub fn primitive_types() {
 
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    use std::mem;
+
+    #[test]
+    fn char_is_four_bytes() {
+        assert_eq!(mem::size_of::<char>(), 4);
+    }
+
+    #[test]
+    fn numeric_literals_default_to_i32_and_f64() {
+        let some_int = 42;
+        let some_float = 1.0;
+
+        assert_eq!(mem::size_of_val(&some_int), mem::size_of::<i32>());
+        assert_eq!(mem::size_of_val(&some_float), mem::size_of::<f64>());
+    }
+
+    #[test]
+    fn arrays_and_slices() {
+        let nums2 = [0; 20];
+        assert_eq!(nums2.len(), 20);
+        assert!(nums2.iter().all(|&n| n == 0));
+
+        let a = [0, 1, 2, 3, 4];
+        assert_eq!(a[..].len(), 5);
+        assert_eq!(&a[1..4], &[1, 2, 3]);
+    }
+
+    #[test]
+    fn tuples() {
+        let some_tuple = (1, "hello");
+        let (t1, t2) = some_tuple;
+
+        assert_eq!(t1, 1);
+        assert_eq!(t2, "hello");
+        assert_eq!(some_tuple.1, "hello");
+    }
+}
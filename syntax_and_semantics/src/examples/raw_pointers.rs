@@ -1,5 +1,7 @@
 #![allow(unused_variables)]
 
+use crate::linked_lists::RawQueue;
+
 pub fn raw_pointers() {
     println!("Raw pointers\n");
 
@@ -59,5 +61,59 @@ pub fn raw_pointers() {
     // harder to use incorrectly; for example, it requires that x is a pointer
     // (unlike transmute).
 
+    // Box, Rc and raw pointers together
+    // The linked_lists module builds the same list three ways. A Box can only
+    // link forward, an Rc<RefCell<_>> can link both ways at the cost of
+    // checks at run time, and raw pointers can point anywhere with the checks
+    // left to the programmer. RawQueue uses them to reach its last node.
+    let mut queue: RawQueue<i32> = (1..=3).collect();
+
+    queue.push_back(4);
+
+    println!("RawQueue after push_back(4) is {:?}", queue);
+
+    // Each keeps its unsafe code behind a safe API, so using one needs no
+    // unsafe block. Tests of code like this can be run under Miri, which
+    // reports any use of a pointer that breaks the borrowing rules.
+
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn dereferencing_raw_pointers() {
+        let x = 5;
+        let raw = &x as *const i32;
+
+        assert_eq!(unsafe { *raw }, 5);
+    }
+
+    #[test]
+    fn converting_back_to_references() {
+        let i: u32 = 1;
+        let p_imm: *const u32 = &i as *const u32;
+
+        let mut m: u32 = 2;
+        let p_mut: *mut u32 = &mut m;
+
+        unsafe {
+            let ref_imm: &u32 = &*p_imm;
+            let ref_mut: &mut u32 = &mut *p_mut;
+
+            assert_eq!(*ref_imm, 1);
+            *ref_mut += 1;
+        }
+
+        assert_eq!(m, 3);
+    }
+
+    #[test]
+    fn references_and_raw_pointers_share_a_representation() {
+        let x = 5;
+        let reference = &x;
+        let raw = reference as *const i32;
+
+        assert_eq!(raw as usize, reference as *const i32 as usize);
+    }
+}
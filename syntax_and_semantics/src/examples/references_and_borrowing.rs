@@ -1,3 +1,6 @@
+// sum_vec deliberately uses return, which clippy would flag as unneeded.
+#![allow(clippy::needless_return)]
+
 pub fn references_and_borrowing() {
     println!("References and Borrowing\n");
 
@@ -26,10 +29,40 @@ pub fn references_and_borrowing() {
 }
 
 // Folds over a vec to compute the sum.
+// Clippy would take a slice rather than a &Vec, and use sum rather than fold,
+// but the example borrows a Vec and shows fold.
+#[allow(clippy::ptr_arg, clippy::unnecessary_fold)]
 fn sum_vec(v: &Vec<i32>) -> i32 {
     return v.iter().fold(0, |a, &b| a + b);
 }
 
+#[allow(clippy::ptr_arg)]
 fn sum_two_vecs(v1: &Vec<i32>, v2: &Vec<i32>) -> i32 {
     sum_vec(v1) + sum_vec(v2)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn immutable_borrows_leave_the_vectors_usable() {
+        let v1 = vec![1, 2, 3];
+        let v2 = vec![1, 2, 3];
+
+        assert_eq!(sum_two_vecs(&v1, &v2), 12);
+        assert_eq!(v1.len() + v2.len(), 6);
+    }
+
+    #[test]
+    fn mutable_reference_modifies_the_original() {
+        let mut x = 5;
+
+        {
+            let y = &mut x;
+            *y += 1;
+        }
+
+        assert_eq!(x, 6);
+    }
+}
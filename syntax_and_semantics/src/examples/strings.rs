@@ -105,3 +105,60 @@ multiline
 
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn literals_with_and_without_whitespace() {
+        let with_whitespace = "a
+  b";
+        let without_whitespace = "Foo\
+            Bar\
+            Baz";
+
+        assert_eq!(with_whitespace, "a\n  b");
+        assert_eq!(without_whitespace, "FooBarBaz");
+    }
+
+    #[test]
+    fn strings_can_grow() {
+        let mut some_string = "Hello".to_string();
+        some_string.push_str(" World!");
+
+        assert_eq!(some_string, "Hello World!");
+    }
+
+    #[test]
+    fn strings_are_utf8_bytes() {
+        assert_eq!("Some".as_bytes(), &[83, 111, 109, 101]);
+
+        // One char may take several bytes.
+        assert_eq!("€".len(), 3);
+        assert_eq!("€".chars().count(), 1);
+    }
+
+    #[test]
+    fn slicing_on_char_boundaries() {
+        assert_eq!(&"Some characters"[0..5], "Some ");
+    }
+
+    #[test]
+    #[should_panic(expected = "char boundary")]
+    fn slicing_inside_a_char_panics() {
+        let s = "€uro".to_string();
+        let _ = &s[0..1];
+    }
+
+    #[test]
+    fn concatenation() {
+        let hello = "Hello ".to_string();
+        let world = "World!";
+
+        assert_eq!(hello + world, "Hello World!");
+
+        let a = "foo".to_string();
+        let b = "bar".to_string();
+
+        assert_eq!(a + &b, "foobar");
+    }
+}
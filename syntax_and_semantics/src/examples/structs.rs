@@ -71,3 +71,40 @@ pub fn structs() {
 
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[test]
+    fn mutable_binding_allows_field_assignment() {
+        let mut mut_point = Point { x: 1, y: 1 };
+
+        mut_point.x = 100;
+
+        assert_eq!((mut_point.x, mut_point.y), (100, 1));
+    }
+
+    #[test]
+    fn update_syntax_copies_remaining_fields() {
+        let some_point = Point { x: 12, y: 54 };
+        let another_point = Point { y: 12, ..some_point };
+
+        assert_eq!((another_point.x, another_point.y), (12, 12));
+    }
+
+    #[test]
+    fn tuple_structs_and_newtypes() {
+        struct Color(i32, i32, i32);
+        struct Inches(i32);
+
+        let red = Color(255, 0, 0);
+        assert_eq!((red.0, red.1, red.2), (255, 0, 0));
+
+        let Inches(length_value) = Inches(10);
+        assert_eq!(length_value, 10);
+    }
+}
@@ -40,18 +40,22 @@ pub fn trait_objects() {
     // Rust supports dynamic dispatch through a feature called 'trait objects'.
     // A trait object can be obtained from a pointer to a concrete type that
     // implements the type through casting or coercing as shown below.
+    //
+    // The dyn keyword marks a type as a trait object. Before the 2021 edition
+    // the trait name could be used on its own, e.g. &Foo rather than &dyn Foo,
+    // which made it easy to miss that dynamic dispatch was involved.
     // x is cast as Foo
     {
-      fn do_something(x: &Foo) -> String {
+      fn do_something(x: &dyn Foo) -> String {
           x.method()
       }
 
       let x = 5u8;
-      println!("result from casting x as Foo {}", do_something(&x as &Foo));
+      println!("result from casting x as Foo {}", do_something(&x as &dyn Foo));
     }
     // y is coerced into a Foo
     {
-      fn do_something(x: &Foo) -> String {
+      fn do_something(x: &dyn Foo) -> String {
           x.method()
       }
 
@@ -59,9 +63,11 @@ pub fn trait_objects() {
       println!("result from casting x as Foo {}", do_something(&y));
     }
 
-    // A function that takes a &Foo is not specialised to each of the types
+    // A function that takes a &dyn Foo is not specialised to each of the types
     // that implements Foo meaning less code is generated. However this comes
     // at the cost of requiring slower virtual function calls.
+    // benches/dispatch.rs measures both the speed and the size differences,
+    // along with a third option, dispatching with a match on an enum.
 
     // Note - not every trait can be used to make trait objects.
     // For example, Vectors cannot be used to make a trait object since they
@@ -77,3 +83,55 @@ pub fn trait_objects() {
 
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    trait Foo {
+        fn method(&self) -> String;
+    }
+
+    impl Foo for u8 {
+        fn method(&self) -> String {
+            format!("u8: {}", *self)
+        }
+    }
+
+    impl Foo for String {
+        fn method(&self) -> String {
+            format!("String: {}", *self)
+        }
+    }
+
+    fn static_dispatch<T: Foo>(x: T) -> String {
+        x.method()
+    }
+
+    fn dynamic_dispatch(x: &dyn Foo) -> String {
+        x.method()
+    }
+
+    #[test]
+    fn static_and_dynamic_dispatch_agree() {
+        assert_eq!(static_dispatch(5u8), "u8: 5");
+        assert_eq!(static_dispatch("Hello".to_string()), "String: Hello");
+
+        assert_eq!(dynamic_dispatch(&5u8 as &dyn Foo), "u8: 5");
+        assert_eq!(dynamic_dispatch(&"Hello".to_string()), "String: Hello");
+    }
+
+    #[test]
+    fn trait_objects_allow_mixed_collections() {
+        let objects: Vec<Box<dyn Foo>> = vec![Box::new(1u8), Box::new("a".to_string())];
+        let results: Vec<String> = objects.iter().map(|o| o.method()).collect();
+
+        assert_eq!(results, vec!["u8: 1", "String: a"]);
+    }
+
+    #[test]
+    fn trait_object_references_are_fat_pointers() {
+        use std::mem::size_of;
+
+        // A pointer to the data plus a pointer to the vtable.
+        assert_eq!(size_of::<&dyn Foo>(), 2 * size_of::<&u8>());
+    }
+}
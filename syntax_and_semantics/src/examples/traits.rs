@@ -1,4 +1,6 @@
-use std;
+// The 2015 edition needed use std; here before std::f64::consts::PI could be
+// referred to from inside this module. Since the 2018 edition external crates,
+// including std, are in scope everywhere.
 
 pub fn traits() {
     println!("Traits\n");
@@ -32,9 +34,13 @@ pub fn traits() {
 
     // For example implementing an IsLarger trait using Self references.
     trait IsLarger {
-        fn is_larger(&self, &Self) -> bool;
+        fn is_larger(&self, other: &Self) -> bool;
     }
 
+    // Older editions allowed the parameter name to be left out of a trait
+    // method declaration, e.g. fn is_larger(&self, &Self) -> bool. Names are
+    // now required, although _ can be used if the name isn't important.
+
     impl IsLarger for Circle {
         fn is_larger(&self, other: &Self) -> bool {
             self.area() > other.area()
@@ -133,3 +139,59 @@ pub fn traits() {
 
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    trait HasArea {
+        fn area(&self) -> f64;
+    }
+
+    trait IsLarger {
+        fn is_larger(&self, other: &Self) -> bool;
+    }
+
+    struct Circle {
+        radius: f64,
+    }
+
+    struct Square {
+        side_length: f64,
+    }
+
+    impl HasArea for Circle {
+        fn area(&self) -> f64 {
+            std::f64::consts::PI * (self.radius * self.radius)
+        }
+    }
+
+    impl HasArea for Square {
+        fn area(&self) -> f64 {
+            self.side_length * self.side_length
+        }
+    }
+
+    impl IsLarger for Circle {
+        fn is_larger(&self, other: &Self) -> bool {
+            self.area() > other.area()
+        }
+    }
+
+    fn area_of<T: HasArea>(shape: T) -> f64 {
+        shape.area()
+    }
+
+    #[test]
+    fn self_refers_to_the_implementing_type() {
+        let c = Circle { radius: 12.0 };
+        let d = Circle { radius: 24.0 };
+
+        assert!(d.is_larger(&c));
+        assert!(!c.is_larger(&d));
+    }
+
+    #[test]
+    fn trait_bounds_accept_any_implementation() {
+        assert_eq!(area_of(Square { side_length: 100.0 }), 10000.0);
+        assert!(area_of(Circle { radius: 100.0 }) > 10000.0);
+    }
+}
@@ -25,3 +25,32 @@ pub fn type_aliases() {
 
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn alias_is_the_same_type() {
+        type Name = String;
+
+        let name: Name = "Foo".to_string();
+        let string: String = name.clone();
+
+        assert_eq!(name, string);
+    }
+
+    #[test]
+    fn generic_alias_fixes_a_parameter() {
+        #[derive(Debug, PartialEq)]
+        enum ConcreteError {
+            Foo,
+        }
+
+        type Result<T> = std::result::Result<T, ConcreteError>;
+
+        let ok: Result<i32> = Ok(1);
+        let err: Result<i32> = Err(ConcreteError::Foo);
+
+        assert_eq!(ok, Ok(1));
+        assert_eq!(err, Err(ConcreteError::Foo));
+    }
+}
@@ -48,3 +48,34 @@ pub fn universal_function_call_syntax() {
 
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    trait Foo {
+        fn f(&self) -> &'static str;
+    }
+
+    trait Bar {
+        fn f(&self) -> &'static str;
+    }
+
+    struct Baz;
+
+    impl Foo for Baz {
+        fn f(&self) -> &'static str { "Foo" }
+    }
+
+    impl Bar for Baz {
+        fn f(&self) -> &'static str { "Bar" }
+    }
+
+    #[test]
+    fn trait_name_disambiguates_methods() {
+        let b = Baz;
+
+        assert_eq!(Foo::f(&b), "Foo");
+        assert_eq!(Bar::f(&b), "Bar");
+        assert_eq!(<Baz as Foo>::f(&b), "Foo");
+        assert_eq!(<Baz as Bar>::f(&b), "Bar");
+    }
+}
@@ -56,3 +56,27 @@ pub fn unsized_types() {
 
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    use std::mem::size_of;
+
+    struct Foo<T: ?Sized> {
+        f: T,
+    }
+
+    #[test]
+    fn pointers_to_unsized_types_carry_a_length() {
+        assert_eq!(size_of::<&[i32]>(), 2 * size_of::<&i32>());
+        assert_eq!(size_of::<&str>(), 2 * size_of::<&u8>());
+    }
+
+    #[test]
+    fn maybe_sized_parameter_accepts_unsized_types() {
+        let sized: &Foo<[i32; 3]> = &Foo { f: [1, 2, 3] };
+        let unsized_foo: &Foo<[i32]> = sized;
+
+        assert_eq!(unsized_foo.f.len(), 3);
+        assert_eq!(size_of::<&Foo<[i32]>>(), 2 * size_of::<&i32>());
+    }
+}
@@ -54,3 +54,33 @@ pub fn variable_bindings() {
 
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn let_takes_a_pattern() {
+        let (y, z) = (1, 2);
+        assert_eq!((y, z), (1, 2));
+    }
+
+    #[test]
+    fn shadowing_in_a_nested_block_leaves_outer_binding() {
+        let bar: u8 = 20;
+
+        {
+            assert_eq!(bar, 20);
+            let bar = 56;
+            assert_eq!(bar, 56);
+        }
+
+        assert_eq!(bar, 20);
+    }
+
+    #[test]
+    fn shadowing_in_the_same_scope_can_change_type() {
+        let bar: u8 = 20;
+        let bar = bar as i64 - 100;
+
+        assert_eq!(bar, -80);
+    }
+}
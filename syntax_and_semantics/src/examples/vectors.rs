@@ -53,3 +53,49 @@ pub fn vectors() {
     // iterated over again. The reference based approaches allow the vector to
     // be iterated over multiple times.
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn vec_macro_with_repeated_element() {
+        assert_eq!(vec![0; 2], vec![0, 0]);
+    }
+
+    #[test]
+    fn indexing_and_get() {
+        let v: Vec<i32> = (1..6).collect();
+
+        assert_eq!(v[2], 3);
+        assert_eq!(v.get(2), Some(&3));
+        assert_eq!(v.get(10), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds_index_panics() {
+        let v: Vec<i32> = (1..6).collect();
+        let index = v.len();
+
+        let _ = v[index];
+    }
+
+    // (&v).into_iter() is spelled out to show which IntoIterator impl a for
+    // loop over &v uses, even though v.iter() is the same.
+    #[test]
+    #[allow(clippy::into_iter_on_ref)]
+    fn references_allow_repeated_iteration() {
+        let mut v = vec![1, 2, 3];
+
+        let first: i32 = (&v).into_iter().sum();
+        let second: i32 = (&v).into_iter().sum();
+        assert_eq!(first, second);
+
+        for i in &mut v {
+            *i *= 10;
+        }
+        assert_eq!(v, vec![10, 20, 30]);
+
+        let owned: Vec<i32> = v.into_iter().collect();
+        assert_eq!(owned, vec![10, 20, 30]);
+    }
+}
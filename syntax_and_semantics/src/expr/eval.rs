@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use super::{Error, Expr, Op};
+
+// The values given to variables so far.
+pub type Vars = HashMap<String, f64>;
+
+pub fn eval(expr: &Expr, vars: &Vars) -> Result<f64, Error> {
+    match expr {
+        Expr::Num(n) => Ok(*n),
+        Expr::Var(name) => vars.get(name).copied().ok_or_else(|| Error::UnknownVariable(name.clone())),
+        Expr::Neg(operand) => Ok(-eval(operand, vars)?),
+        Expr::Binary(op, left, right) => apply(*op, eval(left, vars)?, eval(right, vars)?),
+    }
+}
+
+// Applies an operator to two values. Floats would give infinity or NaN for
+// a zero divisor, so that is matched first and turned into an error. The 0.0
+// pattern matches -0.0 as well, since float patterns compare with ==.
+pub(super) fn apply(op: Op, left: f64, right: f64) -> Result<f64, Error> {
+    match (op, left, right) {
+        (Op::Div | Op::Rem, _, 0.0) => Err(Error::DivisionByZero),
+        (Op::Add, l, r) => Ok(l + r),
+        (Op::Sub, l, r) => Ok(l - r),
+        (Op::Mul, l, r) => Ok(l * r),
+        (Op::Div, l, r) => Ok(l / r),
+        (Op::Rem, l, r) => Ok(l % r),
+        (Op::Pow, l, r) => Ok(l.powf(r)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::expr::{parse, Statement};
+
+    fn eval_with(source: &str, vars: &[(&str, f64)]) -> Result<f64, Error> {
+        let vars = vars.iter().map(|&(name, value)| (name.to_string(), value)).collect();
+
+        match parse(source)? {
+            Statement::Expr(expr) => eval(&expr, &vars),
+            Statement::Assign(..) => panic!("{} is an assignment", source),
+        }
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(eval_with("1 + 2 * 3", &[]), Ok(7.0));
+        assert_eq!(eval_with("(1 + 2) * 3", &[]), Ok(9.0));
+        assert_eq!(eval_with("7 % 4 - 10 / 4", &[]), Ok(0.5));
+        assert_eq!(eval_with("2 ^ 3 ^ 2", &[]), Ok(512.0));
+        assert_eq!(eval_with("-2 ^ 2", &[]), Ok(-4.0));
+        assert_eq!(eval_with("2 ^ -1", &[]), Ok(0.5));
+    }
+
+    #[test]
+    fn variables() {
+        assert_eq!(eval_with("x * x + y", &[("x", 3.0), ("y", 1.0)]), Ok(10.0));
+        assert_eq!(eval_with("x + z", &[("x", 3.0)]), Err(Error::UnknownVariable("z".to_string())));
+    }
+
+    #[test]
+    fn division_by_zero_is_an_error() {
+        assert_eq!(eval_with("1 / 0", &[]), Err(Error::DivisionByZero));
+        assert_eq!(eval_with("1 % (x - x)", &[("x", 2.0)]), Err(Error::DivisionByZero));
+        assert_eq!(eval_with("0 / 1", &[]), Ok(0.0));
+    }
+}
@@ -0,0 +1,205 @@
+// Arithmetic expressions, as a larger example of pattern matching than the
+// matching and patterns chapters have room for.
+//
+// Source text goes through three stages, each of them mostly a match.
+//  o token::tokenize splits the text into tokens, matching on characters
+//    with ranges and @ bindings.
+//  o parser::parse builds an Expr tree from the tokens by recursive descent,
+//    matching on the next token to decide what comes next.
+//  o eval evaluates the tree, and simplify folds constants in it, matching
+//    on the shape of the tree with nested patterns.
+//
+//  let statement = expr::parse("2 * (x + 1)")?;
+use std::fmt;
+
+mod eval;
+mod parser;
+pub mod repl;
+mod simplify;
+mod token;
+
+pub use self::eval::{eval, Vars};
+pub use self::parser::parse;
+pub use self::simplify::simplify;
+pub use self::token::{tokenize, Token, TokenKind};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Pow,
+}
+
+impl Op {
+    // How tightly the operator binds, higher binding tighter.
+    pub fn precedence(self) -> u8 {
+        match self {
+            Op::Add | Op::Sub => 1,
+            Op::Mul | Op::Div | Op::Rem => 2,
+            Op::Pow => 3,
+        }
+    }
+
+    pub fn symbol(self) -> char {
+        match self {
+            Op::Add => '+',
+            Op::Sub => '-',
+            Op::Mul => '*',
+            Op::Div => '/',
+            Op::Rem => '%',
+            Op::Pow => '^',
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Num(f64),
+    Var(String),
+    Neg(Box<Expr>),
+    Binary(Op, Box<Expr>, Box<Expr>),
+}
+
+// A line of input, which either gives a variable a value or is an expression
+// to evaluate.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Statement {
+    Assign(String, Expr),
+    Expr(Expr),
+}
+
+// Columns count characters from 1, so they can be used to point at the
+// problem under the input.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    UnexpectedChar { found: char, column: usize },
+    InvalidNumber { text: String, column: usize },
+    UnexpectedToken { found: TokenKind, expected: &'static str, column: usize },
+    UnexpectedEnd { expected: &'static str, column: usize },
+    UnknownVariable(String),
+    DivisionByZero,
+}
+
+impl Error {
+    // Where the error is in the input, for errors found while parsing.
+    pub fn column(&self) -> Option<usize> {
+        match *self {
+            Error::UnexpectedChar { column, .. }
+            | Error::InvalidNumber { column, .. }
+            | Error::UnexpectedToken { column, .. }
+            | Error::UnexpectedEnd { column, .. } => Some(column),
+            Error::UnknownVariable(_) | Error::DivisionByZero => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::UnexpectedChar { found, column } => write!(f, "unexpected {:?} at column {}", found, column),
+            Error::InvalidNumber { text, column } => write!(f, "invalid number {} at column {}", text, column),
+            Error::UnexpectedToken { found, expected, column } => {
+                write!(f, "expected {} but found {} at column {}", expected, found, column)
+            }
+            Error::UnexpectedEnd { expected, column } => {
+                write!(f, "expected {} at column {}, but the input ended", expected, column)
+            }
+            Error::UnknownVariable(name) => write!(f, "{} has no value", name),
+            Error::DivisionByZero => write!(f, "division by zero"),
+        }
+    }
+}
+
+// Writes the expression back out with as few brackets as its structure
+// allows, so parsing the result gives the same tree.
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expr::Num(n) => write!(f, "{}", n),
+            Expr::Var(name) => write!(f, "{}", name),
+            Expr::Neg(operand) => match **operand {
+                Expr::Binary(..) => write!(f, "-({})", operand),
+                _ => write!(f, "-{}", operand),
+            },
+            Expr::Binary(op, left, right) => {
+                // ^ groups to the right and the others to the left, so which
+                // side needs brackets at equal precedence differs.
+                let (left_min, right_min) = match op {
+                    Op::Pow => (op.precedence() + 1, op.precedence()),
+                    _ => (op.precedence(), op.precedence() + 1),
+                };
+
+                write_operand(f, left, left_min)?;
+                write!(f, " {} ", op.symbol())?;
+                write_operand(f, right, right_min)
+            }
+        }
+    }
+}
+
+// Brackets the operand if it binds less tightly than min. A negation binds
+// less tightly than ^, as -2 ^ 2 is -(2 ^ 2).
+fn write_operand(f: &mut fmt::Formatter, operand: &Expr, min: u8) -> fmt::Result {
+    let precedence = match operand {
+        Expr::Binary(op, ..) => op.precedence(),
+        Expr::Neg(_) => Op::Mul.precedence(),
+        Expr::Num(n) if *n < 0.0 => Op::Mul.precedence(),
+        Expr::Num(_) | Expr::Var(_) => u8::MAX,
+    };
+
+    if precedence < min {
+        write!(f, "({})", operand)
+    } else {
+        write!(f, "{}", operand)
+    }
+}
+
+impl fmt::Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Statement::Assign(name, expr) => write!(f, "{} = {}", name, expr),
+            Statement::Expr(expr) => write!(f, "{}", expr),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(source: &str) -> String {
+        parse(source).unwrap().to_string()
+    }
+
+    #[test]
+    fn display_uses_only_the_brackets_needed() {
+        assert_eq!(round_trip("1+2*3"), "1 + 2 * 3");
+        assert_eq!(round_trip("(1+2)*3"), "(1 + 2) * 3");
+        assert_eq!(round_trip("1-(2-3)"), "1 - (2 - 3)");
+        assert_eq!(round_trip("(1-2)-3"), "1 - 2 - 3");
+        assert_eq!(round_trip("2^3^2"), "2 ^ 3 ^ 2");
+        assert_eq!(round_trip("(2^3)^2"), "(2 ^ 3) ^ 2");
+        assert_eq!(round_trip("-(x+1)"), "-(x + 1)");
+        assert_eq!(round_trip("(-2)^2"), "(-2) ^ 2");
+        assert_eq!(round_trip("y = -x"), "y = -x");
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for source in ["1 + 2 * 3", "(1 + 2) * 3", "2 ^ -1", "-2 ^ 2", "a % (b / c)", "(-2) ^ 2"] {
+            let expr = parse(source).unwrap();
+
+            assert_eq!(parse(&expr.to_string()).unwrap(), expr, "{}", source);
+        }
+    }
+
+    #[test]
+    fn only_parse_errors_have_columns() {
+        assert_eq!(Error::UnexpectedChar { found: '$', column: 3 }.column(), Some(3));
+        assert_eq!(Error::DivisionByZero.column(), None);
+        assert_eq!(Error::UnexpectedChar { found: '$', column: 3 }.to_string(), "unexpected '$' at column 3");
+    }
+}
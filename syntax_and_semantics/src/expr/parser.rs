@@ -0,0 +1,197 @@
+use super::token::{tokenize, Token, TokenKind};
+use super::{Error, Expr, Op, Statement};
+
+// Parses by recursive descent, with a function for each level of precedence.
+//
+//  statement  = IDENT "=" expr | expr
+//  expr       = term (("+" | "-") term)*
+//  term       = unary (("*" | "/" | "%") unary)*
+//  unary      = "-" unary | power
+//  power      = atom ("^" unary)?
+//  atom       = NUMBER | IDENT | "(" expr ")"
+//
+// power takes a unary on its right, so ^ groups to the right and 2 ^ -1 is
+// allowed, while -2 ^ 2 is -(2 ^ 2) as in maths.
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    // The column just past the end of the input, for errors at the end.
+    end: usize,
+}
+
+pub fn parse(source: &str) -> Result<Statement, Error> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser { tokens, position: 0, end: source.chars().count() + 1 };
+
+    let statement = parser.statement()?;
+
+    match parser.peek() {
+        None => Ok(statement),
+        Some(token) => Err(Error::UnexpectedToken {
+            found: token.kind.clone(),
+            expected: "an operator",
+            column: token.column,
+        }),
+    }
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn peek_kind(&self) -> Option<&TokenKind> {
+        self.peek().map(|t| &t.kind)
+    }
+
+    fn next(&mut self, expected: &'static str) -> Result<Token, Error> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or(Error::UnexpectedEnd { expected, column: self.end })?;
+
+        self.position += 1;
+
+        Ok(token)
+    }
+
+    fn statement(&mut self) -> Result<Statement, Error> {
+        // An assignment is only recognised by looking two tokens ahead.
+        if let [Token { kind: TokenKind::Ident(name), .. }, Token { kind: TokenKind::Equals, .. }, ..] =
+            &self.tokens[self.position..]
+        {
+            let name = name.clone();
+            self.position += 2;
+
+            return Ok(Statement::Assign(name, self.expr()?));
+        }
+
+        Ok(Statement::Expr(self.expr()?))
+    }
+
+    fn expr(&mut self) -> Result<Expr, Error> {
+        let mut left = self.term()?;
+
+        while let Some(op) = match self.peek_kind() {
+            Some(TokenKind::Plus) => Some(Op::Add),
+            Some(TokenKind::Minus) => Some(Op::Sub),
+            _ => None,
+        } {
+            self.position += 1;
+            left = Expr::Binary(op, Box::new(left), Box::new(self.term()?));
+        }
+
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<Expr, Error> {
+        let mut left = self.unary()?;
+
+        while let Some(op) = match self.peek_kind() {
+            Some(TokenKind::Star) => Some(Op::Mul),
+            Some(TokenKind::Slash) => Some(Op::Div),
+            Some(TokenKind::Percent) => Some(Op::Rem),
+            _ => None,
+        } {
+            self.position += 1;
+            left = Expr::Binary(op, Box::new(left), Box::new(self.unary()?));
+        }
+
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, Error> {
+        if let Some(TokenKind::Minus) = self.peek_kind() {
+            self.position += 1;
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+
+        self.power()
+    }
+
+    fn power(&mut self) -> Result<Expr, Error> {
+        let base = self.atom()?;
+
+        if let Some(TokenKind::Caret) = self.peek_kind() {
+            self.position += 1;
+            return Ok(Expr::Binary(Op::Pow, Box::new(base), Box::new(self.unary()?)));
+        }
+
+        Ok(base)
+    }
+
+    fn atom(&mut self) -> Result<Expr, Error> {
+        const EXPECTED: &str = "a number, a name or (";
+
+        let Token { kind, column } = self.next(EXPECTED)?;
+
+        match kind {
+            TokenKind::Num(n) => Ok(Expr::Num(n)),
+            TokenKind::Ident(name) => Ok(Expr::Var(name)),
+            TokenKind::LParen => {
+                let inner = self.expr()?;
+
+                match self.next(")")? {
+                    Token { kind: TokenKind::RParen, .. } => Ok(inner),
+                    Token { kind, column } => Err(Error::UnexpectedToken { found: kind, expected: ")", column }),
+                }
+            }
+            found => Err(Error::UnexpectedToken { found, expected: EXPECTED, column }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn num(n: f64) -> Box<Expr> {
+        Box::new(Expr::Num(n))
+    }
+
+    fn var(name: &str) -> Box<Expr> {
+        Box::new(Expr::Var(name.to_string()))
+    }
+
+    fn expr(source: &str) -> Expr {
+        match parse(source).unwrap() {
+            Statement::Expr(expr) => expr,
+            other => panic!("{:?} is not an expression", other),
+        }
+    }
+
+    #[test]
+    fn precedence_and_grouping() {
+        use Op::*;
+
+        assert_eq!(expr("1 + 2 * 3"), Expr::Binary(Add, num(1.0), Box::new(Expr::Binary(Mul, num(2.0), num(3.0)))));
+        assert_eq!(expr("1 - 2 - 3"), Expr::Binary(Sub, Box::new(Expr::Binary(Sub, num(1.0), num(2.0))), num(3.0)));
+        assert_eq!(expr("2 ^ 3 ^ 2"), Expr::Binary(Pow, num(2.0), Box::new(Expr::Binary(Pow, num(3.0), num(2.0)))));
+        assert_eq!(expr("(x)"), Expr::Var("x".to_string()));
+    }
+
+    #[test]
+    fn negation_binds_looser_than_power() {
+        assert_eq!(expr("-2 ^ 2"), Expr::Neg(Box::new(Expr::Binary(Op::Pow, num(2.0), num(2.0)))));
+        assert_eq!(expr("2 ^ -x"), Expr::Binary(Op::Pow, num(2.0), Box::new(Expr::Neg(var("x")))));
+        assert_eq!(expr("--1"), Expr::Neg(Box::new(Expr::Neg(num(1.0)))));
+    }
+
+    #[test]
+    fn assignments() {
+        assert_eq!(parse("x = 1").unwrap(), Statement::Assign("x".to_string(), Expr::Num(1.0)));
+        assert_eq!(parse("x").unwrap(), Statement::Expr(Expr::Var("x".to_string())));
+    }
+
+    #[test]
+    fn errors_point_at_the_problem() {
+        assert_eq!(parse("1 +").unwrap_err().to_string(), "expected a number, a name or ( at column 4, but the input ended");
+        assert_eq!(parse("(1 + 2").unwrap_err(), Error::UnexpectedEnd { expected: ")", column: 7 });
+        assert_eq!(parse("1 2").unwrap_err().to_string(), "expected an operator but found 2 at column 3");
+        assert_eq!(parse("* 2").unwrap_err().column(), Some(1));
+        assert_eq!(parse("(1 = 2)").unwrap_err().to_string(), "expected ) but found = at column 4");
+        assert_eq!(parse("1 = 2").unwrap_err().column(), Some(3));
+        assert_eq!(parse("").unwrap_err().column(), Some(1));
+    }
+}
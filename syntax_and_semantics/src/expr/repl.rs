@@ -0,0 +1,145 @@
+use std::io::{self, BufRead, Write};
+
+use super::{eval, parse, simplify, Error, Statement, Vars};
+
+const PROMPT: &str = "> ";
+
+const HELP: &str = "\
+Type an expression to evaluate it, or name = expression to give a variable a value.
+  + - * / % ^ and brackets work as usual, with ^ grouping to the right.
+  :simplify EXPR  show EXPR with its constants folded, without evaluating it
+  :vars           list the variables and their values
+  :help           show this message
+  :quit           stop, as does the end of the input";
+
+// Reads lines and prints what each evaluates to, until :quit or the end of
+// the input. Variables keep their values from one line to the next.
+pub fn run<R: BufRead, W: Write>(mut input: R, output: &mut W) -> io::Result<()> {
+    let mut vars = Vars::new();
+
+    writeln!(output, "Type :help for help.")?;
+
+    loop {
+        write!(output, "{}", PROMPT)?;
+        output.flush()?;
+
+        let mut line = String::new();
+
+        if input.read_line(&mut line)? == 0 {
+            writeln!(output)?;
+            return Ok(());
+        }
+
+        // Only the line ending is trimmed, so columns still match what was
+        // typed.
+        let line = line.trim_end_matches(['\n', '\r']);
+
+        let (command, rest) = match line.trim_start().strip_prefix(':') {
+            Some(command) => command.split_once(' ').unwrap_or((command, "")),
+            None => ("", line),
+        };
+
+        match command.trim() {
+            "" if rest.trim().is_empty() => {}
+            "" => match execute(rest, &mut vars) {
+                Ok(reply) => writeln!(output, "{}", reply)?,
+                Err(e) => report(output, line, rest, &e)?,
+            },
+            "simplify" => match parse(rest) {
+                Ok(Statement::Expr(expr)) => writeln!(output, "{}", simplify(&expr))?,
+                Ok(Statement::Assign(name, expr)) => writeln!(output, "{} = {}", name, simplify(&expr))?,
+                Err(e) => report(output, line, rest, &e)?,
+            },
+            "vars" if vars.is_empty() => writeln!(output, "No variables have values yet.")?,
+            "vars" => {
+                let mut names: Vec<_> = vars.iter().collect();
+                names.sort_by(|a, b| a.0.cmp(b.0));
+
+                for (name, value) in names {
+                    writeln!(output, "{} = {}", name, value)?;
+                }
+            }
+            "help" => writeln!(output, "{}", HELP)?,
+            "quit" | "q" => return Ok(()),
+            other => writeln!(output, "Unknown command :{}, type :help for the commands.", other)?,
+        }
+    }
+}
+
+// Runs one statement, returning what to print.
+fn execute(source: &str, vars: &mut Vars) -> Result<String, Error> {
+    match parse(source)? {
+        Statement::Assign(name, expr) => {
+            let value = eval(&expr, vars)?;
+            vars.insert(name.clone(), value);
+
+            Ok(format!("{} = {}", name, value))
+        }
+        Statement::Expr(expr) => Ok(eval(&expr, vars)?.to_string()),
+    }
+}
+
+// Prints the error, with a caret under the problem if it has a column. The
+// column is within part, which is at the end of line.
+fn report<W: Write>(output: &mut W, line: &str, part: &str, error: &Error) -> io::Result<()> {
+    if let Some(column) = error.column() {
+        let offset = line.chars().count() - part.chars().count();
+
+        writeln!(output, "{}^", " ".repeat(PROMPT.len() + offset + column - 1))?;
+    }
+
+    writeln!(output, "error: {}", error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(input: &str) -> String {
+        let mut output = Vec::new();
+
+        run(input.as_bytes(), &mut output).unwrap();
+
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn variables_are_remembered() {
+        let output = session("x = 2\ny = x ^ 10\ny - 24\n:vars\n");
+
+        assert!(output.contains("x = 2\n"), "{}", output);
+        assert!(output.contains("y = 1024\n"), "{}", output);
+        assert!(output.contains("> 1000\n"), "{}", output);
+        assert!(output.ends_with("> x = 2\ny = 1024\n> \n"), "{}", output);
+    }
+
+    #[test]
+    fn errors_point_at_the_column() {
+        let output = session("1 + * 2\n:simplify (x\n");
+
+        // The input isn't echoed, so each caret line follows a prompt here,
+        // where in a terminal it would be under the typed line.
+        assert!(output.contains(">       ^\nerror: expected a number, a name or ( but found * at column 5\n"), "{}", output);
+        assert!(output.contains(">               ^\nerror: expected ) at column 3, but the input ended\n"), "{}", output);
+    }
+
+    #[test]
+    fn commands() {
+        let output = session(":simplify x * (1 + 1) + 0\n:vars\n:nope\n:quit\n1 + 1\n");
+
+        assert!(output.contains("x * 2\n"), "{}", output);
+        assert!(output.contains("No variables have values yet."), "{}", output);
+        assert!(output.contains("Unknown command :nope"), "{}", output);
+
+        // Nothing after :quit is run.
+        assert!(!output.contains("> 2\n"), "{}", output);
+    }
+
+    #[test]
+    fn evaluation_errors() {
+        let output = session("1 / 0\nz + 1\n");
+
+        assert!(output.contains("error: division by zero\n"), "{}", output);
+        assert!(output.contains("error: z has no value\n"), "{}", output);
+    }
+}
@@ -0,0 +1,109 @@
+use super::eval::apply;
+use super::{Expr, Op};
+
+// Simplifies an expression without knowing the values of its variables, by
+// folding constants and applying identities such as x * 1 = x.
+//
+// The operands are simplified first, so each rule only has to look one level
+// down. Rules that would hide an error are left alone, so 1 / 0 and 0 * (1 / 0)
+// still fail when evaluated.
+pub fn simplify(expr: &Expr) -> Expr {
+    match expr {
+        Expr::Num(_) | Expr::Var(_) => expr.clone(),
+        Expr::Neg(operand) => negate(simplify(operand)),
+        Expr::Binary(op, left, right) => binary(*op, simplify(left), simplify(right)),
+    }
+}
+
+fn negate(operand: Expr) -> Expr {
+    match operand {
+        Expr::Num(n) => Expr::Num(-n),
+        Expr::Neg(inner) => *inner,
+        other => Expr::Neg(Box::new(other)),
+    }
+}
+
+fn binary(op: Op, left: Expr, right: Expr) -> Expr {
+    use Expr::{Neg, Num, Var};
+
+    match (op, left, right) {
+        // Two constants can be worked out now, unless that would fail.
+        (op, Num(l), Num(r)) => match apply(op, l, r) {
+            Ok(n) => Num(n),
+            Err(_) => Expr::Binary(op, Box::new(Num(l)), Box::new(Num(r))),
+        },
+
+        // Literals can be matched inside the operands, and x is bound on both
+        // sides of the |, so x + 0 and 0 + x share a rule.
+        (Op::Add, x, Num(0.0)) | (Op::Add, Num(0.0), x) => x,
+        (Op::Sub, x, Num(0.0)) => x,
+        (Op::Sub, Num(0.0), x) => negate(x),
+        (Op::Mul, x, Num(1.0)) | (Op::Mul, Num(1.0), x) => x,
+        (Op::Div | Op::Pow, x, Num(1.0)) => x,
+
+        // x * 0 and x ^ 0 drop x, so they only apply when x can't fail, which
+        // a variable can't once it has a value.
+        (Op::Pow, Var(_), Num(0.0)) => Num(1.0),
+        (Op::Mul, Var(_), Num(0.0)) | (Op::Mul, Num(0.0), Var(_)) => Num(0.0),
+
+        // Nested patterns look inside both operands at once.
+        (Op::Sub, Var(a), Var(b)) if a == b => Num(0.0),
+        (Op::Add, x, Neg(y)) => binary(Op::Sub, x, *y),
+        (Op::Sub, x, Neg(y)) => binary(Op::Add, x, *y),
+        (Op::Mul, Neg(x), Neg(y)) => binary(Op::Mul, *x, *y),
+
+        (op, left, right) => Expr::Binary(op, Box::new(left), Box::new(right)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::expr::{parse, Statement};
+
+    fn simplified(source: &str) -> String {
+        match parse(source).unwrap() {
+            Statement::Expr(expr) => simplify(&expr).to_string(),
+            Statement::Assign(..) => panic!("{} is an assignment", source),
+        }
+    }
+
+    #[test]
+    fn constants_are_folded() {
+        assert_eq!(simplified("1 + 2 * 3"), "7");
+        assert_eq!(simplified("x * (2 + 3)"), "x * 5");
+        assert_eq!(simplified("-(2 ^ 3)"), "-8");
+        assert_eq!(simplified("(1 + 1) * x - 4 / 2"), "2 * x - 2");
+    }
+
+    #[test]
+    fn identities() {
+        assert_eq!(simplified("x + 0"), "x");
+        assert_eq!(simplified("0 + x"), "x");
+        assert_eq!(simplified("0 - x"), "-x");
+        assert_eq!(simplified("1 * x / 1"), "x");
+        assert_eq!(simplified("x ^ 1"), "x");
+        assert_eq!(simplified("x ^ (1 - 1)"), "1");
+        assert_eq!(simplified("x * 0"), "0");
+        assert_eq!(simplified("x - x"), "0");
+        assert_eq!(simplified("x - y"), "x - y");
+    }
+
+    #[test]
+    fn negations() {
+        assert_eq!(simplified("--x"), "x");
+        assert_eq!(simplified("x + -y"), "x - y");
+        assert_eq!(simplified("x - -y"), "x + y");
+        assert_eq!(simplified("-x * -y"), "x * y");
+        assert_eq!(simplified("x - -(1 - 1)"), "x");
+    }
+
+    #[test]
+    fn errors_are_not_hidden() {
+        assert_eq!(simplified("1 / 0"), "1 / 0");
+        assert_eq!(simplified("(1 / 0) * 0"), "1 / 0 * 0");
+        assert_eq!(simplified("(1 / 0) ^ 0"), "(1 / 0) ^ 0");
+        assert_eq!(simplified("x / x"), "x / x");
+    }
+}
@@ -0,0 +1,154 @@
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+
+use super::Error;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+    Num(f64),
+    Ident(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Percent,
+    Caret,
+    LParen,
+    RParen,
+    Equals,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    // The column of the token's first character, counting from 1.
+    pub column: usize,
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TokenKind::Num(n) => write!(f, "{}", n),
+            TokenKind::Ident(name) => write!(f, "{}", name),
+            TokenKind::Plus => write!(f, "+"),
+            TokenKind::Minus => write!(f, "-"),
+            TokenKind::Star => write!(f, "*"),
+            TokenKind::Slash => write!(f, "/"),
+            TokenKind::Percent => write!(f, "%"),
+            TokenKind::Caret => write!(f, "^"),
+            TokenKind::LParen => write!(f, "("),
+            TokenKind::RParen => write!(f, ")"),
+            TokenKind::Equals => write!(f, "="),
+        }
+    }
+}
+
+struct Tokenizer<'a> {
+    chars: Peekable<Chars<'a>>,
+    column: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        self.column += 1;
+
+        Some(c)
+    }
+
+    // Takes characters for as long as they match.
+    fn take_while<P: Fn(char) -> bool>(&mut self, first: char, matches: P) -> String {
+        let mut text = first.to_string();
+
+        while let Some(&c) = self.chars.peek() {
+            if !matches(c) {
+                break;
+            }
+            text.push(c);
+            self.bump();
+        }
+
+        text
+    }
+}
+
+pub fn tokenize(source: &str) -> Result<Vec<Token>, Error> {
+    let mut tokenizer = Tokenizer { chars: source.chars().peekable(), column: 0 };
+    let mut tokens = Vec::new();
+
+    while let Some(c) = tokenizer.bump() {
+        let column = tokenizer.column;
+
+        let kind = match c {
+            c if c.is_whitespace() => continue,
+            '+' => TokenKind::Plus,
+            '-' => TokenKind::Minus,
+            '*' => TokenKind::Star,
+            '/' => TokenKind::Slash,
+            '%' => TokenKind::Percent,
+            '^' => TokenKind::Caret,
+            '(' => TokenKind::LParen,
+            ')' => TokenKind::RParen,
+            '=' => TokenKind::Equals,
+            digit @ ('0'..='9' | '.') => {
+                let text = tokenizer.take_while(digit, |c| c.is_ascii_digit() || c == '.');
+
+                match text.parse() {
+                    Ok(n) => TokenKind::Num(n),
+                    Err(_) => return Err(Error::InvalidNumber { text, column }),
+                }
+            }
+            letter @ ('a'..='z' | 'A'..='Z' | '_') => {
+                TokenKind::Ident(tokenizer.take_while(letter, |c| c.is_ascii_alphanumeric() || c == '_'))
+            }
+            found => return Err(Error::UnexpectedChar { found, column }),
+        };
+
+        tokens.push(Token { kind, column });
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(source: &str) -> Vec<TokenKind> {
+        tokenize(source).unwrap().into_iter().map(|t| t.kind).collect()
+    }
+
+    #[test]
+    fn splits_into_tokens() {
+        use TokenKind::*;
+
+        assert_eq!(kinds("x_1 = (2.5+y)*3"), vec![
+            Ident("x_1".to_string()),
+            Equals,
+            LParen,
+            Num(2.5),
+            Plus,
+            Ident("y".to_string()),
+            RParen,
+            Star,
+            Num(3.0),
+        ]);
+        assert_eq!(kinds("-1 % 2 ^ 3 / 4"), vec![Minus, Num(1.0), Percent, Num(2.0), Caret, Num(3.0), Slash, Num(4.0)]);
+        assert!(kinds("  \t").is_empty());
+    }
+
+    #[test]
+    fn tokens_know_their_column() {
+        let columns: Vec<usize> = tokenize(" 12 +  x").unwrap().iter().map(|t| t.column).collect();
+
+        assert_eq!(columns, vec![2, 5, 8]);
+    }
+
+    #[test]
+    fn bad_input_is_reported_where_it_is() {
+        assert_eq!(tokenize("1 + $"), Err(Error::UnexpectedChar { found: '$', column: 5 }));
+        assert_eq!(tokenize("€ + 1"), Err(Error::UnexpectedChar { found: '€', column: 1 }));
+        assert_eq!(tokenize("2 * 1.2.3"), Err(Error::InvalidNumber { text: "1.2.3".to_string(), column: 5 }));
+    }
+}
@@ -1 +1,12 @@
+pub mod adaptors;
+pub mod collections;
+pub mod combinators;
+pub mod dispatch;
+pub mod docs;
+pub mod events;
 pub mod examples;
+pub mod expr;
+pub mod linked_lists;
+pub mod memo;
+pub mod thread_pool;
+pub mod tutor;
@@ -0,0 +1,348 @@
+use std::cell::{Ref, RefCell, RefMut};
+use std::fmt;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::rc::{Rc, Weak};
+
+use super::List;
+
+// A doubly linked list, which can be added to and taken from at either end.
+//
+// Each node is pointed at by the node before it and the node after it, so it
+// can't have a single owner. Instead nodes are shared through Rc, and changed
+// through RefCell since what an Rc shares can't be borrowed mutably. The
+// links forward are strong and the links back are Weak. If both were strong,
+// neighbours would keep each other alive and no node would ever be freed.
+pub struct DoublyLinkedList<T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+}
+
+type Link<T> = Option<Rc<RefCell<Node<T>>>>;
+
+struct Node<T> {
+    value: T,
+    next: Link<T>,
+    prev: Option<Weak<RefCell<Node<T>>>>,
+}
+
+impl<T> Node<T> {
+    fn new(value: T) -> Rc<RefCell<Node<T>>> {
+        Rc::new(RefCell::new(Node { value, next: None, prev: None }))
+    }
+
+    // Takes the value out of a node nothing else points at any more.
+    fn into_value(node: Rc<RefCell<Node<T>>>) -> T {
+        match Rc::try_unwrap(node) {
+            Ok(node) => node.into_inner().value,
+            Err(_) => panic!("an unlinked node is still shared"),
+        }
+    }
+}
+
+impl<T> DoublyLinkedList<T> {
+    pub fn new() -> DoublyLinkedList<T> {
+        DoublyLinkedList { head: None, tail: None, len: 0 }
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        let node = Node::new(value);
+
+        match self.head.take() {
+            Some(old) => {
+                old.borrow_mut().prev = Some(Rc::downgrade(&node));
+                node.borrow_mut().next = Some(old);
+            }
+            None => self.tail = Some(Rc::clone(&node)),
+        }
+
+        self.head = Some(node);
+        self.len += 1;
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        let node = Node::new(value);
+
+        match self.tail.take() {
+            Some(old) => {
+                node.borrow_mut().prev = Some(Rc::downgrade(&old));
+                old.borrow_mut().next = Some(Rc::clone(&node));
+            }
+            None => self.head = Some(Rc::clone(&node)),
+        }
+
+        self.tail = Some(node);
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let old = self.head.take()?;
+        let next = old.borrow_mut().next.take();
+
+        match next {
+            Some(next) => {
+                next.borrow_mut().prev = None;
+                self.head = Some(next);
+            }
+            None => self.tail = None,
+        }
+
+        self.len -= 1;
+
+        Some(Node::into_value(old))
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        let old = self.tail.take()?;
+        let prev = old.borrow_mut().prev.take().and_then(|prev| prev.upgrade());
+
+        match prev {
+            Some(prev) => {
+                prev.borrow_mut().next = None;
+                self.tail = Some(prev);
+            }
+            None => self.head = None,
+        }
+
+        self.len -= 1;
+
+        Some(Node::into_value(old))
+    }
+
+    // A plain &T can't be handed out, as the value is inside a RefCell. The
+    // Ref that's returned instead keeps the node borrowed until it's dropped.
+    pub fn peek_front(&self) -> Option<Ref<'_, T>> {
+        self.head.as_ref().map(|node| Ref::map(node.borrow(), |node| &node.value))
+    }
+
+    pub fn peek_back(&self) -> Option<Ref<'_, T>> {
+        self.tail.as_ref().map(|node| Ref::map(node.borrow(), |node| &node.value))
+    }
+
+    pub fn peek_front_mut(&mut self) -> Option<RefMut<'_, T>> {
+        self.head.as_ref().map(|node| RefMut::map(node.borrow_mut(), |node| &mut node.value))
+    }
+
+    pub fn peek_back_mut(&mut self) -> Option<RefMut<'_, T>> {
+        self.tail.as_ref().map(|node| RefMut::map(node.borrow_mut(), |node| &mut node.value))
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Iterates over copies of the values, front first. A borrowing iterator
+    // isn't possible, as each value can only be reached through a Ref on its
+    // node, which the iterator would have to keep alive after moving on.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { nodes: self.nodes() }
+    }
+
+    fn nodes(&self) -> Nodes<'_, T> {
+        Nodes { next: self.head.clone(), list: PhantomData }
+    }
+}
+
+// Popping each node in turn unlinks it from the next, so dropping a long list
+// doesn't recurse through it.
+impl<T> Drop for DoublyLinkedList<T> {
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+impl<T> List<T> for DoublyLinkedList<T> {
+    fn push_front(&mut self, value: T) {
+        DoublyLinkedList::push_front(self, value);
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        DoublyLinkedList::pop_front(self)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.nodes().any(|node| node.borrow().value == *value)
+    }
+}
+
+impl<T> Default for DoublyLinkedList<T> {
+    fn default() -> DoublyLinkedList<T> {
+        DoublyLinkedList::new()
+    }
+}
+
+// Walks the nodes, holding an Rc to the next so it stays alive meanwhile. It
+// also borrows the list, even though it doesn't use it, so that nothing can be
+// popped while the walk still shares the node.
+struct Nodes<'a, T> {
+    next: Link<T>,
+    list: PhantomData<&'a DoublyLinkedList<T>>,
+}
+
+impl<T> Iterator for Nodes<'_, T> {
+    type Item = Rc<RefCell<Node<T>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next.take()?;
+        self.next = node.borrow().next.clone();
+
+        Some(node)
+    }
+}
+
+pub struct Iter<'a, T> {
+    nodes: Nodes<'a, T>,
+}
+
+impl<T: Clone> Iterator for Iter<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.nodes.next().map(|node| node.borrow().value.clone())
+    }
+}
+
+// Takes values from either end, so it can be reversed.
+pub struct IntoIter<T> {
+    list: DoublyLinkedList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.list.pop_back()
+    }
+}
+
+impl<T> IntoIterator for DoublyLinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { list: self }
+    }
+}
+
+impl<T> FromIterator<T> for DoublyLinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> DoublyLinkedList<T> {
+        let mut list = DoublyLinkedList::new();
+
+        for value in iter {
+            list.push_back(value);
+        }
+
+        list
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for DoublyLinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut list = f.debug_list();
+
+        for node in self.nodes() {
+            list.entry(&node.borrow().value);
+        }
+
+        list.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn both_ends() {
+        let mut list = DoublyLinkedList::new();
+
+        list.push_back(2);
+        list.push_front(1);
+        list.push_back(3);
+
+        assert_eq!(*list.peek_front().unwrap(), 1);
+        assert_eq!(*list.peek_back().unwrap(), 3);
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_back(), Some(2));
+        assert_eq!(list.pop_back(), None);
+        assert!(list.peek_front().is_none());
+    }
+
+    #[test]
+    fn iterators() {
+        let mut list: DoublyLinkedList<i32> = (1..=4).collect();
+
+        *list.peek_front_mut().unwrap() += 10;
+        *list.peek_back_mut().unwrap() += 10;
+
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![11, 2, 3, 14]);
+        assert_eq!(format!("{:?}", list), "[11, 2, 3, 14]");
+        assert_eq!(list.into_iter().rev().collect::<Vec<_>>(), vec![14, 3, 2, 11]);
+    }
+
+    #[test]
+    fn links_back_match_links_forward() {
+        let mut list: DoublyLinkedList<i32> = (1..=3).collect();
+
+        // Taking everything from the back only works if every prev link
+        // was kept up to date.
+        list.push_front(0);
+        list.pop_front();
+        list.push_front(0);
+
+        assert_eq!(list.into_iter().rev().collect::<Vec<_>>(), vec![3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn only_the_links_forward_are_strong() {
+        let list: DoublyLinkedList<i32> = (1..=3).collect();
+        let head = list.head.clone().unwrap();
+        let tail = list.tail.clone().unwrap();
+
+        // The head is owned by the list, the tail by the list and the node
+        // before it, and the clones here. The Weak links back don't count.
+        assert_eq!(Rc::strong_count(&head), 2);
+        assert_eq!(Rc::strong_count(&tail), 3);
+        assert_eq!(Rc::weak_count(&head), 1);
+
+        // With the clones gone, the list is the only thing keeping its nodes
+        // alive.
+        let (weak_head, weak_tail) = (Rc::downgrade(&head), Rc::downgrade(&tail));
+        drop((head, tail));
+
+        assert!(weak_head.upgrade().is_some());
+        drop(list);
+
+        assert!(weak_head.upgrade().is_none());
+        assert!(weak_tail.upgrade().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "already mutably borrowed")]
+    fn borrows_are_checked_at_run_time() {
+        let mut list: DoublyLinkedList<i32> = (1..=2).collect();
+        let node = list.head.clone().unwrap();
+
+        let _front = list.peek_front_mut();
+        let _value = node.borrow();
+    }
+}
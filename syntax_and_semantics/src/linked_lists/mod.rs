@@ -0,0 +1,117 @@
+// Three linked lists, each built from a different kind of pointer that the
+// chapters cover on their own.
+//  o SinglyLinkedList owns each node through a Box, so every node has exactly
+//    one owner and the compiler checks everything.
+//  o DoublyLinkedList shares nodes through Rc<RefCell<_>>, since a node is
+//    pointed at from both sides. The links back are Weak so the two
+//    directions don't keep each other alive.
+//  o RawQueue keeps raw pointers to both ends, so it can add at the back in
+//    O(1), and upholds the borrowing rules itself inside unsafe blocks.
+//
+// All three can add and remove at the front cheaply, which is what List asks
+// for, so code written against List works with any of them.
+use std::iter::FromIterator;
+
+mod doubly;
+mod queue;
+mod singly;
+
+pub use self::doubly::DoublyLinkedList;
+pub use self::queue::RawQueue;
+pub use self::singly::SinglyLinkedList;
+
+// Collecting into a list keeps the iterator's order, front first, and a list's
+// IntoIterator gives its values back from the front.
+pub trait List<T>: Default + FromIterator<T> + IntoIterator<Item = T> {
+    fn push_front(&mut self, value: T);
+
+    fn pop_front(&mut self) -> Option<T>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::rc::Rc;
+
+    // Miri runs far slower than a normal test, so lists there are kept short.
+    const LONG: usize = if cfg!(miri) { 100 } else { 100_000 };
+
+    // Runs the same steps against any List, so all three are held to the same
+    // behaviour.
+    fn behaves_like_a_list<L: List<String>>() {
+        let mut list = L::default();
+
+        assert!(list.is_empty());
+        assert_eq!(list.pop_front(), None);
+
+        list.push_front("b".to_string());
+        list.push_front("a".to_string());
+
+        assert_eq!(list.len(), 2);
+        assert!(list.contains(&"b".to_string()));
+        assert!(!list.contains(&"c".to_string()));
+        assert_eq!(list.pop_front().as_deref(), Some("a"));
+
+        let list: L = ["x", "y", "z"].iter().map(|s| s.to_string()).collect();
+
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec!["x", "y", "z"]);
+    }
+
+    // Every value handed to the list is dropped exactly once, whether it's
+    // popped, left in the list or left in a half used iterator.
+    fn drops_every_value<L: List<Rc<()>>>() {
+        let value = Rc::new(());
+
+        let mut list: L = (0..10).map(|_| Rc::clone(&value)).collect();
+        drop(list.pop_front());
+        assert_eq!(Rc::strong_count(&value), 10);
+        drop(list);
+
+        let mut iter = (0..10).map(|_| Rc::clone(&value)).collect::<L>().into_iter();
+        iter.next();
+        drop(iter);
+
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    // Dropping nodes one by one, rather than each dropping the next, keeps a
+    // long list from overflowing the stack.
+    fn drops_a_long_list<L: List<usize>>() {
+        let list: L = (0..LONG).collect();
+
+        assert_eq!(list.len(), LONG);
+    }
+
+    #[test]
+    fn singly_linked_list() {
+        behaves_like_a_list::<SinglyLinkedList<_>>();
+        drops_every_value::<SinglyLinkedList<_>>();
+        drops_a_long_list::<SinglyLinkedList<_>>();
+    }
+
+    #[test]
+    fn doubly_linked_list() {
+        behaves_like_a_list::<DoublyLinkedList<_>>();
+        drops_every_value::<DoublyLinkedList<_>>();
+        drops_a_long_list::<DoublyLinkedList<_>>();
+    }
+
+    #[test]
+    fn raw_queue() {
+        behaves_like_a_list::<RawQueue<_>>();
+        drops_every_value::<RawQueue<_>>();
+        drops_a_long_list::<RawQueue<_>>();
+    }
+}
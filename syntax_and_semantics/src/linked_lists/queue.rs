@@ -0,0 +1,291 @@
+use std::fmt;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ptr;
+
+use super::List;
+
+// A queue kept as a singly linked list with raw pointers to both ends.
+//
+// Adding at the back needs a way to reach the last node, but with Box every
+// node already has an owner, and Rc<RefCell<_>> would pay for checks this
+// queue doesn't need. So nodes are allocated with Box::into_raw and owned by
+// the queue through raw pointers, and freed with Box::from_raw when popped.
+//
+// Nothing checks the pointers, so every unsafe block relies on the same rules.
+//  o head and tail are both null or both point at live nodes, and every next
+//    pointer is null or points at a live node.
+//  o Nodes are only ever reached through these raw pointers. References to
+//    them are made for no longer than a borrow of the queue, so they can't
+//    overlap with the queue changing its nodes.
+pub struct RawQueue<T> {
+    head: *mut Node<T>,
+    tail: *mut Node<T>,
+    len: usize,
+    // The queue owns its values, which raw pointers alone don't say. This tells
+    // the compiler that dropping a queue may drop Ts.
+    marker: PhantomData<T>,
+}
+
+struct Node<T> {
+    value: T,
+    next: *mut Node<T>,
+}
+
+impl<T> RawQueue<T> {
+    pub fn new() -> RawQueue<T> {
+        RawQueue { head: ptr::null_mut(), tail: ptr::null_mut(), len: 0, marker: PhantomData }
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        let node = Box::into_raw(Box::new(Node { value, next: ptr::null_mut() }));
+
+        if self.tail.is_null() {
+            self.head = node;
+        } else {
+            // The tail is live, and nothing else refers to it while the queue
+            // is borrowed mutably.
+            unsafe { (*self.tail).next = node };
+        }
+
+        self.tail = node;
+        self.len += 1;
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        let node = Box::into_raw(Box::new(Node { value, next: self.head }));
+
+        if self.tail.is_null() {
+            self.tail = node;
+        }
+
+        self.head = node;
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.head.is_null() {
+            return None;
+        }
+
+        // head came from Box::into_raw and is unlinked here before anything
+        // else can reach it, so it's turned back into a Box exactly once.
+        let node = unsafe { Box::from_raw(self.head) };
+
+        self.head = node.next;
+        if self.head.is_null() {
+            self.tail = ptr::null_mut();
+        }
+        self.len -= 1;
+
+        Some(node.value)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        // The reference lasts as long as the borrow of the queue.
+        unsafe { self.head.as_ref() }.map(|node| &node.value)
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        unsafe { self.head.as_mut() }.map(|node| &mut node.value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: unsafe { self.head.as_ref() } }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { next: unsafe { self.head.as_mut() } }
+    }
+}
+
+// Every node was allocated by the queue, so it has to free them itself.
+impl<T> Drop for RawQueue<T> {
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+impl<T> List<T> for RawQueue<T> {
+    fn push_front(&mut self, value: T) {
+        RawQueue::push_front(self, value);
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        RawQueue::pop_front(self)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|v| v == value)
+    }
+}
+
+impl<T> Default for RawQueue<T> {
+    fn default() -> RawQueue<T> {
+        RawQueue::new()
+    }
+}
+
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.next?;
+        self.next = unsafe { node.next.as_ref() };
+
+        Some(&node.value)
+    }
+}
+
+// Each node is only reached once, so the &mut references handed out never
+// overlap.
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut Node<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        let node = self.next.take()?;
+        self.next = unsafe { node.next.as_mut() };
+
+        Some(&mut node.value)
+    }
+}
+
+pub struct IntoIter<T> {
+    queue: RawQueue<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.queue.pop_front()
+    }
+}
+
+impl<T> IntoIterator for RawQueue<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { queue: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RawQueue<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T> FromIterator<T> for RawQueue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> RawQueue<T> {
+        let mut queue = RawQueue::new();
+
+        for value in iter {
+            queue.push_back(value);
+        }
+
+        queue
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for RawQueue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_in_first_out() {
+        let mut queue = RawQueue::new();
+
+        queue.push_back(1);
+        queue.push_back(2);
+        queue.push_front(0);
+
+        assert_eq!(queue.peek(), Some(&0));
+        assert_eq!(queue.pop_front(), Some(0));
+        assert_eq!(queue.pop_front(), Some(1));
+
+        // Emptying the queue has to reset the tail as well, or the next push
+        // would write through a freed node.
+        assert_eq!(queue.pop_front(), Some(2));
+        assert_eq!(queue.pop_front(), None);
+
+        queue.push_back(3);
+        queue.push_back(4);
+
+        assert_eq!(queue.iter().collect::<Vec<_>>(), vec![&3, &4]);
+    }
+
+    #[test]
+    fn pushing_to_the_front_of_an_empty_queue_sets_the_tail() {
+        let mut queue = RawQueue::new();
+
+        queue.push_front(1);
+        queue.push_back(2);
+
+        assert_eq!(queue.into_iter().collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn mutable_access() {
+        let mut queue: RawQueue<i32> = (1..=3).collect();
+
+        for value in queue.iter_mut() {
+            *value *= 10;
+        }
+        *queue.peek_mut().unwrap() += 1;
+        queue.push_back(40);
+
+        assert_eq!(format!("{:?}", queue), "[11, 20, 30, 40]");
+    }
+
+    #[test]
+    fn interleaved_pushes_and_pops() {
+        let mut queue = RawQueue::new();
+        let mut expected = std::collections::VecDeque::new();
+
+        for i in 0..50 {
+            queue.push_back(i);
+            expected.push_back(i);
+
+            if i % 3 == 0 {
+                assert_eq!(queue.pop_front(), expected.pop_front());
+            }
+        }
+
+        assert_eq!(queue.len(), expected.len());
+        assert!(queue.into_iter().eq(expected));
+    }
+}
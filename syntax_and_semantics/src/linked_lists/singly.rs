@@ -0,0 +1,256 @@
+use std::fmt;
+use std::iter::FromIterator;
+
+use super::List;
+
+// A singly linked list, where each node owns the next through a Box.
+pub struct SinglyLinkedList<T> {
+    head: Link<T>,
+    len: usize,
+}
+
+type Link<T> = Option<Box<Node<T>>>;
+
+struct Node<T> {
+    value: T,
+    next: Link<T>,
+}
+
+impl<T> SinglyLinkedList<T> {
+    pub fn new() -> SinglyLinkedList<T> {
+        SinglyLinkedList { head: None, len: 0 }
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        // take leaves None in head while the new node takes the old one over.
+        let next = self.head.take();
+        self.head = Some(Box::new(Node { value, next }));
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let node = self.head.take()?;
+        self.head = node.next;
+        self.len -= 1;
+
+        Some(node.value)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.value)
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.head.as_mut().map(|node| &mut node.value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Turns the list round by moving each node to the front of a new chain.
+    pub fn reverse(&mut self) {
+        let mut reversed = None;
+        let mut rest = self.head.take();
+
+        while let Some(mut node) = rest {
+            rest = node.next.take();
+            node.next = reversed;
+            reversed = Some(node);
+        }
+
+        self.head = reversed;
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: self.head.as_deref() }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { next: self.head.as_deref_mut() }
+    }
+}
+
+// The default drop would drop the head, which drops the next node and so on,
+// recursing once per node. Unlinking the nodes in a loop drops each on its
+// own.
+impl<T> Drop for SinglyLinkedList<T> {
+    fn drop(&mut self) {
+        let mut link = self.head.take();
+
+        while let Some(mut node) = link {
+            link = node.next.take();
+        }
+    }
+}
+
+impl<T> List<T> for SinglyLinkedList<T> {
+    fn push_front(&mut self, value: T) {
+        SinglyLinkedList::push_front(self, value);
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        SinglyLinkedList::pop_front(self)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|v| v == value)
+    }
+}
+
+impl<T> Default for SinglyLinkedList<T> {
+    fn default() -> SinglyLinkedList<T> {
+        SinglyLinkedList::new()
+    }
+}
+
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.next?;
+        self.next = node.next.as_deref();
+
+        Some(&node.value)
+    }
+}
+
+// Each node is borrowed mutably once, as take moves the borrow out of the
+// iterator rather than copying it.
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut Node<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        let node = self.next.take()?;
+        self.next = node.next.as_deref_mut();
+
+        Some(&mut node.value)
+    }
+}
+
+pub struct IntoIter<T> {
+    list: SinglyLinkedList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+}
+
+impl<T> IntoIterator for SinglyLinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SinglyLinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+// Keeps a borrow of the last link so each value can be added at the end
+// without walking the list again.
+impl<T> FromIterator<T> for SinglyLinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> SinglyLinkedList<T> {
+        let mut head = None;
+        let mut len = 0;
+        let mut tail = &mut head;
+
+        for value in iter {
+            let node = tail.insert(Box::new(Node { value, next: None }));
+            tail = &mut node.next;
+            len += 1;
+        }
+
+        SinglyLinkedList { head, len }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SinglyLinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_and_pop_at_the_front() {
+        let mut list = SinglyLinkedList::new();
+
+        list.push_front(1);
+        list.push_front(2);
+
+        assert_eq!(list.peek(), Some(&2));
+        assert_eq!(list.pop_front(), Some(2));
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_front(), None);
+    }
+
+    #[test]
+    fn iterators() {
+        let mut list: SinglyLinkedList<i32> = (1..=3).collect();
+
+        for value in list.iter_mut() {
+            *value *= 10;
+        }
+        *list.peek_mut().unwrap() += 1;
+
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![11, 20, 30]);
+        assert_eq!(format!("{:?}", list), "[11, 20, 30]");
+    }
+
+    #[test]
+    fn reverse() {
+        let mut list: SinglyLinkedList<i32> = (1..=4).collect();
+
+        list.reverse();
+
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn reversing_moves_nodes_not_values() {
+        let mut list: SinglyLinkedList<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+        let first = list.peek().unwrap() as *const String;
+
+        // The node holding "a" is relinked at the back, so its value stays
+        // where it was rather than being moved between nodes.
+        list.reverse();
+
+        assert_eq!(list.iter().last().unwrap() as *const String, first);
+        assert_eq!(list.len(), 3);
+        assert_eq!(format!("{:?}", list), "[\"c\", \"b\", \"a\"]");
+    }
+}
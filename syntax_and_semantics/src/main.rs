@@ -1,41 +1,93 @@
-extern crate syntax_and_semantics;
+// Before the 2018 edition the library had to be declared with
+// extern crate syntax_and_semantics; before it could be used. Cargo now passes
+// dependencies, including a package's own library, to the compiler directly.
+use std::env;
 
-use syntax_and_semantics::examples::*;
+use cmdline::{Arg, Command, Error};
+use syntax_and_semantics::examples::{Chapter, CHAPTERS};
+
+fn command() -> Command {
+    Command::new("syntax_and_semantics")
+        .about("Examples from the Syntax and Semantics section of the Rust book")
+        .arg(Arg::flag("list").help("list the chapters instead of running them"))
+        .arg(
+            Arg::positional("chapter")
+                .repeatable()
+                .help("the chapters to run, in the order given, all of them by default"),
+        )
+}
+
+// The chapters to run, or None if they should be listed instead.
+fn chapters<I, S>(args: I) -> Result<Option<Vec<Chapter>>, Error>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let matches = command().parse(args)?;
+
+    if matches.flag("list") {
+        return Ok(None);
+    }
+
+    let names: Vec<String> = matches.values("chapter")?;
+
+    if names.is_empty() {
+        return Ok(Some(CHAPTERS.to_vec()));
+    }
+
+    names
+        .iter()
+        .map(|name| {
+            CHAPTERS
+                .iter()
+                .find(|&&(chapter, _)| chapter == name)
+                .copied()
+                .ok_or_else(|| matches.error(&format!("unknown chapter {:?}, see --list", name)))
+        })
+        .collect::<Result<_, _>>()
+        .map(Some)
+}
 
 // Examples from Rust Book - Syntax and Semantics.
 // See https://doc.rust-lang.org/book/first-edition/syntax-and-semantics.html
 fn main() {
-    variable_bindings();
-    functions();
-    primitive_types();
-    if_examples();
-    loops();
-    vectors();
-    ownership();
-    references_and_borrowing();
-    lifetimes();
-    mutability();
-    structs();
-    enums();
-    matching();
-    patterns();
-    method_syntax();
-    strings();
-    generics();
-    traits();
-    drop();
-    if_let_while_let();
-    trait_objects();
-    closures();
-    universal_function_call_syntax();
-    const_and_static();
-    attributes();
-    type_aliases();
-    coercion();
-    associated_types();
-    unsized_types();
-    operators_and_overloading();
-    deref_coercions();
-    macros();
-    raw_pointers();
+    match chapters(env::args().skip(1)).unwrap_or_else(|e| e.exit()) {
+        Some(chapters) => {
+            for (_, chapter) in chapters {
+                chapter();
+            }
+        }
+        None => {
+            for (name, _) in CHAPTERS {
+                println!("{}", name);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(args: &[&str]) -> Result<Option<Vec<&'static str>>, String> {
+        chapters(args.iter().copied())
+            .map(|chapters| chapters.map(|c| c.iter().map(|&(name, _)| name).collect()))
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn every_chapter_runs_by_default() {
+        let all = names(&[]).unwrap().unwrap();
+
+        assert_eq!(all.len(), CHAPTERS.len());
+        assert_eq!(all.first(), Some(&"variable_bindings"));
+        assert_eq!(all.last(), Some(&"error_handling"));
+    }
+
+    #[test]
+    fn chapters_can_be_picked_by_name() {
+        assert_eq!(names(&["closures", "loops"]).unwrap(), Some(vec!["closures", "loops"]));
+        assert_eq!(names(&["--list"]).unwrap(), None);
+        assert_eq!(names(&["loopz"]).unwrap_err(), "unknown chapter \"loopz\", see --list");
+    }
 }
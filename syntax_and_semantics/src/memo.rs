@@ -0,0 +1,322 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+// Memoisation, where a function's results are cached so it's only called once
+// for each argument.
+//
+// combinators::memoize does this with a closure that owns its cache. Memo is
+// the same idea as a generic struct, which means the cache can be given a
+// capacity and asked how well it's doing.
+
+// How often the cache had the answer.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+impl Stats {
+    // The fraction of lookups that were hits, or 0 if there haven't been any.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+
+        if lookups == 0 { 0.0 } else { self.hits as f64 / lookups as f64 }
+    }
+}
+
+// A cache that evicts the least recently used entry once it's full.
+//
+// Each use of an entry stamps it with the next value of a counter. The order
+// map goes from stamps back to keys, so its first entry is always the least
+// recently used.
+#[derive(Debug)]
+struct Cache<K, V> {
+    entries: HashMap<K, (V, u64)>,
+    order: BTreeMap<u64, K>,
+    capacity: Option<usize>,
+    clock: u64,
+    stats: Stats,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> Cache<K, V> {
+    fn new(capacity: Option<usize>) -> Cache<K, V> {
+        assert!(capacity != Some(0), "a memo needs room for at least one entry");
+
+        Cache { entries: HashMap::new(), order: BTreeMap::new(), capacity, clock: 0, stats: Stats::default() }
+    }
+
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    // Looks a key up, counting a hit or a miss and marking it as just used.
+    fn get(&mut self, key: &K) -> Option<V> {
+        let now = self.tick();
+
+        match self.entries.get_mut(key) {
+            Some((value, used)) => {
+                let key = self.order.remove(used).expect("every entry is in the order map");
+                self.order.insert(now, key);
+                *used = now;
+                self.stats.hits += 1;
+
+                Some(value.clone())
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        let now = self.tick();
+
+        if let Some((_, used)) = self.entries.insert(key.clone(), (value, now)) {
+            self.order.remove(&used);
+        }
+        self.order.insert(now, key);
+
+        while self.capacity.is_some_and(|capacity| self.entries.len() > capacity) {
+            let (_, oldest) = self.order.pop_first().expect("a full cache has entries");
+            self.entries.remove(&oldest);
+            self.stats.evictions += 1;
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+}
+
+// Wraps a function of one argument and caches its results.
+//
+//  let mut slow_square = Memo::new(|&x: &u64| x * x);
+//  slow_square.get(4); // calls the closure
+//  slow_square.get(4); // uses the cached 16
+//
+// The closure is FnMut, so it may keep state of its own, and takes the key by
+// reference so that it doesn't need cloning for every call.
+pub struct Memo<F, K, V> {
+    f: F,
+    cache: Cache<K, V>,
+}
+
+impl<F, K, V> Memo<F, K, V>
+where
+    F: FnMut(&K) -> V,
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    // A memo that keeps every result.
+    pub fn new(f: F) -> Memo<F, K, V> {
+        Memo { f, cache: Cache::new(None) }
+    }
+
+    // A memo that keeps at most capacity results, dropping the least recently
+    // used when it's full. Panics if capacity is 0.
+    pub fn with_capacity(capacity: usize, f: F) -> Memo<F, K, V> {
+        Memo { f, cache: Cache::new(Some(capacity)) }
+    }
+
+    pub fn get(&mut self, key: K) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            return value;
+        }
+
+        let value = (self.f)(&key);
+        self.cache.insert(key, value.clone());
+
+        value
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.cache.entries.contains_key(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.entries.is_empty()
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.cache.stats
+    }
+
+    // Forgets every result, but not the stats.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
+
+// A memo for recursive functions such as Fibonacci.
+//
+// A closure can't call itself, since it has no name, and even a memo stored
+// in a variable couldn't be borrowed from inside its own closure. So the
+// function is given a second argument instead, which it calls to recurse and
+// which goes through the cache.
+//
+//  let mut fib = RecursiveMemo::new(|fib, &n: &u64| {
+//      if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+//  });
+pub struct RecursiveMemo<F, K, V> {
+    f: F,
+    cache: Cache<K, V>,
+}
+
+impl<F, K, V> RecursiveMemo<F, K, V>
+where
+    F: Fn(&mut dyn FnMut(K) -> V, &K) -> V,
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    pub fn new(f: F) -> RecursiveMemo<F, K, V> {
+        RecursiveMemo { f, cache: Cache::new(None) }
+    }
+
+    pub fn with_capacity(capacity: usize, f: F) -> RecursiveMemo<F, K, V> {
+        RecursiveMemo { f, cache: Cache::new(Some(capacity)) }
+    }
+
+    pub fn get(&mut self, key: K) -> V {
+        compute(&self.f, &mut self.cache, key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.entries.is_empty()
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.cache.stats
+    }
+}
+
+// The function and the cache are borrowed separately, so the closure passed
+// to f can borrow the cache mutably while f itself is being called. The
+// closure is passed as a trait object since its type would otherwise contain
+// itself.
+fn compute<F, K, V>(f: &F, cache: &mut Cache<K, V>, key: K) -> V
+where
+    F: Fn(&mut dyn FnMut(K) -> V, &K) -> V,
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    if let Some(value) = cache.get(&key) {
+        return value;
+    }
+
+    let value = f(&mut |k| compute(f, cache, k), &key);
+    cache.insert(key, value.clone());
+
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cell::Cell;
+
+    #[test]
+    fn results_are_cached() {
+        let calls = Cell::new(0);
+        let mut square = Memo::new(|&x: &u64| {
+            calls.set(calls.get() + 1);
+            x * x
+        });
+
+        assert_eq!(square.get(3), 9);
+        assert_eq!(square.get(3), 9);
+        assert_eq!(square.get(4), 16);
+
+        assert_eq!(calls.get(), 2);
+        assert_eq!(square.stats(), Stats { hits: 1, misses: 2, evictions: 0 });
+        assert_eq!(square.len(), 2);
+    }
+
+    #[test]
+    fn least_recently_used_is_evicted() {
+        let mut length = Memo::with_capacity(2, |s: &String| s.len());
+
+        length.get("a".to_string());
+        length.get("bb".to_string());
+
+        // Using a makes bb the least recently used, so it goes when ccc is
+        // added.
+        length.get("a".to_string());
+        length.get("ccc".to_string());
+
+        assert!(length.contains(&"a".to_string()));
+        assert!(!length.contains(&"bb".to_string()));
+        assert!(length.contains(&"ccc".to_string()));
+        assert_eq!(length.stats().evictions, 1);
+    }
+
+    #[test]
+    fn the_closure_can_keep_state() {
+        let mut next_id = 0;
+        let mut ids = Memo::new(move |_: &&str| {
+            next_id += 1;
+            next_id
+        });
+
+        assert_eq!(ids.get("x"), 1);
+        assert_eq!(ids.get("y"), 2);
+        assert_eq!(ids.get("x"), 1);
+
+        ids.clear();
+        assert!(ids.is_empty());
+        assert_eq!(ids.get("x"), 3);
+    }
+
+    #[test]
+    fn hit_rate() {
+        assert_eq!(Stats::default().hit_rate(), 0.0);
+        assert_eq!(Stats { hits: 3, misses: 1, evictions: 0 }.hit_rate(), 0.75);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one entry")]
+    fn zero_capacity_panics() {
+        Memo::with_capacity(0, |&x: &u8| x);
+    }
+
+    // A plain function works as well as a closure.
+    fn fibonacci(fib: &mut dyn FnMut(u64) -> u64, &n: &u64) -> u64 {
+        if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+    }
+
+    #[test]
+    fn recursion_goes_through_the_cache() {
+        let mut fib = RecursiveMemo::new(fibonacci);
+
+        // Without the cache this would take around 2^90 calls.
+        assert_eq!(fib.get(90), 2_880_067_194_370_816_120);
+
+        // Each of fib(0) to fib(90) is worked out once. From fib(3) up, the
+        // second call, to fib(n - 2), is already answered.
+        assert_eq!(fib.stats(), Stats { hits: 88, misses: 91, evictions: 0 });
+        assert_eq!(fib.len(), 91);
+    }
+
+    #[test]
+    fn recursion_only_needs_the_recent_results() {
+        // fib(n) only ever needs the two results before it, so a tiny cache is
+        // enough to keep the recursion linear.
+        let mut fib = RecursiveMemo::with_capacity(3, fibonacci);
+
+        assert_eq!(fib.get(50), 12_586_269_025);
+        assert_eq!(fib.len(), 3);
+        assert!(fib.stats().misses <= 51);
+    }
+}
@@ -0,0 +1,279 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+// A job is any closure that can be sent to another thread and called once.
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+// A fixed size pool of worker threads.
+//
+// Jobs are submitted as closures and handed to whichever worker is free via a
+// channel. Dropping the pool closes the channel, lets the workers finish every
+// job that was already queued and then joins them, so no submitted job is lost.
+pub struct ThreadPool {
+    workers: Vec<Worker>,
+    sender: Option<Sender<Job>>,
+}
+
+impl ThreadPool {
+    // Creates a pool with the given number of workers.
+    //
+    // Panics if size is zero since a pool without workers would never run
+    // anything.
+    pub fn new(size: usize) -> ThreadPool {
+        assert!(size > 0, "a thread pool needs at least one worker");
+
+        let (sender, receiver) = mpsc::channel();
+
+        // Every worker pulls from the same receiver, so it is shared behind an
+        // Arc and a Mutex ensures only one worker takes a given job.
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let workers = (0..size)
+            .map(|_| Worker::new(Arc::clone(&receiver)))
+            .collect();
+
+        ThreadPool { workers, sender: Some(sender) }
+    }
+
+    pub fn size(&self) -> usize {
+        self.workers.len()
+    }
+
+    // Queues a closure to be run by the next free worker.
+    pub fn execute<F>(&self, f: F)
+        where F: FnOnce() + Send + 'static
+    {
+        self.sender
+            .as_ref()
+            .expect("thread pool has already been shut down")
+            .send(Box::new(f))
+            .expect("all thread pool workers have stopped");
+    }
+
+    // Waits for all queued jobs to complete and stops the workers. This is
+    // equivalent to dropping the pool but reads better at call sites.
+    pub fn shutdown(self) {}
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        // Closing the channel tells each worker to stop once the queue has
+        // been drained.
+        drop(self.sender.take());
+
+        for worker in &mut self.workers {
+            if let Some(thread) = worker.thread.take() {
+                thread.join().expect("thread pool worker panicked");
+            }
+        }
+    }
+}
+
+struct Worker {
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Worker {
+    fn new(receiver: Arc<Mutex<Receiver<Job>>>) -> Worker {
+        let thread = thread::spawn(move || loop {
+            // The lock guard is a temporary so it is released as soon as a job
+            // has been received, before the job itself runs.
+            let job = receiver.lock().expect("job queue lock poisoned").recv();
+
+            match job {
+                // A panicking job must not take the worker down with it,
+                // otherwise the pool would quietly shrink.
+                Ok(job) => { let _ = panic::catch_unwind(AssertUnwindSafe(job)); }
+                Err(_)  => break,
+            }
+        });
+
+        Worker { thread: Some(thread) }
+    }
+}
+
+// Gathers the return values of jobs run on a pool.
+//
+// Each submitted job is given its own clone of the sending half of a channel
+// and sends its result, tagged with the order in which it was submitted, when
+// it completes.
+pub struct Collector<T> {
+    sender: Sender<(usize, T)>,
+    receiver: Receiver<(usize, T)>,
+    submitted: usize,
+}
+
+impl<T: Send + 'static> Collector<T> {
+    pub fn new() -> Collector<T> {
+        let (sender, receiver) = mpsc::channel();
+        Collector { sender, receiver, submitted: 0 }
+    }
+
+    // Runs f on the pool, keeping hold of whatever it returns.
+    pub fn submit<F>(&mut self, pool: &ThreadPool, f: F)
+        where F: FnOnce() -> T + Send + 'static
+    {
+        let index = self.submitted;
+        let sender = self.sender.clone();
+
+        pool.execute(move || {
+            // The receiver only goes away once the collector has been dropped,
+            // at which point nobody is interested in the result anyway.
+            let _ = sender.send((index, f()));
+        });
+
+        self.submitted += 1;
+    }
+
+    pub fn submitted(&self) -> usize {
+        self.submitted
+    }
+
+    // Blocks until every submitted job has finished and returns their results
+    // in submission order, one for each job.
+    //
+    // A job that panics drops its sender without sending, so its entry is None
+    // and the results of the jobs after it stay where they were submitted.
+    pub fn wait(self) -> Vec<Option<T>> {
+        let Collector { sender, receiver, submitted } = self;
+
+        // Once our own sender is dropped the channel closes as soon as the last
+        // job finishes, which ends the iteration below.
+        drop(sender);
+
+        let mut results: Vec<Option<T>> = (0..submitted).map(|_| None).collect();
+
+        for (index, result) in receiver {
+            results[index] = Some(result);
+        }
+
+        results
+    }
+}
+
+impl<T: Send + 'static> Default for Collector<T> {
+    fn default() -> Collector<T> {
+        Collector::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn pool_has_requested_number_of_workers() {
+        let pool = ThreadPool::new(3);
+        assert_eq!(pool.size(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one worker")]
+    fn pool_of_zero_workers_is_rejected() {
+        ThreadPool::new(0);
+    }
+
+    #[test]
+    fn move_closures_take_their_captures_to_the_worker() {
+        let pool = ThreadPool::new(2);
+        let mut collector = Collector::new();
+
+        // Each closure owns its own String, which would not be possible with
+        // a borrowing closure since the worker may outlive this stack frame.
+        for name in ["foo", "bar", "baz"] {
+            let owned = name.to_string();
+            collector.submit(&pool, move || owned.to_uppercase());
+        }
+
+        let results = collector.wait();
+        let results: Vec<_> = results.iter().map(Option::as_deref).collect();
+
+        assert_eq!(results, vec![Some("FOO"), Some("BAR"), Some("BAZ")]);
+    }
+
+    #[test]
+    fn move_closure_mutates_its_own_copy() {
+        // As in the closures chapter, moving a Copy value into the closure
+        // leaves the original binding untouched.
+        let mut z = 5;
+        let pool = ThreadPool::new(1);
+        let mut collector = Collector::new();
+
+        collector.submit(&pool, move || {
+            z += 5;
+            z
+        });
+
+        assert_eq!(collector.wait(), vec![Some(10)]);
+        assert_eq!(z, 5);
+    }
+
+    #[test]
+    fn shutdown_runs_every_queued_job() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let pool = ThreadPool::new(4);
+
+        for _ in 0..100 {
+            let counter = Arc::clone(&counter);
+            pool.execute(move || {
+                thread::sleep(Duration::from_millis(1));
+                counter.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+
+        pool.shutdown();
+
+        assert_eq!(counter.load(Ordering::SeqCst), 100);
+    }
+
+    #[test]
+    fn shared_state_behind_arc_mutex() {
+        let total = Arc::new(Mutex::new(0));
+
+        {
+            let pool = ThreadPool::new(3);
+
+            for i in 1..11 {
+                let total = Arc::clone(&total);
+                pool.execute(move || *total.lock().unwrap() += i);
+            }
+        }
+
+        assert_eq!(*total.lock().unwrap(), 55);
+    }
+
+    #[test]
+    fn collector_returns_results_in_submission_order() {
+        let pool = ThreadPool::new(4);
+        let mut collector = Collector::new();
+
+        for i in 0..20u64 {
+            // Later jobs finish first, so completion order is reversed.
+            collector.submit(&pool, move || {
+                thread::sleep(Duration::from_millis(20 - i));
+                i * i
+            });
+        }
+
+        assert_eq!(collector.submitted(), 20);
+        assert_eq!(collector.wait(), (0..20).map(|i| Some(i * i)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn panicking_job_does_not_lose_other_jobs() {
+        let pool = ThreadPool::new(1);
+        let mut collector = Collector::new();
+
+        collector.submit(&pool, || 1);
+        collector.submit(&pool, || panic!("job failed"));
+        collector.submit(&pool, || 3);
+
+        // The failed job keeps its place, so 3 is still the third result.
+        assert_eq!(collector.wait(), vec![Some(1), None, Some(3)]);
+        assert_eq!(pool.size(), 1);
+    }
+}
@@ -0,0 +1,284 @@
+// A tutor that works through the chapters in order.
+//
+// Each chapter starts with the explanations from its source, is then run, and
+// ends with a short quiz. Progress is recorded after every chapter so a
+// learner can stop at any point and carry on later.
+use std::io::{self, BufRead, Write};
+use std::mem;
+
+use crate::examples::{source, Chapter};
+
+mod progress;
+mod quiz;
+
+pub use self::progress::{Progress, Record};
+pub use self::quiz::{questions, Question};
+
+// Pulls the explanations out of a chapter's source, as paragraphs of comment
+// lines.
+//
+// Only the comments from the chapter's function onwards are used, since those
+// above it explain lint attributes rather than Rust, and the tests at the end
+// are left out. Comments at the end of a line of code are left out too, as
+// they make little sense without the code.
+pub fn comments(chapter: &str, source: &str) -> Vec<String> {
+    let start = format!("pub fn {}(", chapter);
+    let mut paragraphs = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+
+    let lines = source
+        .lines()
+        .skip_while(|line| !line.starts_with(&start))
+        .take_while(|line| !line.starts_with("#[cfg(test)]"));
+
+    for line in lines {
+        let text = line.trim_start().strip_prefix("//").map(|text| text.strip_prefix(' ').unwrap_or(text));
+
+        match text {
+            Some(text) if !text.trim().is_empty() => paragraph.push(text.trim_end()),
+            _ if !paragraph.is_empty() => paragraphs.push(mem::take(&mut paragraph).join("\n")),
+            _ => {}
+        }
+    }
+
+    if !paragraph.is_empty() {
+        paragraphs.push(paragraph.join("\n"));
+    }
+
+    paragraphs
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    // Every chapter has been finished.
+    Finished,
+    // The learner stopped part way through.
+    Stopped,
+}
+
+// Works through the chapters from the first unfinished one, calling save with
+// the progress after each.
+//
+// Chapters print straight to stdout, so output is flushed before each is run
+// to keep the two in order.
+pub fn play<R, W, S>(
+    chapters: &[Chapter],
+    progress: &mut Progress,
+    mut save: S,
+    mut input: R,
+    output: &mut W,
+) -> io::Result<Outcome>
+where
+    R: BufRead,
+    W: Write,
+    S: FnMut(&Progress) -> io::Result<()>,
+{
+    let start = match progress.next(chapters) {
+        Some(start) => start,
+        None => {
+            writeln!(output, "Every chapter is finished, well done!")?;
+            return Ok(Outcome::Finished);
+        }
+    };
+
+    if !progress.is_empty() {
+        writeln!(output, "Welcome back, carrying on from {}.\n", chapters[start].0)?;
+    }
+
+    for (number, &(name, chapter)) in chapters.iter().enumerate().skip(start) {
+        if progress.get(name).is_some() {
+            continue;
+        }
+
+        writeln!(output, "== Chapter {} of {}: {} ==\n", number + 1, chapters.len(), name)?;
+
+        for paragraph in comments(name, source(name).unwrap_or_default()) {
+            writeln!(output, "{}\n", paragraph)?;
+        }
+
+        let record = match ask(&mut input, output, "Press enter to run the chapter, s to skip it or q to stop.")? {
+            Some(answer) if answer == "q" => return Ok(Outcome::Stopped),
+            None => return Ok(Outcome::Stopped),
+            Some(answer) if answer == "s" => Record::Skipped,
+            Some(_) => {
+                output.flush()?;
+                chapter();
+
+                match quiz(questions(name), &mut input, output)? {
+                    Some(record) => record,
+                    None => return Ok(Outcome::Stopped),
+                }
+            }
+        };
+
+        progress.record(name, record);
+        save(progress)?;
+    }
+
+    let (correct, asked) = progress.score();
+    writeln!(output, "That's every chapter! You answered {} of {} questions correctly.", correct, asked)?;
+
+    Ok(Outcome::Finished)
+}
+
+// Asks each question in turn, returning None if the input ends part way
+// through.
+fn quiz<R: BufRead, W: Write>(
+    questions: &[Question],
+    input: &mut R,
+    output: &mut W,
+) -> io::Result<Option<Record>> {
+    let mut correct = 0;
+
+    for (number, question) in questions.iter().enumerate() {
+        let prompt = format!("Question {} of {}: {}", number + 1, questions.len(), question.prompt);
+
+        let answer = match ask(input, output, &prompt)? {
+            Some(answer) => answer,
+            None => return Ok(None),
+        };
+
+        if question.check(&answer) {
+            correct += 1;
+            writeln!(output, "Correct!\n")?;
+        } else {
+            writeln!(output, "Not quite, the answer is {}\n", question.answer())?;
+        }
+    }
+
+    writeln!(output, "You answered {} of {} correctly.\n", correct, questions.len())?;
+
+    Ok(Some(Record::Quizzed { correct, asked: questions.len() }))
+}
+
+// Prints a prompt and reads the reply, returning None at the end of the input.
+fn ask<R: BufRead, W: Write>(input: &mut R, output: &mut W, prompt: &str) -> io::Result<Option<String>> {
+    write!(output, "{}\n> ", prompt)?;
+    output.flush()?;
+
+    let mut line = String::new();
+
+    if input.read_line(&mut line)? == 0 {
+        writeln!(output)?;
+        return Ok(None);
+    }
+
+    Ok(Some(line.trim().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::examples::CHAPTERS;
+
+    fn nothing() {}
+
+    // The real chapters are swapped for ones that do nothing, so only the
+    // tutor's own output is checked.
+    const QUIET: &[Chapter] = &[("coercion", nothing), ("drop", nothing)];
+
+    fn run(progress: &mut Progress, input: &str) -> (Outcome, String, Vec<Progress>) {
+        let mut saved = Vec::new();
+        let mut output = Vec::new();
+
+        let outcome = play(
+            QUIET,
+            progress,
+            |p| {
+                saved.push(p.clone());
+                Ok(())
+            },
+            input.as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+
+        (outcome, String::from_utf8(output).unwrap(), saved)
+    }
+
+    #[test]
+    fn every_chapter_has_explanations() {
+        for &(chapter, _) in CHAPTERS {
+            let source = source(chapter).unwrap_or_else(|| panic!("no source for {}", chapter));
+
+            assert!(!comments(chapter, source).is_empty(), "no comments in {}", chapter);
+        }
+    }
+
+    #[test]
+    fn comments_are_grouped_into_paragraphs() {
+        let source = "\
+// About the lints.
+pub fn example() {
+    // First line
+    // second line.
+    let x = 1; // Not this.
+
+    // Another paragraph.
+    //
+    //  indented
+}
+
+#[cfg(test)]
+mod tests {
+    // Not this either.
+}
+";
+
+        assert_eq!(comments("example", source), vec![
+            "First line\nsecond line.",
+            "Another paragraph.",
+            " indented",
+        ]);
+    }
+
+    #[test]
+    fn runs_each_chapter_then_quizzes() {
+        let mut progress = Progress::default();
+        let input = "\n200\n2\n44\n97\ns\n";
+        let (outcome, output, saved) = run(&mut progress, input);
+
+        assert_eq!(outcome, Outcome::Finished);
+        assert!(output.starts_with("== Chapter 1 of 2: coercion ==\n\nThe most common case of coercion"));
+        assert!(output.contains("Question 1 of 4: What does -56i8 as u8 print?\n> Correct!"));
+        assert!(output.contains("Not quite, the answer is 1\n"));
+        assert!(output.contains("You answered 3 of 4 correctly."));
+        assert!(output.contains("== Chapter 2 of 2: drop =="));
+        assert!(output.ends_with("You answered 3 of 4 questions correctly.\n"));
+
+        assert_eq!(saved.len(), 2);
+        assert_eq!(progress.to_string(), "coercion 3/4\ndrop skipped\n");
+    }
+
+    #[test]
+    fn stopping_keeps_the_finished_chapters() {
+        let mut progress = Progress::default();
+        let (outcome, _, saved) = run(&mut progress, "s\nq\n");
+
+        assert_eq!(outcome, Outcome::Stopped);
+        assert_eq!(saved.len(), 1);
+        assert_eq!(progress.to_string(), "coercion skipped\n");
+
+        // Running out of input part way through a quiz stops too, without
+        // recording the chapter.
+        let (outcome, output, saved) = run(&mut progress, "\n");
+
+        assert_eq!(outcome, Outcome::Stopped);
+        assert!(output.starts_with("Welcome back, carrying on from drop.\n"));
+        assert!(saved.is_empty());
+    }
+
+    #[test]
+    fn nothing_to_do_once_finished() {
+        let mut progress = Progress::default();
+        progress.record("coercion", Record::Skipped);
+        progress.record("drop", Record::Skipped);
+
+        let (outcome, output, saved) = run(&mut progress, "");
+
+        assert_eq!(outcome, Outcome::Finished);
+        assert_eq!(output, "Every chapter is finished, well done!\n");
+        assert!(saved.is_empty());
+    }
+}
@@ -0,0 +1,187 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::examples::Chapter;
+
+// How a chapter was finished.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Record {
+    Skipped,
+    Quizzed { correct: usize, asked: usize },
+}
+
+// The chapters a learner has finished, in the order they finished them.
+//
+// It's saved as a line per chapter, giving its name and either the quiz score
+// or skipped, e.g.
+//
+//  variable_bindings 2/2
+//  functions skipped
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Progress {
+    chapters: Vec<(String, Record)>,
+}
+
+impl Progress {
+    pub fn parse(source: &str) -> Result<Progress, String> {
+        let mut progress = Progress::default();
+
+        for (number, line) in source.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let record = line
+                .split_once(' ')
+                .and_then(|(chapter, record)| Some((chapter, parse_record(record.trim())?)))
+                .ok_or_else(|| format!("line {}: expected a chapter and a score", number + 1))?;
+
+            progress.record(record.0, record.1);
+        }
+
+        Ok(progress)
+    }
+
+    // Loads the progress saved at path, which is empty if nothing has been
+    // saved yet.
+    pub fn load(path: &Path) -> io::Result<Progress> {
+        match fs::read_to_string(path) {
+            Ok(source) => {
+                Progress::parse(&source).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Progress::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    // Records how a chapter went, replacing any earlier record for it.
+    pub fn record(&mut self, chapter: &str, record: Record) {
+        match self.chapters.iter_mut().find(|(name, _)| name == chapter) {
+            Some(existing) => existing.1 = record,
+            None => self.chapters.push((chapter.to_string(), record)),
+        }
+    }
+
+    pub fn get(&self, chapter: &str) -> Option<Record> {
+        self.chapters.iter().find(|(name, _)| name == chapter).map(|&(_, record)| record)
+    }
+
+    // The index of the first chapter that hasn't been finished, which is
+    // where a learner carries on from.
+    pub fn next(&self, chapters: &[Chapter]) -> Option<usize> {
+        chapters.iter().position(|&(name, _)| self.get(name).is_none())
+    }
+
+    // The total number of questions answered correctly and asked.
+    pub fn score(&self) -> (usize, usize) {
+        self.chapters.iter().fold((0, 0), |(correct, asked), (_, record)| match *record {
+            Record::Quizzed { correct: c, asked: a } => (correct + c, asked + a),
+            Record::Skipped => (correct, asked),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chapters.is_empty()
+    }
+}
+
+fn parse_record(record: &str) -> Option<Record> {
+    if record == "skipped" {
+        return Some(Record::Skipped);
+    }
+
+    let (correct, asked) = record.split_once('/')?;
+    let correct = correct.parse().ok()?;
+    let asked = asked.parse().ok()?;
+
+    if correct > asked {
+        return None;
+    }
+
+    Some(Record::Quizzed { correct, asked })
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Record::Skipped => write!(f, "skipped"),
+            Record::Quizzed { correct, asked } => write!(f, "{}/{}", correct, asked),
+        }
+    }
+}
+
+impl fmt::Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (chapter, record) in &self.chapters {
+            writeln!(f, "{} {}", chapter, record)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nothing() {}
+
+    const CHAPTERS: &[Chapter] = &[("one", nothing), ("two", nothing), ("three", nothing)];
+
+    #[test]
+    fn round_trips_through_text() {
+        let mut progress = Progress::default();
+        progress.record("one", Record::Quizzed { correct: 1, asked: 2 });
+        progress.record("two", Record::Skipped);
+
+        assert_eq!(progress.to_string(), "one 1/2\ntwo skipped\n");
+        assert_eq!(Progress::parse(&progress.to_string()), Ok(progress));
+    }
+
+    #[test]
+    fn parse_reports_the_bad_line() {
+        assert_eq!(Progress::parse("one 1/2\n\ntwo"), Err("line 3: expected a chapter and a score".to_string()));
+        assert!(Progress::parse("one 3/2").is_err());
+        assert!(Progress::parse("one lots").is_err());
+        assert_eq!(Progress::parse("# comment\n\n"), Ok(Progress::default()));
+    }
+
+    #[test]
+    fn carries_on_from_the_first_unfinished_chapter() {
+        let mut progress = Progress::default();
+        assert_eq!(progress.next(CHAPTERS), Some(0));
+
+        progress.record("one", Record::Skipped);
+        progress.record("three", Record::Quizzed { correct: 0, asked: 1 });
+        assert_eq!(progress.next(CHAPTERS), Some(1));
+
+        progress.record("two", Record::Quizzed { correct: 2, asked: 2 });
+        assert_eq!(progress.next(CHAPTERS), None);
+        assert_eq!(progress.score(), (2, 3));
+    }
+
+    #[test]
+    fn recording_a_chapter_again_replaces_it() {
+        let mut progress = Progress::default();
+        progress.record("one", Record::Skipped);
+        progress.record("one", Record::Quizzed { correct: 1, asked: 1 });
+
+        assert_eq!(progress.get("one"), Some(Record::Quizzed { correct: 1, asked: 1 }));
+        assert_eq!(progress.to_string(), "one 1/1\n");
+    }
+
+    #[test]
+    fn missing_file_is_no_progress() {
+        let path = Path::new("this/file/does/not/exist");
+
+        assert_eq!(Progress::load(path).unwrap(), Progress::default());
+    }
+}
@@ -0,0 +1,375 @@
+// The quiz questions asked after each chapter.
+//
+// Answers are compared ignoring case and extra whitespace, and any of the
+// listed answers counts as correct. The first is the one shown when a question
+// is answered wrongly.
+#[derive(Debug, PartialEq)]
+pub struct Question {
+    pub prompt: &'static str,
+    pub answers: &'static [&'static str],
+}
+
+impl Question {
+    pub fn check(&self, answer: &str) -> bool {
+        let answer = normalise(answer);
+
+        self.answers.iter().any(|a| normalise(a) == answer)
+    }
+
+    pub fn answer(&self) -> &'static str {
+        self.answers[0]
+    }
+}
+
+// Lower cases the answer and collapses runs of whitespace, and drops the
+// backticks someone used to Markdown might wrap code in.
+fn normalise(answer: &str) -> String {
+    answer
+        .trim()
+        .trim_matches('`')
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+// The questions for a chapter, empty if there aren't any.
+pub fn questions(chapter: &str) -> &'static [Question] {
+    QUIZZES
+        .iter()
+        .find(|&&(name, _)| name == chapter)
+        .map_or(&[], |&(_, questions)| questions)
+}
+
+const QUIZZES: &[(&str, &[Question])] = &[
+    ("variable_bindings", &[
+        Question { prompt: "After let (y, z) = (1, 2); what is the value of z?", answers: &["2"] },
+        Question { prompt: "Which keyword makes a binding mutable?", answers: &["mut"] },
+    ]),
+    ("functions", &[
+        Question {
+            prompt: "What type does a statement such as x + 1; evaluate to?",
+            answers: &["()", "unit", "the unit type"],
+        },
+        Question { prompt: "Which keyword returns early from a function?", answers: &["return"] },
+    ]),
+    ("primitive_types", &[
+        Question { prompt: "What type does the literal 42 default to?", answers: &["i32"] },
+        Question { prompt: "What type does the literal 1.0 default to?", answers: &["f64"] },
+        Question { prompt: "How many bytes does a char take?", answers: &["4", "four"] },
+    ]),
+    ("if_examples", &[
+        Question { prompt: "What is y after let y = if 5 > 3 { 10 } else { 20 };?", answers: &["10"] },
+        Question {
+            prompt: "Is if a statement or an expression in Rust?",
+            answers: &["an expression", "expression"],
+        },
+    ]),
+    ("loops", &[
+        Question { prompt: "What is the last value of x in for x in 0..10?", answers: &["9"] },
+        Question { prompt: "Which keyword skips to the next iteration of a loop?", answers: &["continue"] },
+    ]),
+    ("vectors", &[
+        Question { prompt: "What does vec![1, 2, 3].get(7) return?", answers: &["None"] },
+        Question { prompt: "Which type must a vector index be?", answers: &["usize"] },
+    ]),
+    ("ownership", &[
+        Question { prompt: "Can v be used after take(v)? (yes or no)", answers: &["no"] },
+        Question {
+            prompt: "Which trait means an i32 can still be used after being passed to a function?",
+            answers: &["Copy"],
+        },
+    ]),
+    ("references_and_borrowing", &[
+        Question {
+            prompt: "How many mutable references to a value can exist at the same time?",
+            answers: &["1", "one"],
+        },
+        Question { prompt: "What goes in front of a value to borrow it mutably?", answers: &["&mut"] },
+    ]),
+    ("lifetimes", &[
+        Question {
+            prompt: "Which character do lifetime names start with?",
+            answers: &["'", "an apostrophe", "apostrophe", "a single quote"],
+        },
+        Question { prompt: "Which lifetime do string literals have?", answers: &["'static", "static"] },
+    ]),
+    ("mutability", &[
+        Question {
+            prompt: "Which type from std::cell checks borrows at runtime rather than compile time?",
+            answers: &["RefCell", "std::cell::RefCell"],
+        },
+        Question {
+            prompt: "Can a struct declare some of its fields mut and the others not? (yes or no)",
+            answers: &["no"],
+        },
+    ]),
+    ("structs", &[
+        Question {
+            prompt: "Given p = Point { x: 3, y: 4 }, what is q.x after let q = Point { y: 9, ..p };?",
+            answers: &["3"],
+        },
+        Question {
+            prompt: "What is wrapping a single value in a tuple struct, as in struct Inches(i32), called?",
+            answers: &["the newtype pattern", "newtype", "newtype pattern"],
+        },
+    ]),
+    ("enums", &[
+        Question { prompt: "Which operator picks a variant out of an enum, as in Message___Quit?", answers: &["::"] },
+        Question { prompt: "Can two different enums have variants with the same name? (yes or no)", answers: &["yes"] },
+    ]),
+    ("matching", &[
+        Question { prompt: "Which pattern matches any value without binding it?", answers: &["_"] },
+        Question {
+            prompt: "What does match 3 { 1 | 2 => \"low\", _ => \"high\" } evaluate to?",
+            answers: &["high", "\"high\""],
+        },
+    ]),
+    ("patterns", &[
+        Question { prompt: "What does match (0, 1, 2) { (_, _, z) => z } evaluate to?", answers: &["2"] },
+        Question { prompt: "What ignores the rest of a struct's fields in a pattern?", answers: &[".."] },
+        Question {
+            prompt: "Which symbol binds a name to a value that matched a pattern, as in n ___ 1..=5?",
+            answers: &["@"],
+        },
+    ]),
+    ("method_syntax", &[
+        Question { prompt: "What is the first parameter of a method called?", answers: &["self"] },
+        Question {
+            prompt: "What are functions in an impl block that don't take self called?",
+            answers: &["associated functions", "associated function"],
+        },
+    ]),
+    ("strings", &[
+        Question { prompt: "What does \"€\".len() return?", answers: &["3"] },
+        Question { prompt: "What does \"€\".chars().count() return?", answers: &["1"] },
+        Question { prompt: "Which of &str and String can grow?", answers: &["String"] },
+    ]),
+    ("generics", &[
+        Question {
+            prompt: "Which function in std::mem exchanges two values?",
+            answers: &["swap", "mem::swap", "std::mem::swap"],
+        },
+        Question {
+            prompt: "How many type parameters does Result<T, E> have?",
+            answers: &["2", "two"],
+        },
+    ]),
+    ("generic_collections", &[
+        Question {
+            prompt: "Which trait lets .collect() build a Stack<T>?",
+            answers: &["FromIterator", "FromIterator<T>", "std::iter::FromIterator"],
+        },
+        Question {
+            prompt: "Which bound must T meet for a BinaryTree<T> to keep its values sorted?",
+            answers: &["Ord", "T: Ord", "std::cmp::Ord"],
+        },
+        Question {
+            prompt: "Which traversal of a binary search tree gives its values in sorted order?",
+            answers: &["in-order", "in order", "inorder"],
+        },
+    ]),
+    ("traits", &[
+        Question { prompt: "Which operator combines trait bounds, as in T: Display ___ Clone?", answers: &["+"] },
+        Question {
+            prompt: "Which keyword lets trait bounds be listed after a function's signature?",
+            answers: &["where"],
+        },
+    ]),
+    ("drop", &[
+        Question {
+            prompt: "firecracker is declared before tnt. Which of them is dropped first?",
+            answers: &["tnt"],
+        },
+        Question { prompt: "Which method does the Drop trait require?", answers: &["drop"] },
+    ]),
+    ("if_let_while_let", &[
+        Question {
+            prompt: "What does if let Some(x) = None::<i32> { x } else { 0 } evaluate to?",
+            answers: &["0"],
+        },
+        Question {
+            prompt: "Which kind of loop runs for as long as a pattern matches?",
+            answers: &["while let"],
+        },
+    ]),
+    ("trait_objects", &[
+        Question { prompt: "Which keyword marks a trait object, as in &___ Foo?", answers: &["dyn"] },
+        Question {
+            prompt: "Do generic functions with trait bounds use static or dynamic dispatch?",
+            answers: &["static", "static dispatch"],
+        },
+        Question { prompt: "Can Clone be used as a trait object? (yes or no)", answers: &["no"] },
+    ]),
+    ("closures", &[
+        Question { prompt: "What does (|x: i32| x + 1)(2) return?", answers: &["3"] },
+        Question {
+            prompt: "z is 5 and a move closure adds 5 to its z once. What is z outside the closure?",
+            answers: &["5"],
+        },
+        Question {
+            prompt: "Which keyword gives a closure ownership of the values it uses?",
+            answers: &["move"],
+        },
+        Question {
+            prompt: "Which trait does a closure implement if it can only be called once?",
+            answers: &["FnOnce"],
+        },
+    ]),
+    ("universal_function_call_syntax", &[
+        Question {
+            prompt: "Which keyword goes between the type and the trait in <Type ___ Trait>::method()?",
+            answers: &["as"],
+        },
+        Question {
+            prompt: "Baz implements f from both Foo and Bar. Does b.f() compile? (yes or no)",
+            answers: &["no"],
+        },
+    ]),
+    ("const_and_static", &[
+        Question {
+            prompt: "Which of const and static always refers to one fixed location in memory?",
+            answers: &["static"],
+        },
+        Question {
+            prompt: "What kind of block is needed to change a static mut?",
+            answers: &["unsafe", "an unsafe block"],
+        },
+    ]),
+    ("attributes", &[
+        Question { prompt: "Which attribute marks a function as a test?", answers: &["#[test]", "test"] },
+        Question {
+            prompt: "Does #[foo] apply to the next item or to the item it's inside of?",
+            answers: &["the next item", "next", "next item"],
+        },
+    ]),
+    ("type_aliases", &[
+        Question { prompt: "Which keyword declares a type alias?", answers: &["type"] },
+        Question {
+            prompt: "Given type Name = String; can a Name be compared with a String? (yes or no)",
+            answers: &["yes"],
+        },
+    ]),
+    ("coercion", &[
+        Question { prompt: "What does -56i8 as u8 print?", answers: &["200"] },
+        Question { prompt: "What does true as u8 print?", answers: &["1"] },
+        Question { prompt: "What does 300i32 as u8 print?", answers: &["44"] },
+        Question { prompt: "What does 'a' as u8 print?", answers: &["97"] },
+    ]),
+    ("associated_types", &[
+        Question { prompt: "Which keyword declares an associated type in a trait?", answers: &["type"] },
+        Question { prompt: "What is the Iterator trait's associated type called?", answers: &["Item"] },
+    ]),
+    ("unsized_types", &[
+        Question {
+            prompt: "Can a local variable have the type [i32], rather than &[i32]? (yes or no)",
+            answers: &["no"],
+        },
+        Question {
+            prompt: "Which field of a struct may be dynamically sized, the first or the last?",
+            answers: &["the last", "last"],
+        },
+        Question { prompt: "Which bound allows a type parameter to be unsized?", answers: &["?Sized"] },
+    ]),
+    ("operators_and_overloading", &[
+        Question { prompt: "Which trait overloads the + operator?", answers: &["Add", "std::ops::Add"] },
+        Question {
+            prompt: "Which module holds the traits for the operators that can be overloaded?",
+            answers: &["std::ops", "ops"],
+        },
+    ]),
+    ("deref_coercions", &[
+        Question { prompt: "String implements Deref with which Target?", answers: &["str"] },
+        Question {
+            prompt: "Can a &String be passed to a function that takes a &str? (yes or no)",
+            answers: &["yes"],
+        },
+    ]),
+    ("macros", &[
+        Question {
+            prompt: "Which fragment specifier matches an expression, as in $x:___?",
+            answers: &["expr"],
+        },
+        Question {
+            prompt: "Which repetition operator means zero or more times?",
+            answers: &["*", "$(...)*"],
+        },
+    ]),
+    ("raw_pointers", &[
+        Question { prompt: "What is the type of an immutable raw pointer to a T?", answers: &["*const T"] },
+        Question { prompt: "Is creating a raw pointer unsafe? (yes or no)", answers: &["no"] },
+        Question {
+            prompt: "What kind of block is needed to dereference a raw pointer?",
+            answers: &["unsafe", "an unsafe block"],
+        },
+    ]),
+    ("concurrency", &[
+        Question {
+            prompt: "Which function starts a new thread?",
+            answers: &["thread::spawn", "spawn", "std::thread::spawn"],
+        },
+        Question { prompt: "Which of Rc and Arc can be sent to another thread?", answers: &["Arc"] },
+        Question {
+            prompt: "Which marker trait means a value can be moved to another thread?",
+            answers: &["Send"],
+        },
+    ]),
+    ("iterators", &[
+        Question { prompt: "What does (1..=4).fold(0, |sum, x| sum + x) return?", answers: &["10"] },
+        Question { prompt: "What does next return once an iterator is exhausted?", answers: &["None"] },
+        Question { prompt: "What does (1..).take(3).count() return?", answers: &["3"] },
+    ]),
+    ("error_handling", &[
+        Question { prompt: "Which operator returns early with the error from a Result?", answers: &["?"] },
+        Question { prompt: "What does Some(2).map(|x| x * 2) return?", answers: &["Some(4)"] },
+        Question { prompt: "What does None.unwrap_or(7) return?", answers: &["7"] },
+    ]),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::examples::CHAPTERS;
+
+    #[test]
+    fn every_chapter_has_a_quiz() {
+        for &(chapter, _) in CHAPTERS {
+            assert!(!questions(chapter).is_empty(), "no questions for {}", chapter);
+        }
+
+        assert_eq!(QUIZZES.len(), CHAPTERS.len());
+        assert!(questions("nonsense").is_empty());
+    }
+
+    #[test]
+    fn answers_are_compared_loosely() {
+        let question = &questions("coercion")[0];
+
+        assert!(question.check("200"));
+        assert!(question.check("  200\n"));
+        assert!(question.check("`200`"));
+        assert!(!question.check("-56"));
+        assert!(questions("if_let_while_let")[1].check("While  Let"));
+        assert_eq!(question.answer(), "200");
+    }
+
+    // The answers that can be worked out are checked against the compiler, so
+    // a wrong answer in the quiz can't go unnoticed.
+    #[test]
+    fn answers_agree_with_rust() {
+        let answer = |chapter: &str, n: usize| questions(chapter)[n].answer();
+
+        assert_eq!(answer("coercion", 0), (-56i8 as u8).to_string());
+        assert_eq!(answer("coercion", 1), (true as u8).to_string());
+        assert_eq!(answer("coercion", 2), (300i32 as u8).to_string());
+        assert_eq!(answer("coercion", 3), (b'a').to_string());
+        assert_eq!(answer("strings", 0), "€".len().to_string());
+        assert_eq!(answer("strings", 1), "€".chars().count().to_string());
+        assert_eq!(answer("primitive_types", 2), std::mem::size_of::<char>().to_string());
+        assert_eq!(answer("loops", 0), (0..10).last().unwrap().to_string());
+        assert_eq!(answer("iterators", 0), (1..=4).sum::<i32>().to_string());
+        assert_eq!(answer("iterators", 2), (1..).take(3).count().to_string());
+        assert_eq!(answer("error_handling", 1), format!("{:?}", Some(2).map(|x| x * 2)));
+    }
+}
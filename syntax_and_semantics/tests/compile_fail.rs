@@ -0,0 +1,120 @@
+// Several chapters describe code that the compiler rejects. Each of those
+// snippets lives in tests/compile_fail as a standalone library crate, with a
+// header comment naming the error it should fail with.
+//
+//  // error-code: E0605
+//  // error-message: no rules expected `z`
+//
+// error-code is preferred. error-message is for errors, such as those from
+// macro expansion, that have no code. This test compiles every fixture with
+// rustc and checks that compilation fails with the expected errors.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+struct Expectation {
+    codes: Vec<String>,
+    messages: Vec<String>,
+}
+
+fn expectation(source: &str) -> Expectation {
+    let mut codes = Vec::new();
+    let mut messages = Vec::new();
+
+    for line in source.lines() {
+        let line = line.trim();
+
+        if let Some(code) = line.strip_prefix("// error-code:") {
+            codes.push(code.trim().to_string());
+        } else if let Some(message) = line.strip_prefix("// error-message:") {
+            messages.push(message.trim().to_string());
+        }
+    }
+
+    Expectation { codes, messages }
+}
+
+fn fixtures() -> Vec<PathBuf> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/compile_fail");
+
+    let mut fixtures: Vec<PathBuf> = fs::read_dir(&dir)
+        .expect("compile_fail fixture directory is missing")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|e| e == "rs"))
+        .collect();
+
+    fixtures.sort();
+    fixtures
+}
+
+// Returns a description of what went wrong, or None if the fixture failed to
+// compile in the expected way.
+fn check(fixture: &Path, out_dir: &Path) -> Option<String> {
+    let source = fs::read_to_string(fixture).unwrap();
+    let expected = expectation(&source);
+
+    if expected.codes.is_empty() && expected.messages.is_empty() {
+        return Some("no error-code or error-message header".to_string());
+    }
+
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+
+    let output = Command::new(rustc)
+        .args(["--edition", "2021", "--crate-type", "lib", "--emit", "metadata"])
+        .args(["--error-format", "short", "-A", "warnings"])
+        .arg("--out-dir")
+        .arg(out_dir)
+        .arg(fixture)
+        .output()
+        .expect("failed to run rustc");
+
+    if output.status.success() {
+        return Some("compiled successfully".to_string());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    let missing: Vec<String> = expected.codes
+        .iter()
+        .map(|code| format!("error[{}]", code))
+        .chain(expected.messages.iter().cloned())
+        .filter(|wanted| !stderr.contains(wanted.as_str()))
+        .collect();
+
+    if missing.is_empty() {
+        None
+    } else {
+        Some(format!("expected {:?} but rustc reported:\n{}", missing, stderr))
+    }
+}
+
+#[test]
+fn compile_fail_fixtures() {
+    let out_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("compile_fail");
+    fs::create_dir_all(&out_dir).unwrap();
+
+    let fixtures = fixtures();
+    assert!(!fixtures.is_empty(), "no compile_fail fixtures found");
+
+    // Check every fixture before failing, so that one run reports them all.
+    let failures: Vec<String> = fixtures
+        .iter()
+        .filter_map(|fixture| {
+            check(fixture, &out_dir)
+                .map(|problem| format!("{}: {}", fixture.display(), problem))
+        })
+        .collect();
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n\n"));
+}
+
+#[test]
+fn expectation_headers_are_parsed() {
+    let source = "// some prose\n// error-code: E0605\n  // error-message: oops \nfn f() {}";
+    let expected = expectation(source);
+
+    assert_eq!(expected.codes, vec!["E0605"]);
+    assert_eq!(expected.messages, vec!["oops"]);
+}
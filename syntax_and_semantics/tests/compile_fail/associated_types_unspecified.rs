@@ -0,0 +1,12 @@
+// associated_types: a trait object must give concrete types for the trait's
+// associated types.
+// error-code: E0191
+
+pub trait Graph {
+    type N;
+    type E;
+
+    fn has_edge(&self, start: &Self::N, end: &Self::N) -> bool;
+}
+
+pub fn graph(g: &dyn Graph) {}
@@ -0,0 +1,13 @@
+// closures: while a closure holds a mutable borrow of y, y cannot be borrowed
+// again.
+// error-code: E0499
+
+pub fn borrow() {
+    let mut y = 5;
+
+    let mut add_y = |x| y += x;
+    let other = &mut y;
+
+    add_y(5);
+    *other += 1;
+}
@@ -0,0 +1,11 @@
+// closures: a closure that moves out a value it captured is FnOnce, so calling
+// it a second time uses a value that's already been moved.
+// error-code: E0382
+
+pub fn twice() {
+    let name = String::from("Ferris");
+    let take_name = move || name;
+
+    take_name();
+    take_name();
+}
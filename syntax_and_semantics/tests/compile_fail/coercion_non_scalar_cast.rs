@@ -0,0 +1,9 @@
+// coercion: `as` only performs safe casts, so four bytes cannot be cast into a
+// u32. mem::transmute is needed instead.
+// error-code: E0605
+
+pub fn cast() -> u32 {
+    let c = [0u8, 0u8, 0u8, 0u8];
+    let d = c as u32; // Four u8s makes a u32.
+    d
+}
@@ -0,0 +1,10 @@
+// coercion: transmute checks that both types are the same size, so four bytes
+// cannot be transmuted into an i64.
+// error-code: E0512
+
+use std::mem;
+
+pub fn transmute() -> i64 {
+    let c = [0u8, 0u8, 0u8, 0u8];
+    unsafe { mem::transmute(c) }
+}
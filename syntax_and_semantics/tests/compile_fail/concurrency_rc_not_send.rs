@@ -0,0 +1,13 @@
+// concurrency: Rc is not Send, so it cannot be moved into another thread.
+// error-code: E0277
+
+use std::rc::Rc;
+use std::thread;
+
+pub fn spawn() {
+    let counter = Rc::new(0);
+
+    thread::spawn(move || {
+        println!("{}", counter);
+    });
+}
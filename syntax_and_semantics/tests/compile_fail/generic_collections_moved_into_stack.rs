@@ -0,0 +1,22 @@
+// generic_collections: pushing a value onto a stack moves it in, so the
+// original binding can no longer be used.
+// error-code: E0382
+
+pub struct Stack<T> {
+    items: Vec<T>,
+}
+
+impl<T> Stack<T> {
+    pub fn push(&mut self, value: T) {
+        self.items.push(value);
+    }
+}
+
+pub fn moved() {
+    let mut names = Stack { items: Vec::new() };
+    let name = String::from("Ferris");
+
+    names.push(name);
+
+    println!("{}", name);
+}
@@ -0,0 +1,9 @@
+// lifetimes: the less_than function will not compile without lifetime
+// annotations, since the compiler can't tell which parameter the result
+// borrows from.
+// error-code: E0106
+
+pub fn less_than(data: &mut Vec<i32>, limit: &i32) -> &Vec<i32> {
+    data.retain(|&d| d < *limit);
+    data
+}
@@ -0,0 +1,13 @@
+// macros: a macro invocation must match one of the macro's rules exactly.
+// Macro expansion errors have no error code, so the message is checked.
+// error-message: no rules expected `z`
+
+macro_rules! foo {
+    (x => $e:expr) => (println!("mode X: {}", $e));
+    (y => $e:expr) => (println!("mode Y: {}", $e));
+}
+
+pub fn call() {
+    foo!(x => 2);
+    foo!(z => 4);
+}
@@ -0,0 +1,7 @@
+// mutability: variables are immutable by default.
+// error-code: E0384
+
+pub fn assign() {
+    let x = 1;
+    x = 2;
+}
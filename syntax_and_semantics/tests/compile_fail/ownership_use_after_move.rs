@@ -0,0 +1,14 @@
+// ownership: after v has been passed to take, v can no longer be used.
+// error-code: E0382
+
+fn take(v: Vec<i32>) {}
+
+pub fn ownership() {
+    let v = vec![1, 2, 3];
+
+    take(v);
+
+    for i in v {
+        println!("take iterating over v, at element {}", i);
+    }
+}
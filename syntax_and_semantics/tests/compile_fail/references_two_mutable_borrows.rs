@@ -0,0 +1,13 @@
+// references_and_borrowing: there can only be one mutable reference to a value
+// at a time, which is why the chapter introduces an extra scope.
+// error-code: E0499
+
+pub fn borrow() {
+    let mut x = 5;
+
+    let y = &mut x;
+    let z = &mut x;
+
+    *y += 1;
+    *z += 1;
+}
@@ -0,0 +1,7 @@
+// strings: because strings are UTF-8 they do not support indexing.
+// error-code: E0277
+
+pub fn first() {
+    let s = "Hello";
+    let t = s[0];
+}
@@ -0,0 +1,4 @@
+// trait_objects: Clone requires Self: Sized, so it is not object safe.
+// error-code: E0038
+
+pub fn clone_it(x: &dyn Clone) {}
@@ -0,0 +1,9 @@
+// trait_objects: a method with type parameters is not object safe, so a trait
+// containing one cannot be made into a trait object.
+// error-code: E0038
+
+pub trait Foo {
+    fn method<T>(&self, t: T);
+}
+
+pub fn do_something(x: &dyn Foo) {}
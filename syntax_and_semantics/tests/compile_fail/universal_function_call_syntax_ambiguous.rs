@@ -0,0 +1,26 @@
+// universal_function_call_syntax: with two applicable methods called f, b.f()
+// is ambiguous.
+// error-code: E0034
+
+trait Foo {
+    fn f(&self);
+}
+
+trait Bar {
+    fn f(&self);
+}
+
+struct Baz;
+
+impl Foo for Baz {
+    fn f(&self) {}
+}
+
+impl Bar for Baz {
+    fn f(&self) {}
+}
+
+pub fn call() {
+    let b = Baz;
+    b.f();
+}
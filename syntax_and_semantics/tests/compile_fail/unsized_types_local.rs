@@ -0,0 +1,6 @@
+// unsized_types: variables cannot have dynamically sized types.
+// error-code: E0277
+
+pub fn local(slice: &[i32]) {
+    let values: [i32] = *slice;
+}